@@ -0,0 +1,29 @@
+use std::{hint::black_box, str::FromStr};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use json::Value;
+use json_bench::corpus;
+
+fn minify(c: &mut Criterion) {
+    let input = corpus::records(200_000);
+    let mut group = c.benchmark_group("minify");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    group.bench_function("parse then to_string", |b| {
+        b.iter(|| Value::from_str(black_box(&input)).unwrap().to_string())
+    });
+    group.bench_function("minify", |b| {
+        b.iter(|| json::minify(black_box(&input)).unwrap())
+    });
+    group.bench_function("parse", |b| {
+        b.iter(|| Value::from_str(black_box(&input)).unwrap())
+    });
+    group.bench_function("validate", |b| {
+        b.iter(|| json::validate(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, minify);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+use std::{hint::black_box, str::FromStr};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use json::{parse_large_array, Value};
+use json_bench::corpus;
+
+fn large_array(c: &mut Criterion) {
+    let input = corpus::records(1_000_000);
+    let mut group = c.benchmark_group("parse_large_array");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    group.bench_function("serial", |b| {
+        b.iter(|| Value::from_str(black_box(&input)).unwrap())
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| parse_large_array(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, large_array);
+criterion_main!(benches);
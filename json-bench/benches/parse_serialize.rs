@@ -0,0 +1,42 @@
+use std::{hint::black_box, str::FromStr};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use json::Value;
+use json_bench::corpus;
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in corpus::all() {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("json", name), &input, |b, x| {
+            b.iter(|| Value::from_str(black_box(x)).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("serde_json", name), &input, |b, x| {
+            b.iter(|| serde_json::from_str::<serde_json::Value>(black_box(x)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+    for (name, input) in corpus::all() {
+        let value = Value::from_str(&input).unwrap();
+        group.throughput(Throughput::Bytes(value.to_string().len() as u64));
+        group.bench_with_input(BenchmarkId::new("json", name), &value, |b, x| {
+            b.iter(|| black_box(x).to_string())
+        });
+
+        {
+            let value = serde_json::from_str::<serde_json::Value>(&input).unwrap();
+            group.bench_with_input(BenchmarkId::new("serde_json", name), &value, |b, x| {
+                b.iter(|| serde_json::to_string(black_box(x)).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, serialize);
+criterion_main!(benches);
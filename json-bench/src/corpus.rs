@@ -0,0 +1,179 @@
+//! Generators for the documents used by the benchmarks.
+//! Everything is generated from a fixed seed, so every run benchmarks the same input.
+
+/// A small xorshift generator, so the corpus doesn't depend on a random number crate.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, max: u64) -> u64 {
+        self.next() % max
+    }
+
+    fn word(&mut self) -> &'static str {
+        const WORDS: &[&str] = &[
+            "crab", "rust", "json", "stack", "half", "parse", "value", "array", "object", "string",
+            "number", "ferris", "cargo", "trait", "borrow", "lifetime",
+        ];
+        WORDS[self.below(WORDS.len() as u64) as usize]
+    }
+
+    fn sentence(&mut self, words: usize) -> String {
+        (0..words)
+            .map(|_| self.word())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Gets every corpus document with its name.
+pub fn all() -> Vec<(&'static str, String)> {
+    vec![
+        ("small_object", small_object()),
+        ("numbers", numbers(100_000)),
+        ("nested", nested(100)),
+        ("strings", strings(20_000)),
+        ("twitter", twitter(2_000)),
+        ("citm_catalog", citm_catalog(1_000)),
+    ]
+}
+
+/// A single small object, like a typical API request body.
+pub fn small_object() -> String {
+    r#"{"id": 1234, "name": "half-stack", "active": true, "score": 98.5, "tags": ["json", "web"], "owner": null}"#
+        .to_owned()
+}
+
+/// A large flat array of integers and floats.
+pub fn numbers(count: usize) -> String {
+    let mut rng = Rng(0x5eed);
+    let items = (0..count)
+        .map(|i| match i % 3 {
+            0 => rng.next().to_string(),
+            1 => format!("-{}", rng.below(1_000_000)),
+            _ => format!("{}.{}", rng.below(1000), rng.below(1000)),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
+
+/// Arrays and objects nested `depth` levels deep.
+pub fn nested(depth: usize) -> String {
+    let mut out = String::new();
+    for i in 0..depth {
+        match i % 2 {
+            0 => out.push_str(&format!(r#"{{"level": {i}, "child": "#)),
+            _ => out.push_str(&format!("[{i}, ")),
+        }
+    }
+    out.push_str("null");
+    for i in (0..depth).rev() {
+        out.push(if i % 2 == 0 { '}' } else { ']' });
+    }
+    out
+}
+
+/// An array of strings full of escape sequences and non-ASCII text.
+pub fn strings(count: usize) -> String {
+    const PARTS: &[&str] = &[
+        r#"\"quoted\""#,
+        r"tab\tseparated",
+        r"line\nbreak",
+        r"back\\slash ",
+        r"\/path\/to",
+        "caf\u{e9} \u{1f980} \u{4e2d}\u{6587}",
+        "plain text",
+    ];
+
+    let mut rng = Rng(0xe5ca);
+    let items = (0..count)
+        .map(|_| {
+            let parts = (0..4).map(|_| PARTS[rng.below(PARTS.len() as u64) as usize]);
+            format!("\"{}\"", parts.collect::<Vec<_>>().join(" "))
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
+
+/// A `twitter.json`-style search result, with statuses holding nested user objects.
+pub fn twitter(statuses: usize) -> String {
+    let mut rng = Rng(0x7417);
+    let items = (0..statuses)
+        .map(|i| {
+            let user = format!(
+                r#"{{"id": {}, "screen_name": "{}_{}", "name": "{}", "followers_count": {}, "verified": {}, "description": "{}", "profile_image_url": "https:\/\/example.com\/{}.png"}}"#,
+                rng.next(),
+                rng.word(),
+                i,
+                rng.sentence(2),
+                rng.below(100_000),
+                rng.below(10) == 0,
+                rng.sentence(8),
+                i
+            );
+            format!(
+                r#"{{"id": {}, "id_str": "{}", "text": "{} #{}", "created_at": "Sun Aug 31 00:29:15 +0000 2014", "user": {user}, "retweet_count": {}, "favorited": false, "entities": {{"hashtags": [{{"text": "{}", "indices": [1, 5]}}], "urls": [], "user_mentions": []}}, "in_reply_to_status_id": null, "lang": "en"}}"#,
+                rng.next(),
+                rng.next(),
+                rng.sentence(12),
+                rng.word(),
+                rng.below(500),
+                rng.word()
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        r#"{{"statuses": [{}], "search_metadata": {{"count": {statuses}, "completed_in": 0.087, "query": "rust"}}}}"#,
+        items.join(", ")
+    )
+}
+
+/// A `citm_catalog.json`-style document, with large maps of ids to events and mostly numeric data.
+pub fn citm_catalog(events: usize) -> String {
+    let mut rng = Rng(0xc177);
+    let names = (0..events)
+        .map(|i| format!(r#""{}": "{}""#, 100_000 + i, rng.sentence(3)))
+        .collect::<Vec<_>>();
+    let items = (0..events)
+        .map(|i| {
+            let prices = (0..rng.below(6) + 1)
+                .map(|_| {
+                    format!(
+                        r#"{{"amount": {}, "audienceSubCategoryId": {}, "seatCategoryId": {}}}"#,
+                        rng.below(100_000),
+                        337_100_890 + rng.below(10),
+                        338_937_295 + rng.below(100)
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!(
+                r#""{id}": {{"id": {id}, "name": "{}", "description": null, "logo": null, "subTopicIds": [{}, {}], "topicIds": [{}], "subjectCode": null, "prices": [{}]}}"#,
+                rng.sentence(4),
+                337_184_262 + rng.below(100),
+                337_184_283 + rng.below(100),
+                324_846_099 + rng.below(100),
+                prices.join(", "),
+                id = 100_000 + i
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        r#"{{"areaNames": {{{}}}, "events": {{{}}}, "venueNames": {{"PLEYEL_PLEYEL": "Salle Pleyel"}}}}"#,
+        names.join(", "),
+        items.join(", ")
+    )
+}
+
+/// A large pretty-printed array of small records, like a bulk export.
+pub fn records(count: usize) -> String {
+    let items = (0..count)
+        .map(|i| format!(r#"{{"id": {i}, "name": "item\t{i}", "tags": ["a", "b"], "ok": true}}"#))
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(",\n  "))
+}
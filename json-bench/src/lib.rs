@@ -0,0 +1,4 @@
+//! Benchmarks for the json crate, kept in their own crate so comparing against `serde_json` doesn't add it to json's dependencies.
+//! Run with `cargo bench -p json-bench --features rayon`, which the parallel parsing benchmark needs.
+
+pub mod corpus;
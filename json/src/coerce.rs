@@ -0,0 +1,160 @@
+//! Accessors for values that may have been encoded as strings.
+
+use std::{str::FromStr, time::Duration};
+
+use crate::{JsonPath, Number, PathSegment, Value};
+
+impl Value {
+    /// Gets the value as a `u64`, from either a number or a string containing one.
+    /// Strings are trimmed before parsing.
+    pub fn coerce_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(Number::UInt(x)) => Some(*x),
+            Value::Number(Number::Int(x)) => u64::try_from(*x).ok(),
+            Value::Number(Number::UInt128(x)) => u64::try_from(*x).ok(),
+            Value::Number(Number::Int128(x)) => u64::try_from(*x).ok(),
+            Value::Number(Number::Raw(x)) => x.parse().ok(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as an `i64`, from either a number or a string containing one.
+    /// Strings are trimmed before parsing.
+    pub fn coerce_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(Number::UInt(x)) => i64::try_from(*x).ok(),
+            Value::Number(Number::Int(x)) => Some(*x),
+            Value::Number(Number::UInt128(x)) => i64::try_from(*x).ok(),
+            Value::Number(Number::Int128(x)) => i64::try_from(*x).ok(),
+            Value::Number(Number::Raw(x)) => x.parse().ok(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as an `f64`, from either a number or a string containing one.
+    /// Strings are trimmed before parsing and must be finite (so no `NaN` or `inf`).
+    pub fn coerce_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(Number::UInt(x)) => Some(*x as f64),
+            Value::Number(Number::Int(x)) => Some(*x as f64),
+            Value::Number(Number::UInt128(x)) => Some(*x as f64),
+            Value::Number(Number::Int128(x)) => Some(*x as f64),
+            Value::Number(Number::Float(x)) => Some(*x),
+            Value::Number(Number::Raw(x)) => x.parse().ok(),
+            Value::String(s) => s.trim().parse().ok().filter(|x: &f64| x.is_finite()),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `bool`, from either a bool or a string containing `true` or `false`.
+    /// Strings are trimmed and compared case-insensitively.
+    pub fn coerce_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            Value::String(s) if s.trim().eq_ignore_ascii_case("true") => Some(true),
+            Value::String(s) if s.trim().eq_ignore_ascii_case("false") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a [`Duration`], from either a number of seconds or a string with a unit.
+    /// Ex. `1.5`, `"1500ms"`, `"2s"` or `"5m"`
+    /// The units are `ns`, `us` (or `µs`), `ms`, `s`, `m`, `h` and `d`.
+    /// Returns `None` for unknown units and negative or non-finite durations.
+    pub fn as_duration(&self) -> Option<Duration> {
+        let secs = match self {
+            Value::Number(_) => self.coerce_f64()?,
+            Value::String(s) => {
+                let s = s.trim();
+                let (num, unit) = s.split_at(s.find(char::is_alphabetic)?);
+                let scale = match unit {
+                    "ns" => 1e-9,
+                    "us" | "µs" => 1e-6,
+                    "ms" => 1e-3,
+                    "s" => 1.0,
+                    "m" => 60.0,
+                    "h" => 60.0 * 60.0,
+                    "d" => 24.0 * 60.0 * 60.0,
+                    _ => return None,
+                };
+                num.trim_end().parse::<f64>().ok()? * scale
+            }
+            _ => return None,
+        };
+
+        Duration::try_from_secs_f64(secs).ok()
+    }
+
+    /// Gets the value as a [`Number`], from a number, a string containing one, or a bool.
+    /// Strings are trimmed and parsed with [`Number::from_str`], and must be finite if they parse as floats.
+    /// Bools become `0` or `1`.
+    pub fn coerce_to_number(&self) -> Option<Number> {
+        match self {
+            Value::Number(x) => Some(x.clone()),
+            Value::String(s) => match Number::from_str(s.trim()).ok()? {
+                Number::Float(x) if !x.is_finite() => None,
+                x => Some(x),
+            },
+            Value::Bool(b) => Some(Number::UInt(*b as u64)),
+            _ => None,
+        }
+    }
+
+    /// Recursively converts string leaves that parse cleanly as numbers into [`Value::Number`]s.
+    /// Strings with leading zeros (like ZIP codes or `007`) are left alone, as converting them would lose data.
+    /// Use [`Value::coerce_numeric_strings_with`] to choose which strings are converted.
+    pub fn coerce_numeric_strings(&mut self) {
+        self.coerce_numeric_strings_with(|_, s| !has_leading_zero(s));
+    }
+
+    /// Like [`Value::coerce_numeric_strings`], but only converts strings where `filter` returns true.
+    /// The filter is given the path to the string and its trimmed contents, so it can skip keys like IDs.
+    /// Only strings that parse cleanly as finite numbers are passed to the filter.
+    pub fn coerce_numeric_strings_with(&mut self, mut filter: impl FnMut(&JsonPath, &str) -> bool) {
+        fn coerce(
+            path: &mut JsonPath,
+            value: &mut Value,
+            f: &mut impl FnMut(&JsonPath, &str) -> bool,
+        ) {
+            match value {
+                Value::String(s) => {
+                    let trimmed = s.trim();
+                    // Infinite floats can't be written back as JSON, the same as `coerce_to_number`
+                    let number = Number::from_str(trimmed)
+                        .ok()
+                        .filter(|x| !matches!(x, Number::Float(x) if !x.is_finite()));
+                    if let Some(x) = number {
+                        if f(path, trimmed) {
+                            *value = Value::Number(x);
+                        }
+                    }
+                }
+                Value::Array(a) => {
+                    for (i, x) in a.iter_mut().enumerate() {
+                        path.push(PathSegment::Index(i));
+                        coerce(path, x, f);
+                        path.pop();
+                    }
+                }
+                Value::Object(o) => {
+                    for (k, x) in o.iter_mut() {
+                        path.push(PathSegment::Key(k.to_owned()));
+                        coerce(path, x, f);
+                        path.pop();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        coerce(&mut JsonPath::new(), self, &mut filter);
+    }
+}
+
+/// Checks if a number has a redundant leading zero, like `007` or `-01`.
+fn has_leading_zero(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s).as_bytes();
+    digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit()
+}
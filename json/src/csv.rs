@@ -0,0 +1,174 @@
+//! Converting between arrays of flat objects and [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180) CSV.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::Value;
+
+/// Errors that can occur while converting to or from CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvError {
+    /// The value isn't an array of objects.
+    /// Contains the index of the first element that isn't an object, or `None` if the value isn't an array.
+    NotTable(Option<usize>),
+    /// A cell held an array or object, which can't be written to CSV.
+    NestedValue {
+        /// The index of the row.
+        row: usize,
+        /// The key of the cell.
+        key: String,
+    },
+    /// A quoted field was never closed.
+    /// Contains the position of the opening quote.
+    UnterminatedQuote(usize),
+    /// A quote was found somewhere other than the start of a field, or a closing quote wasn't followed by a separator.
+    /// Contains the position of the unexpected character.
+    UnexpectedQuote(usize),
+    /// A row has a different number of fields than the header.
+    /// Contains the index of the row, not counting the header.
+    RowLength(usize),
+}
+
+/// Converts an array of flat objects into CSV.
+/// The header is the union of the keys of every row, in sorted order.
+/// Rows are separated with `\r\n`, as in the RFC.
+///
+/// Strings are written as they are, quoted if they contain a comma, quote or newline.
+/// Null and missing values are written as empty cells, while empty strings are written as `""` so they survive [`from_csv`].
+/// Fails with [`CsvError::NestedValue`] if any cell is an array or object.
+pub fn to_csv(value: &Value) -> Result<String, CsvError> {
+    let rows = value.as_array().ok_or(CsvError::NotTable(None))?;
+    let rows = rows
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x.as_object().ok_or(CsvError::NotTable(Some(i))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let header = rows.iter().flat_map(|x| x.keys()).collect::<BTreeSet<_>>();
+    let mut out = String::new();
+    write_row(&mut out, header.iter().map(|x| escape(x)));
+
+    for (i, row) in rows.iter().enumerate() {
+        let cells = header
+            .iter()
+            .map(|&key| match row.get(key) {
+                None | Some(Value::Null) => Ok(String::new()),
+                Some(Value::String(s)) => Ok(escape(s)),
+                Some(Value::Array(_) | Value::Object(_)) => Err(CsvError::NestedValue {
+                    row: i,
+                    key: key.to_owned(),
+                }),
+                Some(x) => Ok(x.to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        write_row(&mut out, cells.into_iter());
+    }
+
+    Ok(out)
+}
+
+/// Parses CSV with a header row into an array of objects, one per row, keyed by the header.
+/// Both `\r\n` and `\n` line endings are accepted, and a blank line at the end of the input is ignored.
+///
+/// Every cell is read as a string, except for unquoted empty cells, which are left out of their row.
+/// Use [`Value::coerce_numeric_strings`] on the result to turn numeric cells into numbers.
+pub fn from_csv(input: &str) -> Result<Value, CsvError> {
+    let mut records = parse(input)?.into_iter();
+    let header = match records.next() {
+        Some(x) => x,
+        None => return Ok(Value::Array(Vec::new())),
+    };
+
+    let mut out = Vec::new();
+    for (i, record) in records.enumerate() {
+        if record.len() != header.len() {
+            return Err(CsvError::RowLength(i));
+        }
+
+        let row = header
+            .iter()
+            .zip(record)
+            .filter(|(_, cell)| cell.quoted || !cell.text.is_empty())
+            .map(|(key, cell)| (key.text.to_owned(), Value::String(cell.text)))
+            .collect::<BTreeMap<_, _>>();
+        out.push(Value::Object(row));
+    }
+
+    Ok(Value::Array(out))
+}
+
+struct Field {
+    text: String,
+    quoted: bool,
+}
+
+/// Splits CSV into records of fields.
+fn parse(input: &str) -> Result<Vec<Vec<Field>>, CsvError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while chars.peek().is_some() {
+        let mut field = Field {
+            text: String::new(),
+            quoted: false,
+        };
+
+        if let Some(&(start, '"')) = chars.peek() {
+            chars.next();
+            field.quoted = true;
+            loop {
+                match chars.next() {
+                    Some((_, '"')) if matches!(chars.peek(), Some((_, '"'))) => {
+                        chars.next();
+                        field.text.push('"');
+                    }
+                    Some((_, '"')) => break,
+                    Some((_, c)) => field.text.push(c),
+                    None => return Err(CsvError::UnterminatedQuote(start)),
+                }
+            }
+        }
+
+        loop {
+            match chars.next() {
+                Some((_, ',')) => {
+                    record.push(field);
+                    break;
+                }
+                Some((_, '\r')) if matches!(chars.peek(), Some((_, '\n'))) => {}
+                None | Some((_, '\n')) => {
+                    record.push(field);
+                    records.push(std::mem::take(&mut record));
+                    break;
+                }
+                Some((pos, c)) if c == '"' || field.quoted => {
+                    return Err(CsvError::UnexpectedQuote(pos));
+                }
+                Some((_, c)) => field.text.push(c),
+            }
+        }
+    }
+
+    // A blank line at the end, as many exporters write, isn't a record
+    if let Some([x]) = records.last().map(Vec::as_slice) {
+        if !x.quoted && x.text.is_empty() {
+            records.pop();
+        }
+    }
+
+    Ok(records)
+}
+
+fn write_row(out: &mut String, cells: impl Iterator<Item = String>) {
+    out.push_str(&cells.collect::<Vec<_>>().join(","));
+    out.push_str("\r\n");
+}
+
+/// Quotes a cell if it is empty or contains a comma, quote or newline.
+fn escape(cell: &str) -> String {
+    if cell.is_empty() || cell.contains([',', '"', '\r', '\n']) {
+        return format!(r#""{}""#, cell.replace('"', r#""""#));
+    }
+
+    cell.to_owned()
+}
@@ -0,0 +1,398 @@
+//! A lossless, format-preserving JSON document.
+//!
+//! Unlike [`Value`], a [`JsonDocument`] keeps the original source text around, along with the byte span of every node.
+//! Everything between nodes (whitespace, commas, `//` and `/* */` comments) is trivia that is never touched, so editing
+//! one value leaves the rest of the file byte-for-byte identical.
+
+use std::{
+    fmt::{self, Display},
+    ops::Range,
+    str::FromStr,
+};
+
+use crate::{error::JsonResult, pointer, Error, Value};
+
+/// A parsed JSON document that remembers its exact formatting.
+#[derive(Debug, Clone)]
+pub struct JsonDocument {
+    text: String,
+    root: Node,
+}
+
+/// Errors that can occur while editing a [`JsonDocument`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum EditError {
+    /// The JSON Pointer was not valid.
+    InvalidPointer,
+    /// Nothing exists at the pointer and it can't be created.
+    /// New keys can only be added to existing objects.
+    NotFound,
+    /// The edit wouldn't leave valid JSON, ex. setting a `NaN` or infinite float.
+    /// The document is left unchanged.
+    Invalid,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    span: Range<usize>,
+    kind: NodeKind,
+}
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    Scalar(Value),
+    Array(Vec<Node>),
+    Object(Vec<Member>),
+}
+
+#[derive(Debug, Clone)]
+struct Member {
+    key: String,
+    key_span: Range<usize>,
+    value: Node,
+}
+
+impl JsonDocument {
+    /// Parses `input` into a document.
+    /// In addition to standard JSON, `//` line comments and `/* */` block comments are allowed as trivia.
+    pub fn parse(input: &str) -> JsonResult<Self> {
+        let mut scanner = Scanner::new(input);
+        scanner.trivia()?;
+        let root = scanner.node()?;
+        scanner.trivia()?;
+
+        if scanner.pos < scanner.len() {
+            return Err(Error::UnexpectedChar(scanner.pos));
+        }
+
+        Ok(Self {
+            text: input.to_owned(),
+            root,
+        })
+    }
+
+    /// Converts the whole document into a [`Value`], dropping all formatting.
+    pub fn value(&self) -> Value {
+        self.root.value()
+    }
+
+    /// Gets the value at the JSON Pointer `pointer`.
+    ///
+    /// The value is built from the document rather than borrowed from it, so there is no `get_mut`.
+    /// A mutable reference would let the value change without its source text being rewritten,
+    /// so all edits go through [`JsonDocument::set`], which updates the text and spans together.
+    pub fn get(&self, pointer: &str) -> Option<Value> {
+        let tokens = pointer::tokens(pointer)?;
+        self.root.find(&tokens).map(Node::value)
+    }
+
+    /// Sets the value at the JSON Pointer `pointer` to `value`.
+    ///
+    /// If something already exists there, only its text is replaced.
+    /// If the pointer refers to a missing key on an existing object, the key is appended to the end of that object,
+    /// copying the indentation and separator style of its last member.
+    pub fn set(&mut self, pointer: &str, value: Value) -> Result<(), EditError> {
+        let tokens = pointer::tokens(pointer).ok_or(EditError::InvalidPointer)?;
+
+        if let Some(node) = self.root.find(&tokens) {
+            let span = node.span.clone();
+            return self.splice(span, &value.to_string());
+        }
+
+        let (key, parent) = tokens.split_last().ok_or(EditError::NotFound)?;
+        let parent = self.root.find(parent).ok_or(EditError::NotFound)?;
+        let members = match &parent.kind {
+            NodeKind::Object(members) => members,
+            _ => return Err(EditError::NotFound),
+        };
+
+        let entry = |sep: &str| format!("{}{sep}{value}", Value::String(key.to_owned()));
+        let (at, insert) = match members.as_slice() {
+            [] => (parent.span.start + 1, entry(": ")),
+            [.., last] => {
+                let indent_start = match members.len() {
+                    1 => parent.span.start + 1,
+                    n => self.separator_end(members[n - 2].value.span.end),
+                };
+                let indent = indent(&self.text[indent_start..last.key_span.start]);
+                let sep = match &self.text[last.key_span.end..last.value.span.start] {
+                    x if x.bytes().all(|x| x == b':' || is_whitespace(x)) => x,
+                    _ => ": ",
+                };
+                (last.value.span.end, format!(",{indent}{}", entry(sep)))
+            }
+        };
+
+        self.splice(at..at, &insert)
+    }
+
+    /// Finds the end of the `,` following a member that ends at `pos`, skipping any comments before it.
+    fn separator_end(&self, pos: usize) -> usize {
+        let mut scanner = Scanner {
+            input: &self.text,
+            pos,
+        };
+        // The document already parsed, so the trivia is valid and a `,` follows it
+        let _ = scanner.trivia();
+        let _ = scanner.expect(b',');
+        scanner.pos
+    }
+
+    /// Replaces `span` with `with`, as long as the result is still a valid document.
+    fn splice(&mut self, span: Range<usize>, with: &str) -> Result<(), EditError> {
+        let mut text = self.text.clone();
+        text.replace_range(span, with);
+        *self = Self::parse(&text).map_err(|_| EditError::Invalid)?;
+        Ok(())
+    }
+}
+
+/// Gets the indentation to copy from the trivia before a key, without any comments in it.
+/// Ex. `,\n  // about b\n  ` gives `\n  `, and `, ` gives ` `.
+fn indent(trivia: &str) -> &str {
+    match trivia.rfind('\n') {
+        Some(i) => {
+            // Keeps a `\r` so CRLF files stay CRLF
+            let start = match trivia[..i].ends_with('\r') {
+                true => i - 1,
+                false => i,
+            };
+            let line = &trivia[i + 1..];
+            let leading = line.len() - line.trim_start_matches([' ', '\t']).len();
+            &trivia[start..i + 1 + leading]
+        }
+        None => {
+            let trailing = trivia.trim_end_matches([' ', '\t']).len();
+            &trivia[trailing..]
+        }
+    }
+}
+
+fn is_whitespace(x: u8) -> bool {
+    matches!(x, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+impl Node {
+    fn value(&self) -> Value {
+        match &self.kind {
+            NodeKind::Scalar(v) => v.clone(),
+            NodeKind::Array(a) => Value::Array(a.iter().map(Node::value).collect()),
+            NodeKind::Object(o) => Value::Object(
+                o.iter()
+                    .map(|x| (x.key.to_owned(), x.value.value()))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn find(&self, tokens: &[String]) -> Option<&Node> {
+        let (first, rest) = match tokens.split_first() {
+            Some(x) => x,
+            None => return Some(self),
+        };
+
+        let next = match &self.kind {
+            NodeKind::Array(a) => a.get(pointer::index(first)?)?,
+            // The last duplicate key wins, matching `Value`
+            NodeKind::Object(o) => &o.iter().rev().find(|x| &x.key == first)?.value,
+            NodeKind::Scalar(_) => return None,
+        };
+
+        next.find(rest)
+    }
+}
+
+impl Display for JsonDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl FromStr for JsonDocument {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn len(&self) -> usize {
+        self.input.len()
+    }
+
+    fn peek(&self) -> JsonResult<u8> {
+        self.input
+            .as_bytes()
+            .get(self.pos)
+            .copied()
+            .ok_or(Error::UnexpectedEnd(self.pos))
+    }
+
+    fn expect(&mut self, chr: u8) -> JsonResult<()> {
+        if self.peek()? != chr {
+            return Err(Error::UnexpectedChar(self.pos));
+        }
+
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Skips whitespace and comments.
+    fn trivia(&mut self) -> JsonResult<()> {
+        let bytes = self.input.as_bytes();
+        while self.pos < self.len() {
+            match (bytes[self.pos], bytes.get(self.pos + 1)) {
+                (x, _) if is_whitespace(x) => self.pos += 1,
+                (b'/', Some(b'/')) => {
+                    self.pos = self.input[self.pos..]
+                        .find('\n')
+                        .map(|x| self.pos + x)
+                        .unwrap_or(self.len());
+                }
+                (b'/', Some(b'*')) => {
+                    let end = self.input[self.pos + 2..]
+                        .find("*/")
+                        .ok_or(Error::UnexpectedEnd(self.len()))?;
+                    self.pos += end + 4;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn node(&mut self) -> JsonResult<Node> {
+        let start = self.pos;
+        let kind = match self.peek()? {
+            b'[' => self.array()?,
+            b'{' => self.object()?,
+            b'"' => NodeKind::Scalar(self.string()?),
+            _ => NodeKind::Scalar(self.literal()?),
+        };
+
+        Ok(Node {
+            span: start..self.pos,
+            kind,
+        })
+    }
+
+    fn array(&mut self) -> JsonResult<NodeKind> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+
+        self.trivia()?;
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Ok(NodeKind::Array(items));
+        }
+
+        loop {
+            items.push(self.node()?);
+            self.trivia()?;
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => break,
+                _ => return Err(Error::UnexpectedChar(self.pos)),
+            }
+            self.trivia()?;
+        }
+
+        self.pos += 1;
+        Ok(NodeKind::Array(items))
+    }
+
+    fn object(&mut self) -> JsonResult<NodeKind> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+
+        self.trivia()?;
+        if self.peek()? == b'}' {
+            self.pos += 1;
+            return Ok(NodeKind::Object(members));
+        }
+
+        loop {
+            let key_start = self.pos;
+            let key = match self.string()? {
+                Value::String(s) => s,
+                _ => unreachable!(),
+            };
+            let key_span = key_start..self.pos;
+
+            self.trivia()?;
+            self.expect(b':')?;
+            self.trivia()?;
+            members.push(Member {
+                key,
+                key_span,
+                value: self.node()?,
+            });
+
+            self.trivia()?;
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => break,
+                _ => return Err(Error::UnexpectedChar(self.pos)),
+            }
+            self.trivia()?;
+        }
+
+        self.pos += 1;
+        Ok(NodeKind::Object(members))
+    }
+
+    fn string(&mut self) -> JsonResult<Value> {
+        let start = self.pos;
+        self.expect(b'"')?;
+
+        let bytes = self.input.as_bytes();
+        loop {
+            match bytes.get(self.pos) {
+                Some(b'\\') => self.pos += 2,
+                Some(b'"') => break,
+                Some(_) => self.pos += 1,
+                None => return Err(Error::UnexpectedEnd(self.len())),
+            }
+        }
+
+        self.pos += 1;
+        self.input[start..self.pos]
+            .parse()
+            .map_err(|e: Error| e.offset(start))
+    }
+
+    /// Parses a number, `true`, `false` or `null`.
+    fn literal(&mut self) -> JsonResult<Value> {
+        let start = self.pos;
+        let bytes = self.input.as_bytes();
+        while self.pos < self.len()
+            && (bytes[self.pos].is_ascii_alphanumeric()
+                || matches!(bytes[self.pos], b'-' | b'+' | b'.'))
+        {
+            self.pos += 1;
+        }
+
+        match &self.input[start..self.pos] {
+            "" => Err(Error::UnexpectedChar(start)),
+            "null" => Ok(Value::Null),
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            x if x.starts_with(|c: char| c == '-' || c.is_ascii_digit()) => x
+                .parse()
+                .map(Value::Number)
+                .map_err(|error| Error::InvalidNumber { pos: start, error }),
+            _ => Err(Error::UnexpectedChar(start)),
+        }
+    }
+}
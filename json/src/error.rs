@@ -1,18 +1,52 @@
-use std::num::{ParseFloatError, ParseIntError};
+use std::{
+    error,
+    fmt::{self, Display},
+    io,
+    num::{ParseFloatError, ParseIntError},
+    str::Utf8Error,
+};
 
 pub type JsonResult<T> = Result<T, Error>;
 
 /// Errors that can occur during parsing.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug)]
 pub enum Error {
     /// An unexpected character was encountered at the given position.
     UnexpectedChar(usize),
-    ///An invalid number was encountered.
-    InvalidNumber(ParseNumberError),
+    /// An invalid number was encountered.
+    InvalidNumber {
+        /// The position of the start of the number.
+        pos: usize,
+        /// Why the number couldn't be parsed.
+        error: ParseNumberError,
+    },
     /// The end of the input was reached unexpectedly.
     UnexpectedEnd(usize),
     /// An invalid escape sequence was encountered.
-    InvalidEscape(char),
+    InvalidEscape {
+        /// The position of the `\\` starting the escape.
+        pos: usize,
+        /// The character after the `\\`.
+        found: char,
+    },
+    /// A string was longer than [`ParseOptions::max_string_len`](crate::ParseOptions::max_string_len).
+    StringTooLong {
+        /// The position of the opening quote.
+        pos: usize,
+        /// The limit, in bytes.
+        max: usize,
+    },
+    /// More values were parsed than [`ParseOptions::max_nodes`](crate::ParseOptions::max_nodes) allows.
+    TooManyNodes {
+        /// The position of the first value over the limit.
+        pos: usize,
+        /// The limit.
+        max: usize,
+    },
+    /// An IO error occurred while reading the input.
+    Io(io::Error),
+    /// The input was not valid UTF-8.
+    InvalidUtf8(Utf8Error),
 }
 
 /// Errors that can occur during parsing of a number.
@@ -22,16 +56,155 @@ pub enum ParseNumberError {
     ParseIntError(ParseIntError),
     /// An error occurred while parsing a float.
     ParseFloatError(ParseFloatError),
+    /// The number had no digits before or after the decimal point.
+    /// Ex. `-`, `.5` or `1.`
+    MissingDigits,
+    /// The number was longer than [`ParseOptions::max_number_len`](crate::ParseOptions::max_number_len).
+    /// Contains the limit.
+    TooLong(usize),
+}
+
+/// IO errors are compared by their [`io::ErrorKind`].
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::UnexpectedChar(l0), Self::UnexpectedChar(r0)) => l0 == r0,
+            (
+                Self::InvalidNumber { pos: l0, error: l1 },
+                Self::InvalidNumber { pos: r0, error: r1 },
+            ) => l0 == r0 && l1 == r1,
+            (Self::UnexpectedEnd(l0), Self::UnexpectedEnd(r0)) => l0 == r0,
+            (
+                Self::InvalidEscape { pos: l0, found: l1 },
+                Self::InvalidEscape { pos: r0, found: r1 },
+            ) => l0 == r0 && l1 == r1,
+            (
+                Self::StringTooLong { pos: l0, max: l1 },
+                Self::StringTooLong { pos: r0, max: r1 },
+            ) => l0 == r0 && l1 == r1,
+            (Self::TooManyNodes { pos: l0, max: l1 }, Self::TooManyNodes { pos: r0, max: r1 }) => {
+                l0 == r0 && l1 == r1
+            }
+            (Self::Io(l0), Self::Io(r0)) => l0.kind() == r0.kind(),
+            (Self::InvalidUtf8(l0), Self::InvalidUtf8(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Error {}
+
+/// IO errors holding an [`Error`], like ones made with `From<Error> for io::Error`, are unwrapped back into it.
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        match e.get_ref().is_some_and(|x| x.is::<Error>()) {
+            true => *e.into_inner().unwrap().downcast().unwrap(),
+            false => Error::Io(e),
+        }
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Self {
+        Error::InvalidUtf8(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnexpectedChar(pos) => write!(f, "unexpected character at {pos}"),
+            Error::InvalidNumber { pos, error } => write!(f, "invalid number at {pos}: {error}"),
+            Error::UnexpectedEnd(pos) => write!(f, "unexpected end of input at {pos}"),
+            Error::InvalidEscape { pos, found } => {
+                write!(f, "invalid escape `\\{found}` at {pos}")
+            }
+            Error::StringTooLong { pos, max } => {
+                write!(f, "string at {pos} is longer than {max} bytes")
+            }
+            Error::TooManyNodes { pos, max } => {
+                write!(f, "more than {max} values, reached at {pos}")
+            }
+            Error::Io(e) => write!(f, "io error: {e}"),
+            Error::InvalidUtf8(e) => write!(f, "invalid utf-8: {e}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::InvalidNumber { error, .. } => Some(error),
+            Error::Io(e) => Some(e),
+            Error::InvalidUtf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Parse errors become [`io::ErrorKind::InvalidData`] errors holding the original [`Error`],
+/// which can be gotten back with [`io::Error::into_inner`] and a downcast, or by converting back into an [`Error`].
+/// An [`Error::Io`] is unwrapped into the IO error it holds.
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+impl Display for ParseNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseNumberError::ParseIntError(e) => e.fmt(f),
+            ParseNumberError::ParseFloatError(e) => e.fmt(f),
+            ParseNumberError::MissingDigits => f.write_str("missing digits"),
+            ParseNumberError::TooLong(max) => write!(f, "longer than {max} characters"),
+        }
+    }
+}
+
+impl error::Error for ParseNumberError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseNumberError::ParseIntError(e) => Some(e),
+            ParseNumberError::ParseFloatError(e) => Some(e),
+            ParseNumberError::MissingDigits | ParseNumberError::TooLong(_) => None,
+        }
+    }
+}
+
+impl Error {
+    /// Shifts any position in the error by `by` bytes.
+    /// Used when a slice of a larger input was parsed on its own.
+    pub(crate) fn offset(self, by: usize) -> Self {
+        match self {
+            Error::UnexpectedChar(x) => Error::UnexpectedChar(x + by),
+            Error::UnexpectedEnd(x) => Error::UnexpectedEnd(x + by),
+            Error::InvalidNumber { pos, error } => Error::InvalidNumber {
+                pos: pos + by,
+                error,
+            },
+            Error::InvalidEscape { pos, found } => Error::InvalidEscape {
+                pos: pos + by,
+                found,
+            },
+            Error::StringTooLong { pos, max } => Error::StringTooLong { pos: pos + by, max },
+            Error::TooManyNodes { pos, max } => Error::TooManyNodes { pos: pos + by, max },
+            x => x,
+        }
+    }
 }
 
-impl From<ParseIntError> for Error {
+impl From<ParseIntError> for ParseNumberError {
     fn from(e: ParseIntError) -> Self {
-        Error::InvalidNumber(ParseNumberError::ParseIntError(e))
+        ParseNumberError::ParseIntError(e)
     }
 }
 
-impl From<ParseFloatError> for Error {
+impl From<ParseFloatError> for ParseNumberError {
     fn from(e: ParseFloatError) -> Self {
-        Error::InvalidNumber(ParseNumberError::ParseFloatError(e))
+        ParseNumberError::ParseFloatError(e)
     }
 }
@@ -0,0 +1,330 @@
+//! Converting [`Value`]s into Rust types.
+
+use std::fmt::{self, Display};
+
+use crate::{JsonPath, JsonType, Number, PathSegment, Value};
+
+/// A type that can be built from a JSON [`Value`].
+pub trait FromJson: Sized {
+    /// Converts `value` into `Self`.
+    fn from_json(value: &Value) -> Result<Self, FromJsonError>;
+
+    /// Called by [`field`] when the key is not in the object at all.
+    /// By default this is a [`FromJsonErrorKind::Missing`] error, but optional types can override it.
+    fn from_missing() -> Result<Self, FromJsonError> {
+        Err(FromJsonError::new(FromJsonErrorKind::Missing))
+    }
+}
+
+/// An optional field that keeps track of if it was missing or explicitly set to null.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Nullable<T> {
+    /// The key was not in the object.
+    #[default]
+    Missing,
+    /// The key was in the object, but its value was `null`.
+    Null,
+    /// The key was in the object with a non-null value.
+    Value(T),
+}
+
+/// An error converting a [`Value`] into a Rust type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromJsonError {
+    path: JsonPath,
+    kind: FromJsonErrorKind,
+}
+
+/// The reason a [`FromJsonError`] occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromJsonErrorKind {
+    /// A required key was not in the object.
+    Missing,
+    /// The value was not the expected type.
+    WrongType {
+        /// The expected type.
+        expected: JsonType,
+        /// The type that was found.
+        found: JsonType,
+    },
+    /// A number was found where an integer was expected, but it had a fractional part.
+    NotInteger,
+    /// A number was the right type but didn't fit in the target type.
+    OutOfRange,
+    /// A custom error from a user's [`FromJson`] impl.
+    Custom(String),
+}
+
+impl FromJsonError {
+    /// Creates an error at the root of the value being converted.
+    pub fn new(kind: FromJsonErrorKind) -> Self {
+        Self {
+            path: JsonPath::new(),
+            kind,
+        }
+    }
+
+    /// Creates a [`FromJsonErrorKind::WrongType`] error for `found`.
+    pub fn wrong_type(expected: JsonType, found: &Value) -> Self {
+        Self::new(FromJsonErrorKind::WrongType {
+            expected,
+            found: found.kind(),
+        })
+    }
+
+    /// Creates a [`FromJsonErrorKind::Custom`] error.
+    pub fn custom(msg: impl Into<String>) -> Self {
+        Self::new(FromJsonErrorKind::Custom(msg.into()))
+    }
+
+    /// Gets the path of the value that caused the error.
+    pub fn path(&self) -> &JsonPath {
+        &self.path
+    }
+
+    /// Gets the reason for the error.
+    pub fn kind(&self) -> &FromJsonErrorKind {
+        &self.kind
+    }
+
+    /// Adds `segment` to the start of the error's path.
+    /// Used by container impls so errors point at the exact value that failed.
+    pub fn within(mut self, segment: PathSegment) -> Self {
+        self.path.push_front(segment);
+        self
+    }
+}
+
+impl Display for FromJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = match self.path.is_root() {
+            true => "/".to_owned(),
+            false => self.path.to_string(),
+        };
+
+        match &self.kind {
+            FromJsonErrorKind::Missing => write!(f, "missing field at {path}"),
+            FromJsonErrorKind::WrongType { expected, found } => {
+                write!(f, "expected {expected}, found {found} at {path}")
+            }
+            FromJsonErrorKind::NotInteger => write!(f, "expected integer at {path}"),
+            FromJsonErrorKind::OutOfRange => write!(f, "number out of range at {path}"),
+            FromJsonErrorKind::Custom(msg) => write!(f, "{msg} at {path}"),
+        }
+    }
+}
+
+impl std::error::Error for FromJsonError {}
+
+/// Gets the field `key` of the object `obj` as a `T`.
+///
+/// If the key is missing, [`FromJson::from_missing`] decides what happens,
+/// so `Option<T>` and [`Nullable<T>`] fields are allowed to be left out.
+/// Errors from converting the value have `key` added to their path.
+pub fn field<T: FromJson>(obj: &Value, key: &str) -> Result<T, FromJsonError> {
+    let obj = obj
+        .as_object()
+        .ok_or_else(|| FromJsonError::wrong_type(JsonType::Object, obj))?;
+
+    match obj.get(key) {
+        Some(x) => T::from_json(x),
+        None => T::from_missing(),
+    }
+    .map_err(|e| e.within(PathSegment::Key(key.to_owned())))
+}
+
+/// Like [`field`], but calls `default` if the key is missing.
+/// A key that is present with the wrong type (including `null` for non-optional types) is still an error.
+pub fn field_or_else<T: FromJson>(
+    obj: &Value,
+    key: &str,
+    default: impl FnOnce() -> T,
+) -> Result<T, FromJsonError> {
+    match obj.as_object().map(|x| x.contains_key(key)) {
+        Some(false) => Ok(default()),
+        _ => field(obj, key),
+    }
+}
+
+impl Value {
+    /// Gets the field `key` of an object as a `T`, or `default` if the key is missing, null or the wrong type.
+    /// Also returns `default` if the value isn't an object.
+    /// Ex. `cfg.get_or("timeout_ms", 5000u64)`
+    pub fn get_or<T: FromJson>(&self, key: &str, default: T) -> T {
+        field::<Option<T>>(self, key)
+            .ok()
+            .flatten()
+            .unwrap_or(default)
+    }
+
+    /// Gets the field `key` of an object as a `u64`, or `default` if it is missing, null or the wrong type.
+    pub fn get_u64_or(&self, key: &str, default: u64) -> u64 {
+        self.get_or(key, default)
+    }
+
+    /// Gets the field `key` of an object as an `i64`, or `default` if it is missing, null or the wrong type.
+    pub fn get_i64_or(&self, key: &str, default: i64) -> i64 {
+        self.get_or(key, default)
+    }
+
+    /// Gets the field `key` of an object as an `f64`, or `default` if it is missing, null or the wrong type.
+    /// Integers are converted to floats.
+    pub fn get_f64_or(&self, key: &str, default: f64) -> f64 {
+        self.get_or(key, default)
+    }
+
+    /// Gets the field `key` of an object as a `bool`, or `default` if it is missing, null or the wrong type.
+    pub fn get_bool_or(&self, key: &str, default: bool) -> bool {
+        self.get_or(key, default)
+    }
+
+    /// Gets the field `key` of an object as a string, or `default` if it is missing, null or the wrong type.
+    pub fn get_str_or<'a>(&'a self, key: &str, default: &'a str) -> &'a str {
+        self.try_get_str(key).unwrap_or(default)
+    }
+
+    /// Like [`Value::get_u64_or`], but fails instead of defaulting, so typos in keys can be caught.
+    /// The error's path is the key.
+    pub fn try_get_u64(&self, key: &str) -> Result<u64, FromJsonError> {
+        field(self, key)
+    }
+
+    /// Like [`Value::get_i64_or`], but fails instead of defaulting.
+    pub fn try_get_i64(&self, key: &str) -> Result<i64, FromJsonError> {
+        field(self, key)
+    }
+
+    /// Like [`Value::get_f64_or`], but fails instead of defaulting.
+    pub fn try_get_f64(&self, key: &str) -> Result<f64, FromJsonError> {
+        field(self, key)
+    }
+
+    /// Like [`Value::get_bool_or`], but fails instead of defaulting.
+    pub fn try_get_bool(&self, key: &str) -> Result<bool, FromJsonError> {
+        field(self, key)
+    }
+
+    /// Like [`Value::get_str_or`], but fails instead of defaulting.
+    pub fn try_get_str(&self, key: &str) -> Result<&str, FromJsonError> {
+        let obj = self
+            .as_object()
+            .ok_or_else(|| FromJsonError::wrong_type(JsonType::Object, self))?;
+
+        match obj.get(key) {
+            Some(Value::String(s)) => Ok(s.as_str()),
+            Some(x) => Err(FromJsonError::wrong_type(JsonType::String, x)),
+            None => Err(FromJsonError::new(FromJsonErrorKind::Missing)),
+        }
+        .map_err(|e| e.within(PathSegment::Key(key.to_owned())))
+    }
+}
+
+impl FromJson for Value {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        value
+            .as_bool()
+            .copied()
+            .ok_or_else(|| FromJsonError::wrong_type(JsonType::Bool, value))
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        value
+            .as_string()
+            .cloned()
+            .ok_or_else(|| FromJsonError::wrong_type(JsonType::String, value))
+    }
+}
+
+macro_rules! impl_from_json_int {
+    ($($type:ty),*) => {
+        $(
+            impl FromJson for $type {
+                fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+                    let out = match value {
+                        Value::Number(Number::UInt(x)) => <$type>::try_from(*x).ok(),
+                        Value::Number(Number::Int(x)) => <$type>::try_from(*x).ok(),
+                        Value::Number(Number::UInt128(x)) => <$type>::try_from(*x).ok(),
+                        Value::Number(Number::Int128(x)) => <$type>::try_from(*x).ok(),
+                        Value::Number(n @ Number::Raw(x)) if n.is_integer() => x.parse().ok(),
+                        Value::Number(_) => {
+                            return Err(FromJsonError::new(FromJsonErrorKind::NotInteger))
+                        }
+                        _ => return Err(FromJsonError::wrong_type(JsonType::Number, value)),
+                    };
+
+                    out.ok_or_else(|| FromJsonError::new(FromJsonErrorKind::OutOfRange))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_json_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl FromJson for f64 {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        match value {
+            Value::Number(Number::UInt(x)) => Ok(*x as f64),
+            Value::Number(Number::Int(x)) => Ok(*x as f64),
+            Value::Number(Number::UInt128(x)) => Ok(*x as f64),
+            Value::Number(Number::Int128(x)) => Ok(*x as f64),
+            Value::Number(Number::Float(x)) => Ok(*x),
+            Value::Number(Number::Raw(x)) => Ok(x.parse().unwrap_or(f64::NAN)),
+            _ => Err(FromJsonError::wrong_type(JsonType::Number, value)),
+        }
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        f64::from_json(value).map(|x| x as f32)
+    }
+}
+
+/// Missing and `null` values are both `None`.
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        match value {
+            Value::Null => Ok(None),
+            x => T::from_json(x).map(Some),
+        }
+    }
+
+    fn from_missing() -> Result<Self, FromJsonError> {
+        Ok(None)
+    }
+}
+
+impl<T: FromJson> FromJson for Nullable<T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        match value {
+            Value::Null => Ok(Nullable::Null),
+            x => T::from_json(x).map(Nullable::Value),
+        }
+    }
+
+    fn from_missing() -> Result<Self, FromJsonError> {
+        Ok(Nullable::Missing)
+    }
+}
+
+/// The first element that fails to convert is reported, with its index added to the error path.
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        value
+            .as_array()
+            .ok_or_else(|| FromJsonError::wrong_type(JsonType::Array, value))?
+            .iter()
+            .enumerate()
+            .map(|(i, x)| T::from_json(x).map_err(|e| e.within(PathSegment::Index(i))))
+            .collect()
+    }
+}
@@ -0,0 +1,76 @@
+//! Helpers for turning arrays of records into lookup maps.
+
+use std::collections::BTreeMap;
+
+use crate::Value;
+
+impl Value {
+    /// Indexes an array of objects by the value of their `key` field.
+    /// If more than one element has the same key, the last one is kept.
+    /// Ex. `[{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]` => `{"1": {..}, "2": {..}}`
+    ///
+    /// Strings are used as they are and numbers and bools are stringified.
+    /// Elements that aren't objects, are missing the key, or have a null, array or object key are returned separately as skipped.
+    /// If the value isn't an array, both the map and the skipped list are empty.
+    pub fn index_by(&self, key: &str) -> (BTreeMap<String, &Value>, Vec<&Value>) {
+        let (groups, skipped) = group(self.as_array().into_iter().flatten(), key, |x| x);
+        let index = groups
+            .into_iter()
+            .filter_map(|(k, v)| Some((k, v.into_iter().last()?)))
+            .collect();
+        (index, skipped)
+    }
+
+    /// Groups an array of objects by the value of their `key` field, keeping elements in array order.
+    /// Keys and skipped elements are handled the same as in [`Value::index_by`].
+    pub fn group_by(&self, key: &str) -> (BTreeMap<String, Vec<&Value>>, Vec<&Value>) {
+        group(self.as_array().into_iter().flatten(), key, |x| x)
+    }
+
+    /// Like [`Value::index_by`], but consumes the array to avoid cloning its elements.
+    pub fn into_index_by(self, key: &str) -> (BTreeMap<String, Value>, Vec<Value>) {
+        let (groups, skipped) = self.into_group_by(key);
+        let index = groups
+            .into_iter()
+            .filter_map(|(k, v)| Some((k, v.into_iter().last()?)))
+            .collect();
+        (index, skipped)
+    }
+
+    /// Like [`Value::group_by`], but consumes the array to avoid cloning its elements.
+    pub fn into_group_by(self, key: &str) -> (BTreeMap<String, Vec<Value>>, Vec<Value>) {
+        let items = match self {
+            Value::Array(a) => a,
+            _ => Vec::new(),
+        };
+        group(items, key, |x| x)
+    }
+}
+
+fn group<T>(
+    items: impl IntoIterator<Item = T>,
+    key: &str,
+    value: impl Fn(&T) -> &Value,
+) -> (BTreeMap<String, Vec<T>>, Vec<T>) {
+    let mut groups = BTreeMap::<_, Vec<_>>::new();
+    let mut skipped = Vec::new();
+
+    for i in items {
+        match key_of(value(&i), key) {
+            Some(k) => groups.entry(k).or_default().push(i),
+            None => skipped.push(i),
+        }
+    }
+
+    (groups, skipped)
+}
+
+/// Gets the `key` field of an object as a string, if it is a string, number or bool.
+fn key_of(value: &Value, key: &str) -> Option<String> {
+    match value.as_object()?.get(key)? {
+        Value::String(s) => Some(s.to_owned()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,117 @@
+use std::hash::Hasher;
+
+use crate::{Number, Value};
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// A 64 bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hasher.
+/// Unlike the std `DefaultHasher`, it isn't randomly seeded, so hashes are stable across runs.
+struct Fnv64(u64);
+
+impl Hasher for Fnv64 {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &i in bytes {
+            self.0 ^= i as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+impl Value {
+    /// Hashes the canonical form of the value with FNV-1a.
+    /// The hash is stable across program runs and platforms, so it can be used for caching and change detection.
+    /// See [`Value::content_hash_into`] for how values are canonicalized.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv64(FNV_OFFSET);
+        self.content_hash_into(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Feeds the canonical form of the value into `hasher`.
+    ///
+    /// - Object entries are hashed in sorted key order.
+    /// - Integers hash the same no matter which integer variant of [`Number`] they are stored in.
+    /// - Floats hash differently than integers, even if numerically equal (`1` vs `1.0`), matching [`Number`]'s equality.
+    ///   `-0.0` and `0.0` hash the same.
+    /// - Everything is written as little endian bytes with a type tag and length prefixes,
+    ///   so the output doesn't depend on the platform.
+    pub fn content_hash_into(&self, hasher: &mut impl Hasher) {
+        fn len(hasher: &mut impl Hasher, len: usize) {
+            hasher.write(&(len as u64).to_le_bytes());
+        }
+
+        // Integers are hashed in the smallest form they fit in
+        fn uint(hasher: &mut impl Hasher, x: u128) {
+            match u64::try_from(x) {
+                Ok(x) => {
+                    hasher.write(b"u");
+                    hasher.write(&x.to_le_bytes());
+                }
+                Err(_) => {
+                    hasher.write(b"U");
+                    hasher.write(&x.to_le_bytes());
+                }
+            }
+        }
+
+        fn int(hasher: &mut impl Hasher, x: i128) {
+            if let Ok(x) = u128::try_from(x) {
+                return uint(hasher, x);
+            }
+
+            match i64::try_from(x) {
+                Ok(x) => {
+                    hasher.write(b"i");
+                    hasher.write(&x.to_le_bytes());
+                }
+                Err(_) => {
+                    hasher.write(b"I");
+                    hasher.write(&x.to_le_bytes());
+                }
+            }
+        }
+
+        match self {
+            Value::Null => hasher.write(b"n"),
+            Value::Bool(b) => hasher.write(if *b { b"t" } else { b"f" }),
+            Value::Number(Number::UInt(x)) => uint(hasher, *x as u128),
+            Value::Number(Number::Int(x)) => int(hasher, *x as i128),
+            Value::Number(Number::UInt128(x)) => uint(hasher, *x),
+            Value::Number(Number::Int128(x)) => int(hasher, *x),
+            Value::Number(Number::Float(x)) => {
+                hasher.write(b"d");
+                let x = if *x == 0.0 { 0.0 } else { *x };
+                hasher.write(&x.to_bits().to_le_bytes());
+            }
+            Value::Number(Number::Raw(x)) => {
+                hasher.write(b"r");
+                len(hasher, x.len());
+                hasher.write(x.as_bytes());
+            }
+            Value::String(s) => {
+                hasher.write(b"s");
+                len(hasher, s.len());
+                hasher.write(s.as_bytes());
+            }
+            Value::Array(a) => {
+                hasher.write(b"a");
+                len(hasher, a.len());
+                a.iter().for_each(|x| x.content_hash_into(hasher));
+            }
+            Value::Object(o) => {
+                hasher.write(b"o");
+                len(hasher, o.len());
+                for (k, v) in o {
+                    len(hasher, k.len());
+                    hasher.write(k.as_bytes());
+                    v.content_hash_into(hasher);
+                }
+            }
+        }
+    }
+}
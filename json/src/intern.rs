@@ -0,0 +1,67 @@
+//! Sharing object keys between parsed values.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{
+    map::Interned,
+    parser::{ParseOptions, Parser},
+    value::GenericValue,
+    Error,
+};
+
+/// A JSON element, using a [`BTreeMap`](std::collections::BTreeMap) with shared `Arc<str>` keys for objects.
+pub type InternedValue = GenericValue<Interned>;
+
+/// A set of object keys shared between parses, so documents with the same keys only store each key once.
+/// Ex. `ParseOptions { interner: Some(KeyInterner::new()), ..Default::default() }`
+///
+/// Clones share the same set, so one can be kept in the [`ParseOptions`] and another used to check its size.
+/// Keys are kept for as long as the interner, even after every value using them is dropped.
+#[derive(Debug, Clone, Default)]
+pub struct KeyInterner {
+    keys: Arc<Mutex<HashSet<Arc<str>>>>,
+}
+
+impl KeyInterner {
+    /// Creates a new empty [`KeyInterner`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Gets the shared copy of `key`, adding it if it hasn't been seen before.
+    pub fn intern(&self, key: &str) -> Arc<str> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        match keys.get(key) {
+            Some(x) => x.clone(),
+            None => {
+                let key = Arc::<str>::from(key);
+                keys.insert(key.clone());
+                key
+            }
+        }
+    }
+
+    /// Gets the number of different keys stored.
+    pub fn len(&self) -> usize {
+        self.keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Checks if no keys are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl InternedValue {
+    /// Like [`Value::from_str_with`](crate::Value::from_str_with), sharing object keys through [`ParseOptions::interner`] if it is set.
+    /// Ex. `InternedValue::from_str_with(response, &options)`
+    pub fn from_str_with(input: &str, options: &ParseOptions) -> Result<InternedValue, Error> {
+        Parser::with_options(input, options.clone()).parse_interned()
+    }
+}
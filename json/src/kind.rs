@@ -0,0 +1,93 @@
+//! The type of a [`Value`], without its contents.
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::Value;
+
+/// The type of a JSON [`Value`].
+/// Gotten with [`Value::kind`].
+/// Types are ordered the same way as values of different types are, `Null < Bool < Number < String < Array < Object`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum JsonType {
+    /// A [`Value::Null`].
+    Null,
+    /// A [`Value::Bool`].
+    Bool,
+    /// A [`Value::Number`].
+    Number,
+    /// A [`Value::String`].
+    String,
+    /// A [`Value::Array`].
+    Array,
+    /// A [`Value::Object`].
+    Object,
+}
+
+/// The error returned when parsing an unknown type name into a [`JsonType`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseJsonTypeError;
+
+impl JsonType {
+    /// Gets the lowercase name of the type, as used in error messages.
+    /// Ex. `JsonType::Bool` => `bool`
+    pub fn name(self) -> &'static str {
+        match self {
+            JsonType::Null => "null",
+            JsonType::Bool => "bool",
+            JsonType::Number => "number",
+            JsonType::String => "string",
+            JsonType::Array => "array",
+            JsonType::Object => "object",
+        }
+    }
+}
+
+impl Value {
+    /// Gets the type of the value.
+    /// Ex. `[1, 2]` => `JsonType::Array`
+    pub fn kind(&self) -> JsonType {
+        match self {
+            Value::Null => JsonType::Null,
+            Value::Bool(_) => JsonType::Bool,
+            Value::Number(_) => JsonType::Number,
+            Value::String(_) => JsonType::String,
+            Value::Array(_) => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+}
+
+impl Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Display for ParseJsonTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown json type name")
+    }
+}
+
+impl std::error::Error for ParseJsonTypeError {}
+
+/// Parses the names from [`JsonType::name`].
+/// The JSON Schema names `boolean` and `integer` are also accepted, with `integer` parsing as [`JsonType::Number`].
+impl FromStr for JsonType {
+    type Err = ParseJsonTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "null" => JsonType::Null,
+            "bool" | "boolean" => JsonType::Bool,
+            "number" | "integer" => JsonType::Number,
+            "string" => JsonType::String,
+            "array" => JsonType::Array,
+            "object" => JsonType::Object,
+            _ => return Err(ParseJsonTypeError),
+        })
+    }
+}
@@ -1,12 +1,58 @@
 //! A JSON parser and serializer.
 
+mod coerce;
+mod csv;
+mod document;
 mod error;
+mod from_json;
+mod group;
+mod hash;
+mod intern;
+mod kind;
+mod map;
+#[cfg(feature = "mmap")]
+mod mmap;
 mod number;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod parser;
+mod patch;
+mod path;
+mod pointer;
+mod query;
+mod schema;
+mod serialize;
+mod tokenizer;
+mod toml;
 mod value;
-pub use error::Error;
+mod walk;
+mod yaml;
+pub use csv::{from_csv, to_csv, CsvError};
+pub use document::{EditError, JsonDocument};
+pub use error::{Error, ParseNumberError};
+pub use from_json::{field, field_or_else, FromJson, FromJsonError, FromJsonErrorKind, Nullable};
+pub use intern::{InternedValue, KeyInterner};
+pub use kind::{JsonType, ParseJsonTypeError};
+pub use map::{HashValue, Hashed, Interned, MapKey, MapKind, Ordered};
+#[cfg(feature = "mmap")]
+pub use mmap::MappedDocument;
 pub use number::Number;
-pub use value::Value;
+#[cfg(feature = "rayon")]
+pub use parallel::parse_large_array;
+pub use parser::{ParseOptions, Parser};
+pub use patch::{apply_patch, create_patch, PatchError};
+pub use path::{JsonPath, PathSegment};
+pub use pointer::JsonPointerBuf;
+pub use query::{
+    from_query_string, to_query_string, to_query_string_with, ArrayFormat, QueryError,
+};
+pub use schema::{infer_schema, Schema, ValidationError, ValidationErrorKind};
+pub use serialize::SerializeOptions;
+pub use tokenizer::{extract, minify, validate, Token, Tokenizer};
+pub use toml::{from_toml, to_toml, TomlError};
+pub use value::{BufferTooSmall, GenericValue, Stats, Value};
+pub use walk::Nodes;
+pub use yaml::{from_yaml, to_yaml, YamlError};
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,168 @@
+//! Choosing the map used to store objects.
+
+use std::{
+    borrow::{Borrow, Cow},
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    mem,
+    sync::Arc,
+};
+
+use crate::{value::GenericValue, KeyInterner};
+
+/// Chooses the map a [`GenericValue`] stores its objects in.
+/// Implemented by [`Ordered`], used by [`Value`](crate::Value), [`Hashed`], used by [`HashValue`],
+/// and [`Interned`], used by [`InternedValue`](crate::InternedValue).
+pub trait MapKind: Sized {
+    /// The type of object keys.
+    type Key: MapKey;
+    /// The map from keys to values.
+    type Map: Debug
+        + Clone
+        + Eq
+        + Default
+        + IntoIterator<Item = (Self::Key, GenericValue<Self>)>
+        + FromIterator<(Self::Key, GenericValue<Self>)>;
+}
+
+/// An object key, implemented by `String` and by `Arc<str>` for keys that can be shared.
+pub trait MapKey: Debug + Clone + Ord + Hash + Borrow<str> {
+    /// Makes a key from `key`, reusing its allocation if possible.
+    fn from_string(key: String) -> Self;
+
+    /// Turns the key into a `String`, reusing its allocation if possible.
+    fn into_string(self) -> String;
+
+    /// Makes a key for a parsed object, sharing it through `interner` if the key type can be shared.
+    fn from_parsed(key: Cow<str>, _interner: Option<&KeyInterner>) -> Self {
+        Self::from_string(key.into_owned())
+    }
+}
+
+impl MapKey for String {
+    fn from_string(key: String) -> Self {
+        key
+    }
+
+    fn into_string(self) -> String {
+        self
+    }
+}
+
+impl MapKey for Arc<str> {
+    fn from_string(key: String) -> Self {
+        key.into()
+    }
+
+    fn into_string(self) -> String {
+        self.as_ref().to_owned()
+    }
+
+    fn from_parsed(key: Cow<str>, interner: Option<&KeyInterner>) -> Self {
+        match interner {
+            Some(interner) => interner.intern(&key),
+            None => key.into(),
+        }
+    }
+}
+
+/// Stores objects in a [`BTreeMap`], keeping keys sorted.
+/// This is the default, and the only kind that can be serialized directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ordered;
+
+/// Stores objects in a [`HashMap`], which is faster for large objects but unordered.
+/// Convert from and to a [`Value`](crate::Value) with [`GenericValue::convert`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hashed;
+
+/// Stores objects in a [`BTreeMap`] with `Arc<str>` keys, so values parsed with the same [`KeyInterner`] share their keys.
+/// Parse with [`InternedValue::from_str_with`](crate::InternedValue::from_str_with) or [`Parser::parse_interned`](crate::Parser::parse_interned).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Interned;
+
+impl MapKind for Ordered {
+    type Key = String;
+    type Map = BTreeMap<String, GenericValue<Ordered>>;
+}
+
+impl MapKind for Hashed {
+    type Key = String;
+    type Map = HashMap<String, GenericValue<Hashed>>;
+}
+
+impl MapKind for Interned {
+    type Key = Arc<str>;
+    type Map = BTreeMap<Arc<str>, GenericValue<Interned>>;
+}
+
+/// A JSON element, using a [`HashMap`] for objects.
+pub type HashValue = GenericValue<Hashed>;
+
+impl<K: MapKind> GenericValue<K> {
+    /// Converts the value to use another map kind for its objects, recursively.
+    /// Ex. `Value::from_str("{\"a\": 1}")?.convert::<Hashed>()`
+    pub fn convert<J: MapKind>(self) -> GenericValue<J> {
+        match self {
+            GenericValue::Null => GenericValue::Null,
+            GenericValue::Bool(x) => GenericValue::Bool(x),
+            GenericValue::Number(x) => GenericValue::Number(x),
+            GenericValue::String(x) => GenericValue::String(x),
+            GenericValue::Array(x) => {
+                GenericValue::Array(x.into_iter().map(GenericValue::convert).collect())
+            }
+            GenericValue::Object(x) => {
+                let key = |k: K::Key| J::Key::from_string(k.into_string());
+                GenericValue::Object(x.into_iter().map(|(k, v)| (key(k), v.convert())).collect())
+            }
+        }
+    }
+}
+
+// These can't be derived, as the derived bounds would be on `K` rather than `K::Map`.
+// `Debug` is in `value.rs`, as it isn't a derived form.
+
+impl<K: MapKind> Clone for GenericValue<K> {
+    fn clone(&self) -> Self {
+        match self {
+            GenericValue::Null => GenericValue::Null,
+            GenericValue::Bool(x) => GenericValue::Bool(*x),
+            GenericValue::Number(x) => GenericValue::Number(x.clone()),
+            GenericValue::String(x) => GenericValue::String(x.clone()),
+            GenericValue::Array(x) => GenericValue::Array(x.clone()),
+            GenericValue::Object(x) => GenericValue::Object(x.clone()),
+        }
+    }
+}
+
+impl<K: MapKind> PartialEq for GenericValue<K> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (GenericValue::Null, GenericValue::Null) => true,
+            (GenericValue::Bool(a), GenericValue::Bool(b)) => a == b,
+            (GenericValue::Number(a), GenericValue::Number(b)) => a == b,
+            (GenericValue::String(a), GenericValue::String(b)) => a == b,
+            (GenericValue::Array(a), GenericValue::Array(b)) => a == b,
+            (GenericValue::Object(a), GenericValue::Object(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<K: MapKind> Eq for GenericValue<K> {}
+
+// A `HashMap` isn't `Hash`, so only values with ordered objects are
+impl Hash for GenericValue<Ordered> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            GenericValue::Null => {}
+            GenericValue::Bool(x) => x.hash(state),
+            GenericValue::Number(x) => x.hash(state),
+            GenericValue::String(x) => x.hash(state),
+            GenericValue::Array(x) => x.hash(state),
+            GenericValue::Object(x) => x.hash(state),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::{fs::File, path::Path, str};
+
+use memmap2::Mmap;
+
+use crate::{error::JsonResult, parser::Parser, Value};
+
+/// A JSON file that has been memory-mapped and parsed.
+///
+/// The file is parsed straight out of the map rather than being read into a `String` first.
+/// The parsed [`Value`] doesn't borrow from the map, every string and key in it is its own copy,
+/// so this saves reading the file into memory but not the memory the value itself takes.
+/// The map stays alive as long as the document, so [`MappedDocument::source`] can be used to look at the original text.
+pub struct MappedDocument {
+    map: Mmap,
+    value: Value,
+}
+
+impl MappedDocument {
+    /// Memory-maps and parses the file at `path`.
+    /// Fails with [`crate::Error::Io`] if the file can't be mapped and [`crate::Error::InvalidUtf8`] if it isn't valid UTF-8.
+    pub fn open(path: impl AsRef<Path>) -> JsonResult<Self> {
+        let file = File::open(path)?;
+
+        // SAFETY: The map is read only. If the file is changed by another process while mapped
+        // the parsed value may be garbage, but that is the usual caveat of memory-mapped IO.
+        let map = unsafe { Mmap::map(&file)? };
+        let value = Parser::new(str::from_utf8(&map)?).parse()?;
+
+        Ok(Self { map, value })
+    }
+
+    /// Gets the parsed value.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Gets the original text of the file.
+    pub fn source(&self) -> &str {
+        // Checked to be valid UTF-8 in `open`
+        str::from_utf8(&self.map).unwrap()
+    }
+
+    /// Consumes the document, returning the parsed value and unmapping the file.
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+}
@@ -1,14 +1,20 @@
 use std::{
+    cmp::Ordering,
     fmt::{self, Display},
     hash::{Hash, Hasher},
+    num::IntErrorKind,
     str::FromStr,
 };
 
-use crate::Error;
+use crate::ParseNumberError;
 
 /// A JSON number.
-/// Can be a `u64`, `i64` or `f64`.
-#[derive(Debug, Clone, PartialOrd)]
+/// Can be a `u64`, `i64` or `f64`, a `u128` or `i128` for integers too big for 64 bits (with wider integers parsed as an `f64`), or the original text of the number when parsed with [`ParseOptions::raw_numbers`](crate::ParseOptions::raw_numbers).
+///
+/// Numbers of different variants are never equal, even if they have the same value (`1` vs `1.0`),
+/// but they are still ordered by value, with the variant only breaking ties.
+/// Float `NaN`s are equal to themselves, and `-0.0` is equal to `0.0`.
+#[derive(Debug, Clone)]
 pub enum Number {
     /// An unsigned integer.
     /// (u64)
@@ -16,9 +22,19 @@ pub enum Number {
     /// A signed integer.
     /// (i64)
     Int(i64),
+    /// An unsigned integer too big for a [`Number::UInt`].
+    /// (u128)
+    UInt128(u128),
+    /// A signed integer too small for a [`Number::Int`].
+    /// (i128)
+    Int128(i128),
     /// A floating point number.
     /// (f64)
     Float(f64),
+    /// A number kept exactly as it was written, so no precision or formatting is lost.
+    /// Ex. `1.10` or `123456789012345678901234567890`
+    /// Serialized verbatim and ordered by its value as a float.
+    Raw(String),
 }
 
 impl Display for Number {
@@ -26,7 +42,10 @@ impl Display for Number {
         match self {
             Self::UInt(x) => write!(f, "{x}"),
             Self::Int(x) => write!(f, "{x}"),
+            Self::UInt128(x) => write!(f, "{x}"),
+            Self::Int128(x) => write!(f, "{x}"),
             Self::Float(x) => write!(f, "{x}"),
+            Self::Raw(x) => f.write_str(x),
         }
     }
 }
@@ -36,36 +55,199 @@ impl Hash for Number {
         match self {
             Number::UInt(x) => x.hash(state),
             Number::Int(x) => x.hash(state),
-            Number::Float(x) => x.to_bits().hash(state),
+            Number::UInt128(x) => x.hash(state),
+            Number::Int128(x) => x.hash(state),
+            Number::Float(x) => normalize_zero(*x).to_bits().hash(state),
+            Number::Raw(x) => x.hash(state),
         }
     }
 }
 
 impl PartialEq for Number {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::UInt(l0), Self::UInt(r0)) => l0 == r0,
-            (Self::Int(l0), Self::Int(r0)) => l0 == r0,
-            (Self::Float(l0), Self::Float(r0)) => l0 == r0,
-            _ => false,
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders numbers by value, using the same order as [`f64::total_cmp`] for floats other than zero.
+/// Numbers with equal values are ordered `UInt < Int < UInt128 < Int128 < Float < Raw`, and raw numbers with equal values by their text.
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn rank(x: &Number) -> u8 {
+            match x {
+                Number::UInt(_) => 0,
+                Number::Int(_) => 1,
+                Number::UInt128(_) => 2,
+                Number::Int128(_) => 3,
+                Number::Float(_) => 4,
+                Number::Raw(_) => 5,
+            }
+        }
+
+        // Raw numbers are compared as floats
+        fn float(x: &Number) -> Option<f64> {
+            match x {
+                Number::Float(x) => Some(*x),
+                Number::Raw(x) => Some(x.parse().unwrap_or(f64::NAN)),
+                _ => None,
+            }
         }
+
+        let value = match (self.int_parts(), other.int_parts()) {
+            (Some(a), Some(b)) => cmp_ints(a, b),
+            (Some(a), None) => cmp_int_float(a, float(other).unwrap()),
+            (None, Some(b)) => cmp_int_float(b, float(self).unwrap()).reverse(),
+            (None, None) => {
+                let (a, b) = (float(self).unwrap(), float(other).unwrap());
+                normalize_zero(a).total_cmp(&normalize_zero(b))
+            }
+        };
+
+        value
+            .then_with(|| rank(self).cmp(&rank(other)))
+            .then_with(|| match (self, other) {
+                (Self::Raw(a), Self::Raw(b)) => a.cmp(b),
+                _ => Ordering::Equal,
+            })
     }
 }
 
 impl FromStr for Number {
-    type Err = Error;
+    type Err = ParseNumberError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.contains('.') {
+        check_digits(s)?;
+
+        if s.contains(['.', 'e', 'E']) {
             return Ok(Number::Float(s.parse::<f64>()?));
         }
 
+        // Integers only use 128 bits if they don't fit in 64, and become floats if they don't fit in 128
         if s.starts_with('-') {
-            return Ok(Number::Int(s.parse::<i64>()?));
+            return Ok(match s.parse::<i64>() {
+                Ok(x) => Number::Int(x),
+                Err(_) => match s.parse::<i128>() {
+                    Ok(x) => Number::Int128(x),
+                    Err(e) if *e.kind() == IntErrorKind::NegOverflow => Number::Float(s.parse()?),
+                    Err(e) => return Err(e.into()),
+                },
+            });
         }
 
-        Ok(Number::UInt(s.parse::<u64>()?))
+        Ok(match s.parse::<u64>() {
+            Ok(x) => Number::UInt(x),
+            Err(_) => match s.parse::<u128>() {
+                Ok(x) => Number::UInt128(x),
+                Err(e) if *e.kind() == IntErrorKind::PosOverflow => Number::Float(s.parse()?),
+                Err(e) => return Err(e.into()),
+            },
+        })
     }
 }
 
 impl Eq for Number {}
+
+impl Number {
+    /// Checks `s` is a valid number and keeps it as a [`Number::Raw`], without limiting its range or precision.
+    pub(crate) fn parse_raw(s: &str) -> Result<Self, ParseNumberError> {
+        check_digits(s)?;
+        // Letters other than exponents would let through `inf` and `NaN`
+        if !s
+            .bytes()
+            .all(|x| x.is_ascii_digit() || b"-+.eE".contains(&x))
+        {
+            return Err(ParseNumberError::MissingDigits);
+        }
+
+        s.parse::<f64>()?;
+        Ok(Number::Raw(s.to_owned()))
+    }
+
+    /// Splits an integer into its sign and magnitude, so integers of any variant can be compared.
+    fn int_parts(&self) -> Option<(bool, u128)> {
+        match *self {
+            Number::UInt(x) => Some((false, x as u128)),
+            Number::Int(x) => Some((x < 0, x.unsigned_abs() as u128)),
+            Number::UInt128(x) => Some((false, x)),
+            Number::Int128(x) => Some((x < 0, x.unsigned_abs())),
+            _ => None,
+        }
+    }
+
+    /// Checks if the number has no fractional part or exponent.
+    pub(crate) fn is_integer(&self) -> bool {
+        match self {
+            Number::UInt(_) | Number::Int(_) | Number::UInt128(_) | Number::Int128(_) => true,
+            Number::Float(_) => false,
+            Number::Raw(x) => !x.contains(['.', 'e', 'E']),
+        }
+    }
+}
+
+/// Checks there are digits before the decimal point and after it, if there is one.
+fn check_digits(s: &str) -> Result<(), ParseNumberError> {
+    // Rust accepts `1.` and `.5`, but JSON needs digits on both sides of the dot
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let (int, frac) = match unsigned.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (unsigned, None),
+    };
+
+    match int.is_empty() || frac.is_some_and(|x| !x.starts_with(|c: char| c.is_ascii_digit())) {
+        true => Err(ParseNumberError::MissingDigits),
+        false => Ok(()),
+    }
+}
+
+fn normalize_zero(x: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        x
+    }
+}
+
+/// Compares two integers given as their sign and magnitude.
+fn cmp_ints((a_neg, a): (bool, u128), (b_neg, b): (bool, u128)) -> Ordering {
+    match (a_neg, b_neg) {
+        (false, false) => a.cmp(&b),
+        (true, true) => b.cmp(&a),
+        (a_neg, b_neg) => b_neg.cmp(&a_neg),
+    }
+}
+
+/// Compares an integer, given as its sign and magnitude, with a float exactly, without rounding the integer.
+/// `NaN`s are ordered like [`f64::total_cmp`], past the infinity of the same sign.
+fn cmp_int_float((neg, magnitude): (bool, u128), float: f64) -> Ordering {
+    if float.is_nan() {
+        return match float.is_sign_negative() {
+            true => Ordering::Greater,
+            false => Ordering::Less,
+        };
+    }
+
+    let int = match neg {
+        true => -(magnitude as f64),
+        false => magnitude as f64,
+    };
+    match int.partial_cmp(&float).unwrap() {
+        Ordering::Equal => {
+            // `float` is a whole number here, but may be just past the largest `u128`
+            let exact = match float.abs() >= u128::MAX as f64 {
+                true => Ordering::Less,
+                false => magnitude.cmp(&(float.abs() as u128)),
+            };
+            match neg {
+                true => exact.reverse(),
+                false => exact,
+            }
+        }
+        x => x,
+    }
+}
@@ -0,0 +1,78 @@
+use rayon::prelude::*;
+
+use crate::{
+    error::JsonResult,
+    parser::{is_json_whitespace, Parser},
+    Error, Value,
+};
+
+/// Parses a document whose root is a (potentially huge) array, parsing its elements in parallel.
+///
+/// The top level of the array is split at its commas with a string-aware bracket scan,
+/// each slice is parsed on the rayon thread pool and the results are concatenated in order.
+/// Accepts the same input as [`Value::from_str`](std::str::FromStr::from_str), so commas are skipped like whitespace
+/// and ex. `[1,,2]`, `[1,2,]` and `[1 2]` all give `[1, 2]`.
+/// Error positions are relative to the whole of `input`.
+/// If the root isn't an array, this just falls back to the normal serial parser.
+pub fn parse_large_array(input: &str) -> JsonResult<Value> {
+    let start = match input.find(|x: char| !is_json_whitespace(x)) {
+        Some(x) if input.as_bytes()[x] == b'[' => x,
+        _ => return Parser::new(input).parse(),
+    };
+
+    split_elements(input, start)?
+        .into_par_iter()
+        .map(|(start, end)| parse_elements(&input[start..end]).map_err(|e| e.offset(start)))
+        .collect::<JsonResult<Vec<_>>>()
+        .map(|x| Value::Array(x.into_iter().flatten().collect()))
+}
+
+/// Parses every value in `slice`, which can be none if it's empty or more than one if they aren't separated by commas.
+fn parse_elements(slice: &str) -> JsonResult<Vec<Value>> {
+    let mut parser = Parser::new(slice);
+    let mut values = Vec::new();
+    while !parser.is_finished() {
+        values.push(parser.parse()?);
+    }
+    Ok(values)
+}
+
+/// Finds the byte range of every top level element in the array starting at `start`.
+fn split_elements(input: &str, start: usize) -> JsonResult<Vec<(usize, usize)>> {
+    let bytes = input.as_bytes();
+    let mut elements = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escape = false;
+    let mut element_start = start + 1;
+
+    for (i, &chr) in bytes.iter().enumerate().skip(start + 1) {
+        if in_string {
+            match chr {
+                _ if escape => escape = false,
+                b'\\' => escape = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match chr {
+            b'"' => in_string = true,
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' if depth > 0 => depth -= 1,
+            b',' if depth == 0 => {
+                elements.push((element_start, i));
+                element_start = i + 1;
+            }
+            b']' => {
+                elements.push((element_start, i));
+                return Ok(elements);
+            }
+            b'}' => return Err(Error::UnexpectedChar(i)),
+            _ => {}
+        }
+    }
+
+    Err(Error::UnexpectedEnd(input.len()))
+}
@@ -1,22 +1,99 @@
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+
+use crate::{
+    error::JsonResult,
+    map::{MapKey, MapKind},
+    value::GenericValue,
+    Error, InternedValue, KeyInterner, Number, ParseNumberError, Value,
+};
+
+/// Options for [`Value::from_str_with`] and [`Parser::with_options`].
+/// The defaults match [`Value::from_str`](std::str::FromStr::from_str).
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Whether numbers are kept as their original text in a [`Number::Raw`], rather than parsed.
+    /// Ex. `1.10` stays `1.10` instead of becoming `1.1`, and integers too big for a `u64` don't lose precision.
+    /// Defaults to false.
+    pub raw_numbers: bool,
+    /// The most characters a number can have, including any sign, decimal point and exponent.
+    /// Longer numbers fail with [`ParseNumberError::TooLong`](crate::ParseNumberError::TooLong) without being parsed,
+    /// so huge inputs can't make the parser spend long on a single number.
+    /// Defaults to 1024.
+    pub max_number_len: usize,
+    /// The most bytes a string can have between its quotes, before escapes are decoded.
+    /// Longer strings fail with [`Error::StringTooLong`] as soon as the limit is passed.
+    /// Defaults to no limit.
+    pub max_string_len: usize,
+    /// The most values that can be parsed, counting every array element, object value and the value holding them.
+    /// Ex. `[1, [2]]` is four values.
+    /// Going over fails with [`Error::TooManyNodes`], bounding the work done however the input is shaped.
+    /// When a [`Parser`] parses several values in a row, they all count towards the same limit.
+    /// Defaults to no limit.
+    pub max_nodes: usize,
+    /// Where object keys are shared from when parsing an [`InternedValue`], so documents with the same keys don't each store their own copy.
+    /// Values with `String` keys, like [`Value`], can't share keys and ignore it.
+    /// Defaults to none, so each key gets its own allocation.
+    pub interner: Option<KeyInterner>,
+}
 
-use crate::{error::JsonResult, Error, Value};
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            raw_numbers: false,
+            max_number_len: 1024,
+            max_string_len: usize::MAX,
+            max_nodes: usize::MAX,
+            interner: None,
+        }
+    }
+}
 
-pub(super) struct Parser<'a> {
+/// A JSON parser over a string.
+/// [`Value::from_str`](std::str::FromStr::from_str) is the simple way to parse a single value,
+/// but a `Parser` can be driven directly to parse several values in a row or to track how much of the input was used.
+/// Ex. `Parser::new("1 [2] {}")` gives `1`, `[2]` and `{}` from three calls to [`Parser::parse`].
+pub struct Parser<'a> {
     input: &'a str,
     len: usize,
     pos: usize,
+    options: ParseOptions,
+    // The number of values started so far, for `ParseOptions::max_nodes`
+    nodes: usize,
 }
 
 impl<'a> Parser<'a> {
-    pub(super) fn new(inp: &'a str) -> Self {
+    /// Creates a parser starting at the beginning of `inp`.
+    pub fn new(inp: &'a str) -> Self {
+        Self::with_options(inp, ParseOptions::default())
+    }
+
+    /// Creates a parser starting at the beginning of `inp` that uses `options`.
+    pub fn with_options(inp: &'a str, options: ParseOptions) -> Self {
         Self {
             input: inp,
             len: inp.len(),
             pos: 0,
+            options,
+            nodes: 0,
         }
     }
 
+    /// Gets the current position in the input, in bytes.
+    /// After a call to [`Parser::parse`], this is just past the end of the parsed value.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Gets the part of the input that hasn't been parsed yet.
+    pub fn remaining(&self) -> &'a str {
+        &self.input[self.pos.min(self.len)..]
+    }
+
+    /// Checks if there is nothing but whitespace left to parse.
+    pub fn is_finished(&self) -> bool {
+        self.remaining().chars().all(is_whitespace)
+    }
+
     fn char(&self, pos: usize) -> char {
         self.input.as_bytes()[pos] as char
     }
@@ -39,39 +116,55 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    pub(super) fn parse(&mut self) -> Result<Value, Error> {
-        if self.len == 0 {
+    /// Parses the next value, skipping any whitespace before it.
+    /// Fails with [`Error::UnexpectedEnd`] if there is nothing left to parse.
+    pub fn parse(&mut self) -> Result<Value, Error> {
+        self.value()
+    }
+
+    /// Like [`Parser::parse`], but parses into an [`InternedValue`], sharing keys through [`ParseOptions::interner`].
+    pub fn parse_interned(&mut self) -> Result<InternedValue, Error> {
+        self.value()
+    }
+
+    fn value<K: MapKind>(&mut self) -> JsonResult<GenericValue<K>> {
+        self.skip_whitespace();
+        if self.pos >= self.len {
             return Err(Error::UnexpectedEnd(self.pos));
         }
 
-        self.skip_whitespace();
+        self.nodes += 1;
+        if self.nodes > self.options.max_nodes {
+            return Err(Error::TooManyNodes {
+                pos: self.pos,
+                max: self.options.max_nodes,
+            });
+        }
+
         let chr = self.char(self.pos);
         match chr {
             'n' => self.parse_null(),
             't' | 'f' => self.parse_bool(),
-            '0'..='9' | '-' => self.parse_number(),
+            '0'..='9' | '-' | '.' => self.parse_number(),
             '"' => self.parse_string(),
             '[' => self.parse_array(),
             '{' => self.parse_object(),
-            x => todo!("Error {x}"),
+            _ => Err(Error::UnexpectedChar(self.pos)),
         }
     }
 
     fn skip_whitespace(&mut self) {
-        fn skip(x: char) -> bool {
-            x.is_whitespace() || x == ','
+        while self.pos < self.len && is_whitespace(self.char(self.pos)) {
+            self.pos += 1;
         }
-
-        while self.pos < self.len && skip(self.next()) {}
-        self.pos -= 1;
     }
 
-    fn parse_null(&mut self) -> JsonResult<Value> {
+    fn parse_null<K: MapKind>(&mut self) -> JsonResult<GenericValue<K>> {
         self.require_chars(b"null")?;
-        Ok(Value::Null)
+        Ok(GenericValue::Null)
     }
 
-    fn parse_bool(&mut self) -> JsonResult<Value> {
+    fn parse_bool<K: MapKind>(&mut self) -> JsonResult<GenericValue<K>> {
         let expected = self.next();
         match expected {
             't' => self.require_chars(b"rue")?,
@@ -79,141 +172,171 @@ impl<'a> Parser<'a> {
             _ => return Err(Error::UnexpectedChar(self.pos)),
         };
 
-        Ok(Value::Bool(expected == 't'))
+        Ok(GenericValue::Bool(expected == 't'))
     }
 
-    fn parse_number(&mut self) -> JsonResult<Value> {
+    fn parse_number<K: MapKind>(&mut self) -> JsonResult<GenericValue<K>> {
+        // Letters are included so `123d` is one invalid number rather than `123` followed by junk
         fn is_digit(digit: char) -> bool {
-            ('0'..='9').contains(&digit) || matches!(digit, '-' | '.')
+            digit.is_ascii_alphanumeric() || matches!(digit, '-' | '+' | '.')
         }
 
         let start = self.pos;
-        while self.pos < self.len && is_digit(self.next()) {}
+        while self.pos < self.len && is_digit(self.char(self.pos)) {
+            if self.pos - start == self.options.max_number_len {
+                return Err(Error::InvalidNumber {
+                    pos: start,
+                    error: ParseNumberError::TooLong(self.options.max_number_len),
+                });
+            }
+            self.pos += 1;
+        }
 
         let num = &self.input[start..self.pos];
-        Ok(Value::Number(num.parse()?))
-    }
-
-    fn parse_string(&mut self) -> JsonResult<Value> {
-        fn unescape(s: &str) -> Result<String, Error> {
-            let mut out = String::new();
-            let mut escape = false;
-
-            for i in s.chars() {
-                if escape {
-                    match i {
-                        '"' => out.push('"'),
-                        '\\' => out.push('\\'),
-                        '/' => out.push('/'),
-                        'b' => out.push('\x08'),
-                        'f' => out.push('\x0C'),
-                        'n' => out.push('\x0A'),
-                        'r' => out.push('\x0D'),
-                        't' => out.push('\x09'),
-                        _ => return Err(Error::InvalidEscape(i)),
-                    }
-                    escape = false;
-                    continue;
-                }
-
-                if i == '\\' {
-                    escape = true;
-                    continue;
-                }
-
-                out.push(i);
-            }
+        let number = match self.options.raw_numbers {
+            true => Number::parse_raw(num),
+            false => num.parse(),
+        };
+        number
+            .map(GenericValue::Number)
+            .map_err(|error| Error::InvalidNumber { pos: start, error })
+    }
 
-            Ok(out)
-        }
+    fn parse_string<K: MapKind>(&mut self) -> JsonResult<GenericValue<K>> {
+        self.parse_str()
+            .map(|x| GenericValue::String(x.into_owned()))
+    }
 
+    // Only allocates if the string has escapes, so interned keys that have already been seen aren't copied
+    fn parse_str(&mut self) -> JsonResult<Cow<'a, str>> {
         self.pos += 1;
         let start = self.pos;
-        let mut escape = false;
-        while self.pos < self.len && (self.char(self.pos) != '"' || escape) {
-            if self.char(self.pos) == '\\' {
-                escape = true;
-                self.pos += 1;
-                continue;
+        while self.pos < self.len && self.char(self.pos) != '"' {
+            // Skips the escaped character too, so an escaped backslash can't escape the closing quote
+            self.pos += match self.char(self.pos) {
+                '\\' => 2,
+                _ => 1,
+            };
+
+            if self.pos - start > self.options.max_string_len {
+                return Err(Error::StringTooLong {
+                    pos: start - 1,
+                    max: self.options.max_string_len,
+                });
             }
-            self.pos += 1;
-            escape = false;
         }
 
-        if self.pos == self.len && self.char(self.pos - 1) != '"' {
-            return Err(Error::UnexpectedEnd(self.pos));
+        // A trailing backslash skips past the end of the input
+        if self.pos >= self.len {
+            self.pos = self.len;
+            return Err(Error::UnexpectedEnd(self.len));
         }
 
         let string = &self.input[start..self.pos];
         self.pos += 1;
-        Ok(Value::String(unescape(string)?))
+        match string.contains('\\') {
+            true => Ok(Cow::Owned(unescape(string, start)?)),
+            false => Ok(Cow::Borrowed(string)),
+        }
     }
 
-    fn parse_array(&mut self) -> JsonResult<Value> {
+    // Parses elements one after another until the closing `]`, so brackets in strings are never mistaken for structure
+    fn parse_array<K: MapKind>(&mut self) -> JsonResult<GenericValue<K>> {
         self.pos += 1;
-        let start = self.pos;
-        let mut depth = 1;
-        while self.pos < self.len && depth != 0 {
-            match self.next() {
-                '[' => depth += 1,
-                ']' => depth -= 1,
-                _ => {}
+        let mut tokens = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.pos >= self.len {
+                return Err(Error::UnexpectedEnd(self.pos));
             }
-        }
 
-        if self.pos == self.len && depth != 0 {
-            return Err(Error::UnexpectedEnd(self.pos));
+            if self.char(self.pos) == ']' {
+                break;
+            }
+            tokens.push(self.value()?);
         }
 
-        let end = self.pos;
-        self.pos = start;
+        // Just past the closing `]`
+        self.pos += 1;
+        Ok(GenericValue::Array(tokens))
+    }
 
+    // Parses entries one after another until the closing `}`, so braces and colons in strings are never mistaken for structure
+    fn parse_object<K: MapKind>(&mut self) -> JsonResult<GenericValue<K>> {
+        self.pos += 1;
         let mut tokens = Vec::new();
-        while self.pos < end.saturating_sub(1) {
+        loop {
+            self.skip_whitespace();
+            if self.pos >= self.len {
+                return Err(Error::UnexpectedEnd(self.pos));
+            }
+
+            match self.char(self.pos) {
+                '}' => break,
+                '"' => {}
+                _ => return Err(Error::UnexpectedChar(self.pos)),
+            }
+
+            let name = self.parse_str()?;
+            let name = K::Key::from_parsed(name, self.options.interner.as_ref());
             self.skip_whitespace();
-            tokens.push(self.parse()?);
+            self.require_chars(b":")?;
+            tokens.push((name, self.value()?));
         }
 
+        // Just past the closing `}`
         self.pos += 1;
-        Ok(Value::Array(tokens))
+        // Collecting keeps the last of any duplicate keys, for both `BTreeMap` and `HashMap`
+        Ok(GenericValue::Object(tokens.into_iter().collect()))
     }
+}
 
-    fn parse_object(&mut self) -> JsonResult<Value> {
-        self.pos += 1;
-        let start = self.pos;
-        let mut depth = 1;
-        while self.pos < self.len && depth != 0 {
-            match self.next() {
-                '{' => depth += 1,
-                '}' => depth -= 1,
-                _ => {}
+/// Unescapes the contents of a string, without its quotes.
+/// `offset` is where `s` starts in the input, used for error positions.
+pub(crate) fn unescape(s: &str, offset: usize) -> Result<String, Error> {
+    let mut out = String::new();
+    let mut escape = false;
+
+    for (pos, i) in s.char_indices() {
+        if escape {
+            match i {
+                '"' => out.push('"'),
+                '\\' => out.push('\\'),
+                '/' => out.push('/'),
+                'b' => out.push('\x08'),
+                'f' => out.push('\x0C'),
+                'n' => out.push('\x0A'),
+                'r' => out.push('\x0D'),
+                't' => out.push('\x09'),
+                _ => {
+                    return Err(Error::InvalidEscape {
+                        pos: offset + pos - 1,
+                        found: i,
+                    })
+                }
             }
+            escape = false;
+            continue;
         }
 
-        if self.pos == self.len && depth != 0 {
-            return Err(Error::UnexpectedEnd(self.pos));
+        if i == '\\' {
+            escape = true;
+            continue;
         }
 
-        let end = self.pos;
-        self.pos = start;
+        out.push(i);
+    }
 
-        let mut tokens = BTreeMap::new();
-        while self.pos < end.saturating_sub(1) {
-            self.skip_whitespace();
-            let key = self.parse_string()?;
-            self.skip_whitespace();
-            self.require_chars(b":")?;
-            self.skip_whitespace();
-            let value = self.parse()?;
+    Ok(out)
+}
 
-            let name = match key {
-                Value::String(s) => s,
-                _ => unreachable!(),
-            };
-            tokens.insert(name, value);
-        }
+/// Checks if `x` is one of the four whitespace characters allowed between tokens by the JSON spec.
+/// Other Unicode whitespace, like vertical tab or non-breaking space, is only allowed inside strings.
+pub(crate) fn is_json_whitespace(x: char) -> bool {
+    matches!(x, ' ' | '\t' | '\n' | '\r')
+}
 
-        self.pos += 1;
-        Ok(Value::Object(tokens))
-    }
+/// Commas are skipped like whitespace when parsing.
+fn is_whitespace(x: char) -> bool {
+    is_json_whitespace(x) || x == ','
 }
@@ -0,0 +1,190 @@
+//! [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON Patch.
+
+use std::{collections::BTreeMap, mem};
+
+use crate::{pointer, JsonPointerBuf, Value};
+
+/// Errors that can occur while applying a JSON Patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchError {
+    /// The patch document is not an array of operation objects.
+    /// Contains the index of the bad operation, or `None` if the patch itself isn't an array.
+    InvalidPatch(Option<usize>),
+    /// An operation referred to a location that doesn't exist or can't be written to.
+    /// Contains the index of the operation.
+    PathNotFound(usize),
+    /// A `test` operation failed.
+    /// Contains the index of the operation.
+    TestFailed(usize),
+}
+
+/// Applies the JSON Patch `patch` to `doc`, returning the patched document.
+/// Patches are atomic, so if any operation fails, the error is returned and nothing is changed.
+///
+/// Supports every operation: `add`, `remove`, `replace`, `move`, `copy` and `test`.
+pub fn apply_patch(doc: &Value, patch: &Value) -> Result<Value, PatchError> {
+    let ops = patch.as_array().ok_or(PatchError::InvalidPatch(None))?;
+    let mut doc = doc.clone();
+
+    for (i, op) in ops.iter().enumerate() {
+        apply_op(&mut doc, op, i)?;
+    }
+
+    Ok(doc)
+}
+
+/// Creates a JSON Patch that turns `from` into `to`.
+/// The patch only uses `add`, `remove` and `replace` operations.
+///
+/// Objects are compared key by key and arrays index by index,
+/// with elements added or removed from the end to make up the difference in length.
+pub fn create_patch(from: &Value, to: &Value) -> Value {
+    let mut ops = Vec::new();
+    diff(&mut JsonPointerBuf::new(), from, to, &mut ops);
+    Value::Array(ops)
+}
+
+fn diff(path: &mut JsonPointerBuf, from: &Value, to: &Value, ops: &mut Vec<Value>) {
+    if from == to {
+        return;
+    }
+
+    match (from, to) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (k, v) in a {
+                let mut path = path.clone();
+                path.push_key(k);
+                match b.get(k) {
+                    Some(x) => diff(&mut path, v, x, ops),
+                    None => ops.push(op("remove", &path, None)),
+                }
+            }
+
+            for (k, v) in b.iter().filter(|x| !a.contains_key(x.0)) {
+                let mut path = path.clone();
+                path.push_key(k);
+                ops.push(op("add", &path, Some(v)));
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for (i, (x, y)) in a.iter().zip(b).enumerate() {
+                let mut path = path.clone();
+                path.push_index(i);
+                diff(&mut path, x, y, ops);
+            }
+
+            // Remove from the back so earlier indices stay valid
+            for i in (b.len()..a.len()).rev() {
+                let mut path = path.clone();
+                path.push_index(i);
+                ops.push(op("remove", &path, None));
+            }
+
+            for (i, x) in b.iter().enumerate().skip(a.len()) {
+                let mut path = path.clone();
+                path.push_index(i);
+                ops.push(op("add", &path, Some(x)));
+            }
+        }
+        _ => ops.push(op("replace", path, Some(to))),
+    }
+}
+
+fn op(kind: &str, path: &JsonPointerBuf, value: Option<&Value>) -> Value {
+    let mut out = BTreeMap::new();
+    out.insert("op".to_owned(), Value::String(kind.to_owned()));
+    out.insert("path".to_owned(), Value::String(path.to_string()));
+    if let Some(x) = value {
+        out.insert("value".to_owned(), x.clone());
+    }
+
+    Value::Object(out)
+}
+
+fn apply_op(doc: &mut Value, op: &Value, i: usize) -> Result<(), PatchError> {
+    let invalid = PatchError::InvalidPatch(Some(i));
+    let not_found = PatchError::PathNotFound(i);
+
+    let op = op.as_object().ok_or(invalid)?;
+    let field = |name: &str| op.get(name).ok_or(invalid);
+    let pointer = |name: &str| {
+        let path = field(name)?.as_string().ok_or(invalid)?;
+        pointer::tokens(path).ok_or(invalid)
+    };
+
+    let kind = field("op")?.as_string().ok_or(invalid)?;
+    let path = pointer("path")?;
+
+    match kind.as_str() {
+        "add" => add(doc, &path, field("value")?.clone()).ok_or(not_found),
+        "remove" => remove(doc, &path).map(drop).ok_or(not_found),
+        "replace" => {
+            *get_mut(doc, &path).ok_or(not_found)? = field("value")?.clone();
+            Ok(())
+        }
+        "move" => {
+            let from = pointer("from")?;
+            // A value can't be moved into one of its own children
+            if path.len() > from.len() && path.starts_with(&from) {
+                return Err(not_found);
+            }
+
+            let value = remove(doc, &from).ok_or(not_found)?;
+            add(doc, &path, value).ok_or(not_found)
+        }
+        "copy" => {
+            let from = pointer("from")?;
+            let value = get_mut(doc, &from).ok_or(not_found)?.clone();
+            add(doc, &path, value).ok_or(not_found)
+        }
+        "test" => match get_mut(doc, &path) {
+            Some(x) if x == field("value")? => Ok(()),
+            _ => Err(PatchError::TestFailed(i)),
+        },
+        _ => Err(invalid),
+    }
+}
+
+fn get_mut<'a>(doc: &'a mut Value, path: &[String]) -> Option<&'a mut Value> {
+    doc.walk_mut(path)
+}
+
+fn add(doc: &mut Value, path: &[String], value: Value) -> Option<()> {
+    let (last, parent) = match path.split_last() {
+        Some(x) => x,
+        None => {
+            *doc = value;
+            return Some(());
+        }
+    };
+
+    match get_mut(doc, parent)? {
+        Value::Object(o) => {
+            o.insert(last.to_owned(), value);
+        }
+        Value::Array(a) if last == "-" => a.push(value),
+        Value::Array(a) => {
+            let index = pointer::index(last).filter(|x| *x <= a.len())?;
+            a.insert(index, value);
+        }
+        _ => return None,
+    }
+
+    Some(())
+}
+
+fn remove(doc: &mut Value, path: &[String]) -> Option<Value> {
+    let (last, parent) = match path.split_last() {
+        Some(x) => x,
+        None => return Some(mem::replace(doc, Value::Null)),
+    };
+
+    match get_mut(doc, parent)? {
+        Value::Object(o) => o.remove(last),
+        Value::Array(a) => {
+            let index = pointer::index(last).filter(|x| *x < a.len())?;
+            Some(a.remove(index))
+        }
+        _ => None,
+    }
+}
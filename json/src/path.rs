@@ -0,0 +1,78 @@
+use std::fmt::{self, Display};
+
+use crate::JsonPointerBuf;
+
+/// A location inside of a JSON document.
+/// Made up of object keys and array indices, starting from the root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JsonPath {
+    segments: Vec<PathSegment>,
+}
+
+/// One step of a [`JsonPath`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// A key of an object.
+    Key(String),
+    /// An index into an array.
+    Index(usize),
+}
+
+impl JsonPath {
+    /// Creates a path pointing at the root of a document.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Gets the segments of the path, from the root down.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Checks if the path points at the root of the document.
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Gets the last segment of the path, if it isn't the root.
+    pub fn last(&self) -> Option<&PathSegment> {
+        self.segments.last()
+    }
+
+    /// Adds a segment to the end of the path.
+    pub fn push(&mut self, segment: PathSegment) {
+        self.segments.push(segment);
+    }
+
+    /// Adds a segment to the start of the path.
+    /// Used when building up paths while unwinding out of nested values.
+    pub(crate) fn push_front(&mut self, segment: PathSegment) {
+        self.segments.insert(0, segment);
+    }
+
+    /// Removes the last segment of the path.
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.segments.pop()
+    }
+
+    /// Converts the path into a JSON Pointer.
+    pub fn to_pointer(&self) -> JsonPointerBuf {
+        let mut out = JsonPointerBuf::new();
+        for i in &self.segments {
+            match i {
+                PathSegment::Key(k) => out.push_key(k),
+                PathSegment::Index(x) => out.push_index(*x),
+            }
+        }
+
+        out
+    }
+}
+
+/// Formats the path as a JSON Pointer.
+/// Ex. `/users/0/name`
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_pointer().fmt(f)
+    }
+}
@@ -0,0 +1,92 @@
+//! [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer helpers.
+
+use std::{
+    fmt::{self, Display},
+    ops::Deref,
+};
+
+/// An owned JSON Pointer that can be built up one reference token at a time.
+/// Ex. `/users/0/name`
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JsonPointerBuf {
+    inner: String,
+}
+
+impl JsonPointerBuf {
+    /// Creates the empty pointer, which refers to the whole document.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds an object key to the end of the pointer, escaping it as needed.
+    pub fn push_key(&mut self, key: &str) {
+        self.inner.push('/');
+        self.inner.push_str(&escape(key));
+    }
+
+    /// Adds an array index to the end of the pointer.
+    pub fn push_index(&mut self, index: usize) {
+        self.inner.push('/');
+        self.inner.push_str(&index.to_string());
+    }
+
+    /// Gets the pointer as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl Deref for JsonPointerBuf {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl Display for JsonPointerBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl From<JsonPointerBuf> for String {
+    fn from(pointer: JsonPointerBuf) -> Self {
+        pointer.inner
+    }
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+/// The empty pointer refers to the whole document and yields no tokens.
+/// Returns `None` if the pointer is non-empty and doesn't start with a `/`.
+pub(crate) fn tokens(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let rest = pointer.strip_prefix('/')?;
+    Some(rest.split('/').map(unescape).collect())
+}
+
+/// Parses a reference token as an array index.
+/// Leading zeros are not allowed (other than `0` itself).
+pub(crate) fn index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+
+    if !token.bytes().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+/// Escapes `~` and `/` in a reference token.
+pub(crate) fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
@@ -0,0 +1,210 @@
+//! Converting between objects and `application/x-www-form-urlencoded` query strings.
+//!
+//! Nested objects use the bracket syntax understood by most web frameworks, so `{"a": {"b": 1}}` becomes `a[b]=1`.
+
+use std::{collections::BTreeMap, mem};
+
+use crate::Value;
+
+/// Errors that can occur while building a query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// The value isn't an object.
+    NotObject,
+    /// An array contained another array or an object, which has no query string form.
+    /// Contains the key of the array, in bracket syntax.
+    NestedArray(String),
+}
+
+/// How arrays are written in a query string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayFormat {
+    /// Repeats the key for each element.
+    /// Ex. `tags=a&tags=b`
+    #[default]
+    Repeat,
+    /// Repeats the key with a `[]` suffix for each element.
+    /// Unlike [`ArrayFormat::Repeat`], this keeps single element arrays as arrays when parsed.
+    /// Ex. `tags[]=a&tags[]=b`
+    Brackets,
+}
+
+/// Converts an object into a query string, writing arrays as repeated keys.
+/// Ex. `{"q": "rust json", "page": 2, "tags": ["a", "b"]}` => `page=2&q=rust%20json&tags=a&tags=b`
+///
+/// Keys and values are percent-encoded, leaving only ASCII letters, digits and `*-._` as they are.
+/// Nulls are written as a key without a value and empty arrays and objects are left out.
+pub fn to_query_string(value: &Value) -> Result<String, QueryError> {
+    to_query_string_with(value, ArrayFormat::Repeat)
+}
+
+/// Like [`to_query_string`], but with the given way of writing arrays.
+pub fn to_query_string_with(value: &Value, arrays: ArrayFormat) -> Result<String, QueryError> {
+    let object = value.as_object().ok_or(QueryError::NotObject)?;
+    let mut pairs = Vec::new();
+    for (k, v) in object {
+        write_pairs(&mut pairs, encode(k), v, arrays)?;
+    }
+
+    Ok(pairs.join("&"))
+}
+
+/// Parses a query string into a [`Value::Object`], rebuilding nested objects from bracket syntax.
+/// Ex. `a[b]=1&c=2&c=3&d[]=4` => `{"a": {"b": "1"}, "c": ["2", "3"], "d": ["4"]}`
+///
+/// Keys and values are percent-decoded before brackets are parsed, so `a%5Bb%5D` is the same as `a[b]`.
+/// `+` is decoded into a space, and invalid escapes are left as they are.
+/// Repeated keys are collected into an array and keys without an `=` map to null.
+/// A `[]` suffix always creates an array, and is only recognized at the end of a key.
+pub fn from_query_string(query: &str) -> Value {
+    let mut out = BTreeMap::new();
+
+    for i in query.split('&').filter(|x| !x.is_empty()) {
+        let (key, value) = match i.split_once('=') {
+            Some((k, v)) => (decode(k), Value::String(decode(v))),
+            None => (decode(i), Value::Null),
+        };
+
+        let (name, path) = split_key(&key);
+        insert(&mut out, name, path, value);
+    }
+
+    Value::Object(out)
+}
+
+fn write_pairs(
+    pairs: &mut Vec<String>,
+    key: String,
+    value: &Value,
+    arrays: ArrayFormat,
+) -> Result<(), QueryError> {
+    match value {
+        Value::Null => pairs.push(key),
+        Value::String(s) => pairs.push(format!("{key}={}", encode(s))),
+        Value::Object(o) => {
+            for (k, v) in o {
+                write_pairs(pairs, format!("{key}[{}]", encode(k)), v, arrays)?;
+            }
+        }
+        Value::Array(a) => {
+            let key = match arrays {
+                ArrayFormat::Repeat => key,
+                ArrayFormat::Brackets => format!("{key}[]"),
+            };
+
+            for i in a {
+                if i.is_array() || i.is_object() {
+                    return Err(QueryError::NestedArray(decode(&key)));
+                }
+                write_pairs(pairs, key.to_owned(), i, arrays)?;
+            }
+        }
+        x => pairs.push(format!("{key}={x}")),
+    }
+
+    Ok(())
+}
+
+/// Splits a key like `a[b][c][]` into its name and path.
+/// Keys with unbalanced brackets or text after a `]` are used as they are.
+fn split_key(key: &str) -> (&str, Vec<&str>) {
+    let (name, mut rest) = match key.find('[') {
+        Some(i) if i > 0 => key.split_at(i),
+        _ => return (key, Vec::new()),
+    };
+
+    let mut path = Vec::new();
+    while !rest.is_empty() {
+        match rest.strip_prefix('[').and_then(|x| x.split_once(']')) {
+            Some((segment, next)) => {
+                path.push(segment);
+                rest = next;
+            }
+            None => return (key, Vec::new()),
+        }
+    }
+
+    (name, path)
+}
+
+fn insert(object: &mut BTreeMap<String, Value>, key: &str, path: Vec<&str>, value: Value) {
+    let mut path = path.into_iter();
+    let next = match path.next() {
+        Some(x) => x,
+        None => {
+            match object.get_mut(key) {
+                Some(Value::Array(a)) => a.push(value),
+                Some(x) => *x = Value::Array(vec![mem::replace(x, Value::Null), value]),
+                None => {
+                    object.insert(key.to_owned(), value);
+                }
+            }
+            return;
+        }
+    };
+
+    let slot = object.entry(key.to_owned()).or_insert(Value::Null);
+    if next.is_empty() && path.len() == 0 {
+        match slot {
+            Value::Array(a) => a.push(value),
+            Value::Null => *slot = Value::Array(vec![value]),
+            x => *x = Value::Array(vec![mem::replace(x, Value::Null), value]),
+        }
+        return;
+    }
+
+    // A nested key replaces anything at the same key that isn't an object
+    if !slot.is_object() {
+        *slot = Value::Object(BTreeMap::new());
+    }
+    if let Value::Object(o) = slot {
+        insert(o, next, path.collect(), value);
+    }
+}
+
+/// Percent-encodes everything other than ASCII letters, digits and `*-._`.
+fn encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for i in s.bytes() {
+        match i {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                out.push(i as char)
+            }
+            x => out.push_str(&format!("%{x:02X}")),
+        }
+    }
+
+    out
+}
+
+/// Decodes `%XX` escapes and `+`, leaving invalid escapes as they are and replacing invalid UTF-8.
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|x| std::str::from_utf8(x).ok())
+            .filter(|x| x.bytes().all(|x| x.is_ascii_hexdigit()))
+            .and_then(|x| u8::from_str_radix(x, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(x)) => {
+                out.push(x);
+                i += 3;
+            }
+            (b'+', _) => {
+                out.push(b' ');
+                i += 1;
+            }
+            (x, _) => {
+                out.push(x);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
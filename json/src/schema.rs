@@ -0,0 +1,316 @@
+//! Inferring and checking a [JSON Schema](https://json-schema.org)-like description of documents.
+//!
+//! Only a small subset of JSON Schema is used:
+//! - `type`, either a single type name or an array of them for unions
+//! - `properties` and `required` for objects, where an inferred key is required if it appears in every sample object
+//! - `items` for arrays, describing every element of every sample array
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+};
+
+use crate::{JsonPointerBuf, JsonType, Value};
+
+/// A minimal schema that values can be checked against with [`Value::validate`].
+/// Ex. `Schema::of(JsonType::Object).required("name", Schema::of(JsonType::String))`
+///
+/// Object keys not listed in `properties` or `required` are allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Schema {
+    /// The types the value can be, or empty to allow any type.
+    pub types: Vec<JsonType>,
+    /// If numbers must be integers, like the JSON Schema `integer` type.
+    pub integer: bool,
+    /// Schemas for object keys, which are checked if the key is present.
+    pub properties: BTreeMap<String, Schema>,
+    /// Object keys that must be present.
+    pub required: BTreeSet<String>,
+    /// A schema every array element must match.
+    pub items: Option<Box<Schema>>,
+}
+
+/// A place where a value didn't match a [`Schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    path: JsonPointerBuf,
+    kind: ValidationErrorKind,
+}
+
+/// The reason a [`ValidationError`] occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    /// A required key was not in the object.
+    Missing,
+    /// The value was not one of the allowed types.
+    WrongType {
+        /// The allowed types.
+        expected: Vec<JsonType>,
+        /// The type that was found.
+        found: JsonType,
+    },
+    /// A number had a fractional part, but the schema only allows integers.
+    NotInteger,
+}
+
+impl Schema {
+    /// Creates a schema that only allows values of type `kind`.
+    pub fn of(kind: JsonType) -> Self {
+        Self {
+            types: vec![kind],
+            ..Default::default()
+        }
+    }
+
+    /// Adds an optional object key, checked against `schema` when present.
+    pub fn property(mut self, key: impl Into<String>, schema: Schema) -> Self {
+        self.properties.insert(key.into(), schema);
+        self
+    }
+
+    /// Adds an object key that must be present and match `schema`.
+    pub fn required(mut self, key: impl Into<String>, schema: Schema) -> Self {
+        let key = key.into();
+        self.required.insert(key.clone());
+        self.properties.insert(key, schema);
+        self
+    }
+
+    /// Sets the schema every array element must match.
+    pub fn items(mut self, schema: Schema) -> Self {
+        self.items = Some(Box::new(schema));
+        self
+    }
+
+    /// Reads a schema from its JSON form, like the output of [`infer_schema`].
+    /// Type names are parsed with [`JsonType::from_str`](std::str::FromStr::from_str), so both `bool` and `boolean` work.
+    /// Returns `None` if the schema is malformed or uses an unknown type name.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let obj = value.as_object()?;
+        let mut out = Schema::default();
+
+        let names = match obj.get("type") {
+            Some(Value::Array(a)) => a.iter().map(Value::as_string).collect::<Option<Vec<_>>>()?,
+            Some(Value::String(s)) => vec![s],
+            Some(_) => return None,
+            None => Vec::new(),
+        };
+        for i in &names {
+            let kind = i.parse().ok()?;
+            if !out.types.contains(&kind) {
+                out.types.push(kind);
+            }
+        }
+        // `integer` and `number` both parse as numbers, but only `integer` alone rules out floats
+        out.integer =
+            names.iter().any(|x| *x == "integer") && !names.iter().any(|x| *x == "number");
+
+        if let Some(properties) = obj.get("properties") {
+            for (k, v) in properties.as_object()? {
+                out.properties.insert(k.to_owned(), Schema::from_value(v)?);
+            }
+        }
+
+        if let Some(required) = obj.get("required") {
+            for i in required.as_array()? {
+                out.required.insert(i.as_string()?.to_owned());
+            }
+        }
+
+        if let Some(items) = obj.get("items") {
+            out.items = Some(Box::new(Schema::from_value(items)?));
+        }
+
+        Some(out)
+    }
+
+    fn check(&self, value: &Value, path: &JsonPointerBuf, errors: &mut Vec<ValidationError>) {
+        let mut error = |path: &JsonPointerBuf, kind| {
+            errors.push(ValidationError {
+                path: path.clone(),
+                kind,
+            })
+        };
+
+        if !self.types.is_empty() && !self.types.contains(&value.kind()) {
+            return error(
+                path,
+                ValidationErrorKind::WrongType {
+                    expected: self.types.clone(),
+                    found: value.kind(),
+                },
+            );
+        }
+
+        match value {
+            Value::Number(n) if self.integer && !n.is_integer() => {
+                error(path, ValidationErrorKind::NotInteger)
+            }
+            Value::Object(o) => {
+                for key in self.required.iter().filter(|x| !o.contains_key(*x)) {
+                    let mut path = path.clone();
+                    path.push_key(key);
+                    error(&path, ValidationErrorKind::Missing);
+                }
+
+                for (k, v) in &self.properties {
+                    if let Some(x) = o.get(k) {
+                        let mut path = path.clone();
+                        path.push_key(k);
+                        v.check(x, &path, errors);
+                    }
+                }
+            }
+            Value::Array(a) => {
+                if let Some(items) = &self.items {
+                    for (i, x) in a.iter().enumerate() {
+                        let mut path = path.clone();
+                        path.push_index(i);
+                        items.check(x, &path, errors);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ValidationError {
+    /// Gets the JSON Pointer to the value that caused the error.
+    /// For [`ValidationErrorKind::Missing`] this points to where the missing key should be.
+    pub fn path(&self) -> &JsonPointerBuf {
+        &self.path
+    }
+
+    /// Gets the reason for the error.
+    pub fn kind(&self) -> &ValidationErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = match self.path.is_empty() {
+            true => "/",
+            false => self.path.as_str(),
+        };
+
+        match &self.kind {
+            ValidationErrorKind::Missing => write!(f, "missing field at {path}"),
+            ValidationErrorKind::WrongType { expected, found } => {
+                let expected = expected.iter().map(|x| x.name()).collect::<Vec<_>>();
+                write!(
+                    f,
+                    "expected {}, found {found} at {path}",
+                    expected.join(" or ")
+                )
+            }
+            ValidationErrorKind::NotInteger => write!(f, "expected integer at {path}"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Value {
+    /// Checks the value against `schema`, collecting every mismatch rather than stopping at the first.
+    /// Values with the wrong type are not checked any further.
+    pub fn validate(&self, schema: &Schema) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        schema.check(self, &JsonPointerBuf::new(), &mut errors);
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+}
+
+/// Infers a schema that describes every value in `values`.
+/// Ex. `[{"a": 1}, {"a": "x", "b": null}]` => `{"type": "object", "properties": {"a": {"type": ["integer", "string"]}, "b": {"type": "null"}}, "required": ["a"]}`
+///
+/// Integers are typed as `integer`, which is widened to `number` if floats are also seen.
+/// With no samples the empty schema `{}` is returned, which matches anything.
+pub fn infer_schema(values: &[Value]) -> Value {
+    let mut inferred = Inferred::default();
+    values.iter().for_each(|x| inferred.add(x));
+    inferred.to_value()
+}
+
+/// The merged shape of every value seen so far.
+#[derive(Default)]
+struct Inferred {
+    types: BTreeSet<&'static str>,
+    /// The number of objects seen, used to work out which properties are required.
+    objects: usize,
+    /// Each property with the number of objects it appeared in.
+    properties: BTreeMap<String, (Inferred, usize)>,
+    items: Option<Box<Inferred>>,
+}
+
+impl Inferred {
+    fn add(&mut self, value: &Value) {
+        self.types.insert(type_name(value));
+
+        match value {
+            Value::Object(o) => {
+                self.objects += 1;
+                for (k, v) in o {
+                    let (property, count) = self.properties.entry(k.to_owned()).or_default();
+                    property.add(v);
+                    *count += 1;
+                }
+            }
+            Value::Array(a) => {
+                for i in a {
+                    self.items.get_or_insert_with(Default::default).add(i);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let mut out = BTreeMap::new();
+
+        let mut types = self.types.clone();
+        if types.contains("number") {
+            types.remove("integer");
+        }
+
+        match types.len() {
+            0 => return Value::Object(out),
+            1 => out.insert("type".to_owned(), types.into_iter().next().unwrap().into()),
+            _ => out.insert(
+                "type".to_owned(),
+                types.into_iter().collect::<Vec<_>>().into(),
+            ),
+        };
+
+        if self.objects > 0 {
+            let properties = self.properties.iter().map(|(k, v)| (k, v.0.to_value()));
+            out.insert("properties".to_owned(), Value::from_entries(properties));
+
+            let required = self.properties.iter().filter(|x| x.1 .1 == self.objects);
+            let required = required.map(|x| x.0.as_str()).collect::<Vec<_>>();
+            if !required.is_empty() {
+                out.insert("required".to_owned(), required.into());
+            }
+        }
+
+        if let Some(items) = &self.items {
+            out.insert("items".to_owned(), items.to_value());
+        }
+
+        Value::Object(out)
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if !n.is_integer() => "number",
+        Value::Number(_) => "integer",
+        x => x.kind().name(),
+    }
+}
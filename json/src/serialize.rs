@@ -0,0 +1,151 @@
+use std::fmt::{self, Write};
+
+use crate::{value::escape, Number, Value};
+
+/// Options for [`Value::to_string_with`].
+/// The defaults match [`Value::to_string`].
+#[derive(Debug, Clone)]
+pub struct SerializeOptions {
+    /// Whether object keys are written in sorted order.
+    /// When false, keys are written in the order the object stores them, after any keys in [`SerializeOptions::key_order`].
+    /// Defaults to true.
+    pub sort_keys: bool,
+    /// Keys to write first, in this order, when `sort_keys` is false.
+    /// Applies to objects at every level and keys that an object doesn't have are skipped.
+    pub key_order: Vec<String>,
+    /// The number of decimal places to write floats with, or `None` for the shortest form that parses back to the same float.
+    /// Ex. with a precision of 2, `3.14159` => `3.14` and `2.0` => `2.00`
+    /// Integers are always written in full.
+    pub precision: Option<usize>,
+    /// The number of spaces to indent nested values by, or `None` to write everything on one line.
+    /// When set, every array element and object entry goes on its own line, and keys are followed by a space.
+    /// Defaults to `None`.
+    pub indent: Option<usize>,
+    /// When indenting, arrays of at most this many numbers, strings, bools or nulls are kept on one line.
+    /// Ex. with a max of 3, `[1, 2, 3]` stays inline while `[1, 2, 3, 4]` and `[[1]]` are expanded.
+    /// Defaults to 0, expanding every non-empty array.
+    pub inline_array_max_len: usize,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        Self {
+            sort_keys: true,
+            key_order: Vec::new(),
+            precision: None,
+            indent: None,
+            inline_array_max_len: 0,
+        }
+    }
+}
+
+impl Value {
+    /// Serializes the value with the given options.
+    /// Ex. `value.to_string_with(&SerializeOptions { sort_keys: false, key_order: vec!["id".into()], ..Default::default() })`
+    pub fn to_string_with(&self, options: &SerializeOptions) -> String {
+        let mut out = String::new();
+        write(&mut out, self, options).unwrap();
+        out
+    }
+
+    /// Serializes the value over multiple lines, indented by two spaces, with short arrays of scalars kept on one line.
+    /// Use [`Value::to_string_with`] with [`SerializeOptions::indent`] for more control.
+    pub fn to_string_pretty(&self) -> String {
+        self.to_string_with(&SerializeOptions {
+            indent: Some(2),
+            inline_array_max_len: 8,
+            ..Default::default()
+        })
+    }
+}
+
+/// Writes `value` to `out` as JSON, compact unless [`SerializeOptions::indent`] is set.
+pub(crate) fn write(
+    out: &mut impl Write,
+    value: &Value,
+    options: &SerializeOptions,
+) -> fmt::Result {
+    write_at(out, value, options, 0)
+}
+
+// Starts a new line indented for `depth`, if indenting at all
+fn newline(out: &mut impl Write, options: &SerializeOptions, depth: usize) -> fmt::Result {
+    match options.indent {
+        Some(indent) => write!(out, "\n{:1$}", "", indent * depth),
+        None => Ok(()),
+    }
+}
+
+fn is_inline(array: &[Value], options: &SerializeOptions) -> bool {
+    array.len() <= options.inline_array_max_len
+        && array
+            .iter()
+            .all(|x| !matches!(x, Value::Array(_) | Value::Object(_)))
+}
+
+fn write_at(
+    out: &mut impl Write,
+    value: &Value,
+    options: &SerializeOptions,
+    depth: usize,
+) -> fmt::Result {
+    match value {
+        Value::Null => out.write_str("null"),
+        Value::Bool(b) => write!(out, "{b}"),
+        Value::Number(Number::Float(x)) if x.is_finite() => match options.precision {
+            Some(precision) => write!(out, "{x:.precision$}"),
+            None => write!(out, "{x}"),
+        },
+        Value::Number(n) => write!(out, "{n}"),
+        Value::String(s) => write!(out, r#""{}""#, escape(s)),
+        Value::Array(a) if a.is_empty() => out.write_str("[]"),
+        Value::Array(a) if options.indent.is_some() && is_inline(a, options) => {
+            out.write_char('[')?;
+            for (i, x) in a.iter().enumerate() {
+                if i > 0 {
+                    out.write_str(", ")?;
+                }
+                write_at(out, x, options, depth)?;
+            }
+            out.write_char(']')
+        }
+        Value::Array(a) => {
+            out.write_char('[')?;
+            for (i, x) in a.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                newline(out, options, depth + 1)?;
+                write_at(out, x, options, depth + 1)?;
+            }
+            newline(out, options, depth)?;
+            out.write_char(']')
+        }
+        Value::Object(o) if o.is_empty() => out.write_str("{}"),
+        Value::Object(o) => {
+            let mut entries = o.iter().collect::<Vec<_>>();
+            if options.sort_keys {
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+            } else if !options.key_order.is_empty() {
+                // Listed keys get their position in the list and everything else goes after, keeping its order
+                let rank = |k: &str| options.key_order.iter().position(|x| x == k);
+                entries.sort_by_key(|x| rank(x.0).unwrap_or(usize::MAX));
+            }
+
+            out.write_char('{')?;
+            for (i, (k, x)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                newline(out, options, depth + 1)?;
+                write!(out, r#""{}":"#, escape(k))?;
+                if options.indent.is_some() {
+                    out.write_char(' ')?;
+                }
+                write_at(out, x, options, depth + 1)?;
+            }
+            newline(out, options, depth)?;
+            out.write_char('}')
+        }
+    }
+}
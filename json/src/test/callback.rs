@@ -0,0 +1,83 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{JsonPath, PathSegment, Value};
+
+fn is_iso_date(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() == 10
+        && b.iter().enumerate().all(|(i, x)| match i {
+            4 | 7 => *x == b'-',
+            _ => x.is_ascii_digit(),
+        })
+}
+
+#[test]
+fn test_reviver_dates() {
+    let value = Value::from_str_with_reviver(
+        r#"{"name": "release", "dates": ["2022-07-04", "soon"], "at": "2022-08-01"}"#,
+        |_, x| match x {
+            Value::String(s) if is_iso_date(&s) => {
+                let mut tagged = BTreeMap::new();
+                tagged.insert("$date".to_owned(), Value::String(s));
+                Value::Object(tagged)
+            }
+            x => x,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        value.to_string(),
+        r#"{"at":{"$date":"2022-08-01"},"dates":[{"$date":"2022-07-04"},"soon"],"name":"release"}"#
+    );
+}
+
+#[test]
+fn test_reviver_order() {
+    let mut seen = Vec::new();
+    Value::from_str_with_reviver(r#"{"a": ["b"], "c": null}"#, |path, x| {
+        seen.push(path.to_string());
+        x
+    })
+    .unwrap();
+
+    assert_eq!(seen, vec!["/a/0", "/a", "/c", ""]);
+}
+
+#[test]
+fn test_replacer_drop_private() {
+    let value: Value =
+        r#"{"id": "7", "_secret": "hunter2", "inner": {"_x": null, "y": [true, {"_z": false}]}}"#
+            .parse()
+            .unwrap();
+
+    let out = value.to_string_with_replacer(|path, x| match path.last() {
+        Some(PathSegment::Key(k)) if k.starts_with('_') => None,
+        _ => Some(Cow::Borrowed(x)),
+    });
+
+    assert_eq!(out, r#"{"id":"7","inner":{"y":[true,{}]}}"#);
+}
+
+#[test]
+fn test_replacer_replace() {
+    let value: Value = r#"["a", "b", "c"]"#.parse().unwrap();
+    let out = value.to_string_with_replacer(|path, x| match path.segments() {
+        [PathSegment::Index(1)] => Some(Cow::Owned(Value::Array(vec![Value::Null]))),
+        [PathSegment::Index(2)] => None,
+        _ => Some(Cow::Borrowed(x)),
+    });
+
+    assert_eq!(out, r#"["a",[null],null]"#);
+    assert_eq!(value.to_string_with_replacer(|_, _| None), "null");
+}
+
+#[test]
+fn test_path_display() {
+    let mut path = JsonPath::new();
+    assert!(path.is_root());
+    path.push(PathSegment::Key("a/b~c".to_owned()));
+    path.push(PathSegment::Index(3));
+    assert_eq!(path.to_string(), "/a~1b~0c/3");
+    assert_eq!(path.pop(), Some(PathSegment::Index(3)));
+}
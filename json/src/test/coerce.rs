@@ -0,0 +1,169 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::{Number, PathSegment, Value};
+
+#[test]
+fn test_coerce_scalars() {
+    assert_eq!(Value::from(42).coerce_u64(), Some(42));
+    assert_eq!(Value::from("42").coerce_u64(), Some(42));
+    assert_eq!(Value::from(" 42\n").coerce_u64(), Some(42));
+    assert_eq!(Value::from("-42").coerce_u64(), None);
+    assert_eq!(Value::from("-42").coerce_i64(), Some(-42));
+    assert_eq!(Value::from(u64::MAX).coerce_i64(), None);
+    assert_eq!(Value::from("1.5").coerce_f64(), Some(1.5));
+    assert_eq!(Value::from(3).coerce_f64(), Some(3.0));
+    assert_eq!(Value::from("NaN").coerce_f64(), None);
+    assert_eq!(Value::from(" TRUE ").coerce_bool(), Some(true));
+    assert_eq!(Value::from(false).coerce_bool(), Some(false));
+
+    for value in [
+        Value::from("forty two"),
+        Value::from(""),
+        Value::Null,
+        Value::from(vec![1]),
+    ] {
+        assert_eq!(value.coerce_u64(), None);
+        assert_eq!(value.coerce_i64(), None);
+        assert_eq!(value.coerce_f64(), None);
+        assert_eq!(value.coerce_bool(), None);
+    }
+}
+
+#[test]
+fn test_coerce_to_number() {
+    assert_eq!(Value::from("42").coerce_to_number(), Some(Number::UInt(42)));
+    assert_eq!(
+        Value::from(" -7 ").coerce_to_number(),
+        Some(Number::Int(-7))
+    );
+    assert_eq!(
+        Value::from("2.5").coerce_to_number(),
+        Some(Number::Float(2.5))
+    );
+    assert_eq!(
+        Value::from("1e3").coerce_to_number(),
+        Some(Number::Float(1e3))
+    );
+    assert_eq!(
+        Value::from(1.5).coerce_to_number(),
+        Some(Number::Float(1.5))
+    );
+    assert_eq!(Value::from(true).coerce_to_number(), Some(Number::UInt(1)));
+    assert_eq!(Value::from(false).coerce_to_number(), Some(Number::UInt(0)));
+
+    for value in [
+        Value::from("forty two"),
+        Value::from(""),
+        Value::from("1.0e999"),
+        Value::Null,
+        Value::from(vec![1]),
+    ] {
+        assert_eq!(value.coerce_to_number(), None, "{value}");
+    }
+}
+
+#[test]
+fn test_coerce_numeric_strings() {
+    let mut value = Value::from_str(
+        r#"{"count": "42", "price": " 1.5 ", "zip": "02134", "neg": "-7", "name": "abc", "list": ["1", "x", "007"], "zero": "0"}"#,
+    )
+    .unwrap();
+    value.coerce_numeric_strings();
+
+    assert_eq!(
+        value,
+        Value::from_str(
+            r#"{"count": 42, "price": 1.5, "zip": "02134", "neg": -7, "name": "abc", "list": [1, "x", "007"], "zero": 0}"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_coerce_numeric_strings_with() {
+    let mut value =
+        Value::from_str(r#"{"id": "123", "user": {"id": "456", "age": "30"}, "zip": "02134"}"#)
+            .unwrap();
+    value.coerce_numeric_strings_with(
+        |path, _| !matches!(path.last(), Some(PathSegment::Key(k)) if k == "id"),
+    );
+
+    assert_eq!(value.pointer("/id"), Some(&Value::from("123")));
+    assert_eq!(value.pointer("/user/id"), Some(&Value::from("456")));
+    assert_eq!(
+        value.pointer("/user/age"),
+        Some(&Value::Number(Number::UInt(30)))
+    );
+    // A custom filter decides about leading zeros too
+    assert_eq!(
+        value.pointer("/zip"),
+        Some(&Value::Number(Number::UInt(2134)))
+    );
+}
+
+#[test]
+fn test_coerce_numeric_strings_infinite() {
+    let mut value =
+        Value::from_str(r#"{"big": "1.0e400", "small": "-1.0e400", "ok": "2.5"}"#).unwrap();
+    let mut seen = Vec::new();
+    value.coerce_numeric_strings_with(|_, s| {
+        seen.push(s.to_owned());
+        true
+    });
+
+    assert_eq!(seen, ["2.5"]);
+    assert_eq!(
+        value.to_string(),
+        r#"{"big":"1.0e400","ok":2.5,"small":"-1.0e400"}"#
+    );
+}
+
+#[test]
+fn test_as_duration() {
+    let duration = |x: Value| x.as_duration();
+    assert_eq!(duration(Value::from(2)), Some(Duration::from_secs(2)));
+    assert_eq!(
+        duration(Value::from(1.5)),
+        Some(Duration::from_millis(1500))
+    );
+    assert_eq!(
+        duration(Value::from("250ms")),
+        Some(Duration::from_millis(250))
+    );
+    assert_eq!(
+        duration(Value::from("1500ms")),
+        Some(Duration::from_millis(1500))
+    );
+    assert_eq!(duration(Value::from(" 2 s ")), Some(Duration::from_secs(2)));
+    assert_eq!(duration(Value::from("5m")), Some(Duration::from_secs(300)));
+    assert_eq!(
+        duration(Value::from("1.5h")),
+        Some(Duration::from_secs(5400))
+    );
+    assert_eq!(
+        duration(Value::from("1d")),
+        Some(Duration::from_secs(86400))
+    );
+    assert_eq!(
+        duration(Value::from("10us")),
+        Some(Duration::from_micros(10))
+    );
+    assert_eq!(duration(Value::from("7ns")), Some(Duration::from_nanos(7)));
+}
+
+#[test]
+fn test_as_duration_invalid() {
+    for value in [
+        Value::from("5 parsecs"),
+        Value::from("5"),
+        Value::from("ms"),
+        Value::from("-5s"),
+        Value::from("1e3ms"),
+        Value::from(-1),
+        Value::from(f64::INFINITY),
+        Value::from(true),
+        Value::Null,
+    ] {
+        assert_eq!(value.as_duration(), None, "{value}");
+    }
+}
@@ -0,0 +1,87 @@
+use std::str::FromStr;
+
+use crate::{from_csv, to_csv, CsvError, Value};
+
+#[test]
+fn test_to_csv() {
+    let value = Value::from_str(
+        r#"[{"name": "Ferris", "age": 7, "crab": true}, {"name": "Say \"hi\", please", "note": "two\nlines"}, {"name": "", "age": null}]"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        to_csv(&value).unwrap(),
+        "age,crab,name,note\r\n7,true,Ferris,\r\n,,\"Say \"\"hi\"\", please\",\"two\nlines\"\r\n,,\"\",\r\n"
+    );
+    assert_eq!(to_csv(&Value::Array(Vec::new())).unwrap(), "\r\n");
+}
+
+#[test]
+fn test_to_csv_fail() {
+    let nested = Value::from_str(r#"[{"a": 1}, {"a": [1]}]"#).unwrap();
+    assert_eq!(
+        to_csv(&nested),
+        Err(CsvError::NestedValue {
+            row: 1,
+            key: "a".to_owned()
+        })
+    );
+
+    let not_object = Value::from_str(r#"[{"a": 1}, 2]"#).unwrap();
+    assert_eq!(to_csv(&not_object), Err(CsvError::NotTable(Some(1))));
+    assert_eq!(to_csv(&Value::from("a")), Err(CsvError::NotTable(None)));
+}
+
+#[test]
+fn test_from_csv() {
+    let value = from_csv("id,name,note\n1,\"Smith, John\",\n2,\"\"\"Q\"\"\",\"\"\r\n").unwrap();
+    assert_eq!(
+        value,
+        Value::from_str(
+            r#"[{"id": "1", "name": "Smith, John"}, {"id": "2", "name": "\"Q\"", "note": ""}]"#
+        )
+        .unwrap()
+    );
+
+    let mut value = from_csv("id,zip\n1,02134").unwrap();
+    value.coerce_numeric_strings();
+    assert_eq!(
+        value,
+        Value::from_str(r#"[{"id": 1, "zip": "02134"}]"#).unwrap()
+    );
+    assert_eq!(from_csv("").unwrap(), Value::Array(Vec::new()));
+}
+
+#[test]
+fn test_from_csv_trailing_blank_line() {
+    assert_eq!(
+        from_csv("a,b\r\n1,2\r\n\r\n").unwrap(),
+        Value::from_str(r#"[{"a": "1", "b": "2"}]"#).unwrap()
+    );
+    assert_eq!(from_csv("a\n\n").unwrap(), Value::Array(Vec::new()));
+    // Only one blank line is dropped, and a quoted empty cell is still a row
+    assert_eq!(from_csv("a,b\n1,2\n\n\n"), Err(CsvError::RowLength(1)));
+    assert_eq!(
+        from_csv("a\n\"\"\n").unwrap(),
+        Value::from_str(r#"[{"a": ""}]"#).unwrap()
+    );
+}
+
+#[test]
+fn test_from_csv_fail() {
+    assert_eq!(from_csv("a,b\n1,\"2"), Err(CsvError::UnterminatedQuote(6)));
+    assert_eq!(from_csv("a,b\n1,2\""), Err(CsvError::UnexpectedQuote(7)));
+    assert_eq!(from_csv("a,b\n\"1\"x,2"), Err(CsvError::UnexpectedQuote(7)));
+    assert_eq!(from_csv("a,b\n1,2\n3"), Err(CsvError::RowLength(1)));
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let value = Value::from_str(
+        r#"[{"quote": "\"quoted\"", "comma": "a, b", "newline": "a\r\nb", "empty": ""}, {"quote": "plain"}]"#,
+    )
+    .unwrap();
+
+    let csv = to_csv(&value).unwrap();
+    assert_eq!(from_csv(&csv).unwrap(), value);
+}
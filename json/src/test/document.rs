@@ -0,0 +1,133 @@
+use crate::{EditError, JsonDocument, Number, Value};
+
+const CONFIG: &str = r#"{
+    // Package metadata
+    "name": "half-stack",
+    "version": "0.1.0",
+
+    /* Keep these sorted */
+    "features": ["json",   "trace"],
+    "nested": { "a": true }
+}
+"#;
+
+#[test]
+fn test_document_round_trip() {
+    let doc = JsonDocument::parse(CONFIG).unwrap();
+    assert_eq!(doc.to_string(), CONFIG);
+    assert_eq!(
+        doc.get("/features/1"),
+        Some(Value::String("trace".to_owned()))
+    );
+    assert_eq!(doc.get("/nested/a"), Some(Value::Bool(true)));
+    assert_eq!(doc.get("/missing"), None);
+}
+
+#[test]
+fn test_document_replace() {
+    let mut doc = JsonDocument::parse(CONFIG).unwrap();
+    doc.set("/version", Value::String("0.2.0".to_owned()))
+        .unwrap();
+
+    let out = doc.to_string();
+    let at = CONFIG.find("0.1.0").unwrap();
+    assert_eq!(out[..at], CONFIG[..at]);
+    assert_eq!(out[at..at + 5], *"0.2.0");
+    assert_eq!(out[at + 5..], CONFIG[at + 5..]);
+}
+
+#[test]
+fn test_document_replace_nested() {
+    let mut doc = JsonDocument::parse(CONFIG).unwrap();
+    doc.set("/nested/a", Value::Number(Number::UInt(1)))
+        .unwrap();
+    doc.set("/features/0", Value::String("misc".to_owned()))
+        .unwrap();
+
+    assert_eq!(
+        doc.to_string(),
+        CONFIG
+            .replace(r#""a": true"#, r#""a": 1"#)
+            .replace(r#"["json","#, r#"["misc","#)
+    );
+}
+
+#[test]
+fn test_document_insert() {
+    let mut doc = JsonDocument::parse(CONFIG).unwrap();
+    doc.set("/edition", Value::String("2021".to_owned()))
+        .unwrap();
+
+    assert_eq!(
+        doc.to_string(),
+        CONFIG.replace(
+            "\"nested\": { \"a\": true }\n",
+            "\"nested\": { \"a\": true },\n    \"edition\": \"2021\"\n"
+        )
+    );
+    assert_eq!(doc.get("/edition"), Some(Value::String("2021".to_owned())));
+}
+
+#[test]
+fn test_document_insert_single_and_empty() {
+    let mut doc = JsonDocument::parse(r#"{ "a": { "b": 1 }, "c": {} }"#).unwrap();
+    doc.set("/a/d", Value::Null).unwrap();
+    doc.set("/c/e", Value::Bool(false)).unwrap();
+
+    assert_eq!(
+        doc.to_string(),
+        r#"{ "a": { "b": 1, "d": null }, "c": {"e": false} }"#
+    );
+}
+
+#[test]
+fn test_document_edit_errors() {
+    let mut doc = JsonDocument::parse(CONFIG).unwrap();
+    assert_eq!(doc.set("name", Value::Null), Err(EditError::InvalidPointer));
+    assert_eq!(doc.set("/a/b", Value::Null), Err(EditError::NotFound));
+    assert_eq!(
+        doc.set("/features/5", Value::Null),
+        Err(EditError::NotFound)
+    );
+    assert_eq!(doc.to_string(), CONFIG);
+
+    // Values that can't be written as JSON leave the document unchanged
+    assert_eq!(
+        doc.set("/version", Value::from(f64::NAN)),
+        Err(EditError::Invalid)
+    );
+    assert_eq!(
+        doc.set("/new", Value::from(f64::INFINITY)),
+        Err(EditError::Invalid)
+    );
+    assert_eq!(doc.to_string(), CONFIG);
+
+    // A comma in a comment isn't taken as the separator
+    let mut doc = JsonDocument::parse(r#"{"a": 1 /* x, y */, "b": 2}"#).unwrap();
+    doc.set("/z", Value::from(3)).unwrap();
+    assert_eq!(doc.to_string(), r#"{"a": 1 /* x, y */, "b": 2, "z": 3}"#);
+}
+
+#[test]
+fn test_document_insert_after_comment() {
+    let mut doc = JsonDocument::parse("{\n  \"a\": 1,\n  // about b\n  \"b\": 2\n}").unwrap();
+    doc.set("/c", Value::from(3)).unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "{\n  \"a\": 1,\n  // about b\n  \"b\": 2,\n  \"c\": 3\n}"
+    );
+
+    let mut doc = JsonDocument::parse("{\r\n  /* first */ \"a\" /* x */: 1\r\n}").unwrap();
+    doc.set("/b", Value::from(2)).unwrap();
+    assert_eq!(
+        doc.to_string(),
+        "{\r\n  /* first */ \"a\" /* x */: 1,\r\n  \"b\": 2\r\n}"
+    );
+}
+
+#[test]
+fn test_document_parse_fail() {
+    assert!(JsonDocument::parse(r#"{"a": 1,}"#).is_err());
+    assert!(JsonDocument::parse(r#"{"a": 1} x"#).is_err());
+    assert!(JsonDocument::parse("/* open").is_err());
+}
@@ -0,0 +1,119 @@
+use std::{
+    env,
+    error::Error as _,
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::{Error, Value};
+
+/// A file in the temp directory that is deleted on drop.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, content: &[u8]) -> Self {
+        let path = env::temp_dir().join(format!("half-stack-json-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn test_from_file() {
+    let file = TempFile::new("from_file.json", br#"{"hello": ["world"]}"#);
+    assert_eq!(
+        Value::from_file(&file.0).unwrap().pointer("/hello/0"),
+        Some(&Value::String("world".to_owned()))
+    );
+}
+
+#[test]
+fn test_from_file_errors() {
+    let file = TempFile::new("invalid_utf8.json", b"\"\xff\xfe\"");
+    assert!(matches!(
+        Value::from_file(&file.0),
+        Err(Error::InvalidUtf8(_))
+    ));
+
+    let missing = env::temp_dir().join("half-stack-json-does-not-exist.json");
+    match Value::from_file(missing) {
+        Err(Error::Io(e)) => assert_eq!(e.kind(), ErrorKind::NotFound),
+        x => panic!("Expected an IO error, got {x:?}"),
+    }
+
+    let file = TempFile::new("bad_json.json", b"nul");
+    assert_eq!(Value::from_file(&file.0), Err(Error::UnexpectedEnd(3)));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mapped_document() {
+    use crate::MappedDocument;
+
+    let text = r#"{"a": {"b": null}}"#;
+    let file = TempFile::new("mapped.json", text.as_bytes());
+    let doc = MappedDocument::open(&file.0).unwrap();
+    assert_eq!(doc.source(), text);
+    assert_eq!(doc.value().pointer("/a/b"), Some(&Value::Null));
+
+    let file = TempFile::new("mapped_invalid_utf8.json", b"[\"\xc3\x28\"]");
+    assert!(matches!(
+        MappedDocument::open(&file.0),
+        Err(Error::InvalidUtf8(_))
+    ));
+}
+
+#[test]
+fn test_from_reader() {
+    let reader: &[u8] = br#"{"hello": ["world"]}"#;
+    assert_eq!(
+        Value::from_reader(reader).unwrap().pointer("/hello/0"),
+        Some(&Value::String("world".to_owned()))
+    );
+
+    let reader: &[u8] = b"[1, \xff]";
+    assert!(matches!(
+        Value::from_reader(reader),
+        Err(Error::InvalidUtf8(_))
+    ));
+}
+
+#[test]
+fn test_io_error_round_trip() {
+    let err = Value::from_reader(&b"[1, nul"[..]).unwrap_err();
+    assert_eq!(err.to_string(), "unexpected end of input at 7");
+
+    let io = io::Error::from(err);
+    assert_eq!(io.kind(), ErrorKind::InvalidData);
+    assert_eq!(io.to_string(), "unexpected end of input at 7");
+    assert_eq!(
+        io.get_ref().and_then(|x| x.downcast_ref::<Error>()),
+        Some(&Error::UnexpectedEnd(7))
+    );
+    assert_eq!(Error::from(io), Error::UnexpectedEnd(7));
+
+    // IO errors pass through unchanged
+    let io = io::Error::from(Error::Io(ErrorKind::NotFound.into()));
+    assert_eq!(io.kind(), ErrorKind::NotFound);
+    assert!(io.get_ref().is_none());
+}
+
+#[test]
+fn test_error_source() {
+    let err = Error::Io(io::Error::other("disk on fire"));
+    assert_eq!(err.to_string(), "io error: disk on fire");
+    assert_eq!(err.source().unwrap().to_string(), "disk on fire");
+
+    let err = Value::from_str("1x").unwrap_err();
+    assert!(matches!(err, Error::InvalidNumber { pos: 0, .. }));
+    assert!(err.source().is_some());
+    assert!(Error::UnexpectedChar(0).source().is_none());
+}
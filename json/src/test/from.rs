@@ -0,0 +1,45 @@
+use std::str::FromStr;
+
+use crate::{Number, Value};
+
+#[test]
+fn test_from_scalars() {
+    assert_eq!(Value::from(true), Value::Bool(true));
+    assert_eq!(Value::from("a"), Value::String("a".to_owned()));
+    assert_eq!(Value::from(5u8), Value::Number(Number::UInt(5)));
+    assert_eq!(Value::from(5), Value::from_str("5").unwrap());
+    assert_eq!(Value::from(-5i64), Value::from_str("-5").unwrap());
+    assert_eq!(Value::from(1.5), Value::Number(Number::Float(1.5)));
+    assert_eq!(Value::from(None::<bool>), Value::Null);
+    assert_eq!(
+        Value::from(vec!["a", "b"]),
+        Value::from_str(r#"["a", "b"]"#).unwrap()
+    );
+}
+
+#[test]
+fn test_from_array_of_tuples() {
+    let value = Value::from([
+        ("name", Value::from("half-stack")),
+        ("version", Value::from([("major", 0), ("minor", 1)])),
+        ("crates", Value::from(vec!["json", "trace", "misc"])),
+        ("published", Value::from(false)),
+    ]);
+
+    assert_eq!(
+        value,
+        Value::from_str(
+            r#"{"name": "half-stack", "version": {"major": 0, "minor": 1}, "crates": ["json", "trace", "misc"], "published": false}"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_from_entries() {
+    let value = Value::from_entries((0..3).map(|x| (format!("key{x}"), x * 2)));
+    assert_eq!(value.to_string(), r#"{"key0":0,"key1":2,"key2":4}"#);
+
+    let value = Value::from([("a", 1), ("b", 2), ("a", 3)]);
+    assert_eq!(value, Value::from_entries([("b", 2), ("a", 3)]));
+}
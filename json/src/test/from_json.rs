@@ -0,0 +1,255 @@
+use std::str::FromStr;
+
+use crate::{
+    field, field_or_else, FromJson, FromJsonError, FromJsonErrorKind, JsonType, Nullable, Value,
+};
+
+#[derive(Debug, PartialEq)]
+struct Config {
+    optional: Option<u64>,
+    nullable: Nullable<u64>,
+    defaulted: u64,
+}
+
+impl FromJson for Config {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        Ok(Self {
+            optional: field(value, "optional")?,
+            nullable: field(value, "nullable")?,
+            defaulted: field_or_else(value, "defaulted", || 7)?,
+        })
+    }
+}
+
+fn config(json: &str) -> Result<Config, FromJsonError> {
+    Config::from_json(&Value::from_str(json).unwrap())
+}
+
+#[test]
+fn test_field_present() {
+    assert_eq!(
+        config(r#"{"optional": 1, "nullable": 2, "defaulted": 3}"#),
+        Ok(Config {
+            optional: Some(1),
+            nullable: Nullable::Value(2),
+            defaulted: 3,
+        })
+    );
+}
+
+#[test]
+fn test_field_absent() {
+    assert_eq!(
+        config("{}"),
+        Ok(Config {
+            optional: None,
+            nullable: Nullable::Missing,
+            defaulted: 7,
+        })
+    );
+}
+
+#[test]
+fn test_field_null() {
+    let err = config(r#"{"optional": null, "nullable": null, "defaulted": null}"#).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &FromJsonErrorKind::WrongType {
+            expected: JsonType::Number,
+            found: JsonType::Null
+        }
+    );
+    assert_eq!(err.to_string(), "expected number, found null at /defaulted");
+
+    assert_eq!(
+        config(r#"{"optional": null, "nullable": null}"#),
+        Ok(Config {
+            optional: None,
+            nullable: Nullable::Null,
+            defaulted: 7,
+        })
+    );
+}
+
+#[test]
+fn test_field_wrong_type() {
+    for key in ["optional", "nullable", "defaulted"] {
+        let err = config(&format!(r#"{{"{key}": "5"}}"#)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("expected number, found string at /{key}")
+        );
+    }
+}
+
+#[test]
+fn test_field_missing() {
+    #[derive(Debug)]
+    struct Required(#[allow(dead_code)] String);
+
+    impl FromJson for Required {
+        fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+            Ok(Self(field(value, "name")?))
+        }
+    }
+
+    let err = Required::from_json(&Value::from_str("{}").unwrap()).unwrap_err();
+    assert_eq!(err.kind(), &FromJsonErrorKind::Missing);
+    assert_eq!(err.to_string(), "missing field at /name");
+
+    let err = Required::from_json(&Value::from_str("[]").unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "expected object, found array at /");
+}
+
+#[test]
+fn test_from_json_numbers() {
+    assert_eq!(u8::from_json(&Value::from(255)), Ok(255));
+    assert_eq!(
+        u8::from_json(&Value::from(256)).unwrap_err().kind(),
+        &FromJsonErrorKind::OutOfRange
+    );
+    assert_eq!(i32::from_json(&Value::from(-4)), Ok(-4));
+    assert!(u32::from_json(&Value::from(-4)).is_err());
+    assert_eq!(f64::from_json(&Value::from(2)), Ok(2.0));
+
+    let err = u64::from_json(&Value::from(1.5)).unwrap_err();
+    assert_eq!(err.kind(), &FromJsonErrorKind::NotInteger);
+    assert_eq!(err.to_string(), "expected integer at /");
+}
+
+#[test]
+fn test_from_json_wrong_type() {
+    let cases = [
+        (
+            bool::from_json(&Value::from("true")).unwrap_err(),
+            "bool",
+            "string",
+        ),
+        (
+            String::from_json(&Value::from_str("{}").unwrap()).unwrap_err(),
+            "string",
+            "object",
+        ),
+        (f64::from_json(&Value::Null).unwrap_err(), "number", "null"),
+        (
+            i8::from_json(&Value::from(vec![1])).unwrap_err(),
+            "number",
+            "array",
+        ),
+    ];
+
+    for (err, expected, found) in cases {
+        assert_eq!(
+            err.to_string(),
+            format!("expected {expected}, found {found} at /")
+        );
+    }
+}
+
+fn settings() -> Value {
+    Value::from_str(
+        r#"{"port": 8080, "offset": -5, "ratio": 0.5, "debug": true, "host": "example.com", "unset": null, "wrong": [1]}"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_get_or_present() {
+    let cfg = settings();
+    assert_eq!(cfg.get_u64_or("port", 80), 8080);
+    assert_eq!(cfg.get_i64_or("offset", 0), -5);
+    assert_eq!(cfg.get_f64_or("ratio", 1.0), 0.5);
+    assert_eq!(cfg.get_f64_or("port", 1.0), 8080.0);
+    assert!(cfg.get_bool_or("debug", false));
+    assert_eq!(cfg.get_str_or("host", "localhost"), "example.com");
+    assert_eq!(cfg.get_or("port", 0u16), 8080);
+}
+
+#[test]
+fn test_get_or_default() {
+    let cfg = settings();
+    for key in ["missing", "unset", "wrong"] {
+        assert_eq!(cfg.get_u64_or(key, 80), 80);
+        assert_eq!(cfg.get_i64_or(key, -1), -1);
+        assert_eq!(cfg.get_f64_or(key, 1.5), 1.5);
+        assert!(cfg.get_bool_or(key, true));
+        assert_eq!(cfg.get_str_or(key, "localhost"), "localhost");
+    }
+
+    // Right type, but out of range or the wrong kind of number
+    assert_eq!(cfg.get_u64_or("offset", 0), 0);
+    assert_eq!(cfg.get_i64_or("ratio", 3), 3);
+    assert_eq!(cfg.get_or("port", 1u8), 1);
+    assert_eq!(Value::Null.get_u64_or("port", 80), 80);
+}
+
+#[test]
+fn test_try_get() {
+    let cfg = settings();
+    assert_eq!(cfg.try_get_u64("port"), Ok(8080));
+    assert_eq!(cfg.try_get_i64("offset"), Ok(-5));
+    assert_eq!(cfg.try_get_f64("ratio"), Ok(0.5));
+    assert_eq!(cfg.try_get_bool("debug"), Ok(true));
+    assert_eq!(cfg.try_get_str("host"), Ok("example.com"));
+
+    let message = |x: FromJsonError| x.to_string();
+    assert_eq!(
+        cfg.try_get_u64("prot").map_err(message),
+        Err("missing field at /prot".to_owned())
+    );
+    assert_eq!(
+        cfg.try_get_i64("unset").map_err(message),
+        Err("expected number, found null at /unset".to_owned())
+    );
+    assert_eq!(
+        cfg.try_get_f64("host").map_err(message),
+        Err("expected number, found string at /host".to_owned())
+    );
+    assert_eq!(
+        cfg.try_get_bool("wrong").map_err(message),
+        Err("expected bool, found array at /wrong".to_owned())
+    );
+    assert_eq!(
+        cfg.try_get_str("port").map_err(message),
+        Err("expected string, found number at /port".to_owned())
+    );
+    assert_eq!(
+        cfg.try_get_str("missing").map_err(message),
+        Err("missing field at /missing".to_owned())
+    );
+    assert_eq!(
+        Value::from(vec![1]).try_get_str("host").map_err(message),
+        Err("expected object, found array at /".to_owned())
+    );
+}
+
+#[test]
+fn test_vec() {
+    let value = Value::from_str("[1, 2, 3]").unwrap();
+    assert_eq!(Vec::<i64>::from_json(&value), Ok(vec![1, 2, 3]));
+    assert_eq!(
+        Vec::<i64>::from_json(&Value::from_str("[]").unwrap()),
+        Ok(vec![])
+    );
+
+    let value = Value::from_str(r#"{"a": [[1], [2, null]]}"#).unwrap();
+    assert_eq!(
+        field::<Vec<Vec<Option<u8>>>>(&value, "a"),
+        Ok(vec![vec![Some(1)], vec![Some(2), None]])
+    );
+}
+
+#[test]
+fn test_vec_fail() {
+    let value = Value::from_str(r#"[1, 2, "three", 4.5]"#).unwrap();
+    let err = Vec::<i64>::from_json(&value).unwrap_err();
+    assert_eq!(err.to_string(), "expected number, found string at /2");
+
+    let value = Value::from_str(r#"{"a": [1, 2.5]}"#).unwrap();
+    let err = field::<Vec<i64>>(&value, "a").unwrap_err();
+    assert_eq!(err.kind(), &FromJsonErrorKind::NotInteger);
+    assert_eq!(err.to_string(), "expected integer at /a/1");
+
+    let err = Vec::<i64>::from_json(&Value::from(5)).unwrap_err();
+    assert_eq!(err.to_string(), "expected array, found number at /");
+}
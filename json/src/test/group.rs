@@ -0,0 +1,58 @@
+use std::str::FromStr;
+
+use crate::Value;
+
+fn records() -> Value {
+    Value::from_str(
+        r#"[{"id": 1, "team": "red", "name": "a"}, {"id": 2, "team": "blue", "name": "b"}, {"id": "3", "team": "red", "name": "c"}, {"team": "blue", "name": "d"}, {"id": [4], "team": null, "name": "e"}, 5]"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_index_by() {
+    let records = records();
+    let items = records.as_array().unwrap();
+    let (index, skipped) = records.index_by("id");
+
+    assert_eq!(index.keys().collect::<Vec<_>>(), ["1", "2", "3"]);
+    assert_eq!(index["3"].pointer("/name"), Some(&Value::from("c")));
+    assert_eq!(skipped, [&items[3], &items[4], &items[5]]);
+}
+
+#[test]
+fn test_index_by_duplicates() {
+    let records = records();
+    let (index, skipped) = records.index_by("team");
+
+    assert_eq!(index.len(), 2);
+    assert_eq!(index["red"].pointer("/name"), Some(&Value::from("c")));
+    assert_eq!(index["blue"].pointer("/name"), Some(&Value::from("d")));
+    assert_eq!(skipped.len(), 2);
+}
+
+#[test]
+fn test_group_by() {
+    let records = records();
+    let items = records.as_array().unwrap();
+    let (groups, skipped) = records.group_by("team");
+
+    assert_eq!(groups["red"], [&items[0], &items[2]]);
+    assert_eq!(groups["blue"], [&items[1], &items[3]]);
+    assert_eq!(skipped, [&items[4], &items[5]]);
+    assert_eq!(
+        Value::from("not an array").group_by("team"),
+        Default::default()
+    );
+}
+
+#[test]
+fn test_into_group_by() {
+    let (groups, skipped) = records().into_group_by("team");
+    assert_eq!(groups["red"].len(), 2);
+    assert_eq!(skipped.len(), 2);
+
+    let (index, skipped) = records().into_index_by("id");
+    assert_eq!(index["1"].pointer("/name"), Some(&Value::from("a")));
+    assert_eq!(skipped.len(), 3);
+}
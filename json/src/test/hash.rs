@@ -0,0 +1,72 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::{Number, Value};
+
+#[test]
+fn test_content_hash_equal() {
+    let parsed = Value::from_str(r#"{"b": [true, null], "a": "x", "c": -5}"#).unwrap();
+
+    let mut built = BTreeMap::new();
+    built.insert("c".to_owned(), Value::Number(Number::Int(-5)));
+    built.insert("a".to_owned(), Value::String("x".to_owned()));
+    built.insert(
+        "b".to_owned(),
+        Value::Array(vec![Value::Bool(true), Value::Null]),
+    );
+    let built = Value::Object(built);
+
+    assert_eq!(parsed, built);
+    assert_eq!(parsed.content_hash(), built.content_hash());
+}
+
+#[test]
+fn test_content_hash_insertion_order() {
+    let a = Value::from_entries([("x", Value::from(1)), ("y", Value::from(vec![2, 3]))]);
+    let b = Value::from_entries([
+        (
+            "y",
+            Value::Array(vec![Value::from(2_i64), Value::from(3_u8)]),
+        ),
+        ("x", Value::Number(Number::Int(1))),
+    ]);
+
+    assert_eq!(a.content_hash(), b.content_hash());
+}
+
+#[test]
+fn test_content_hash_numbers() {
+    let uint = Value::Number(Number::UInt(1));
+    let int = Value::Number(Number::Int(1));
+    let float = Value::Number(Number::Float(1.0));
+    assert_eq!(uint.content_hash(), int.content_hash());
+    assert_ne!(uint.content_hash(), float.content_hash());
+
+    let zero = Value::Number(Number::Float(0.0));
+    let neg_zero = Value::Number(Number::Float(-0.0));
+    assert_eq!(zero.content_hash(), neg_zero.content_hash());
+}
+
+#[test]
+fn test_content_hash_different() {
+    let a = Value::from_str(r#"{"name": "half-stack"}"#).unwrap();
+    let b = Value::from_str(r#"{"name": "half-stacj"}"#).unwrap();
+    assert_ne!(a.content_hash(), b.content_hash());
+
+    // Moving a value between containers must change the hash
+    let a = Value::from_str(r#"[["a"], []]"#).unwrap();
+    let b = Value::from_str(r#"[[], ["a"]]"#).unwrap();
+    assert_ne!(a.content_hash(), b.content_hash());
+
+    let a = Value::from_str(r#"{"ab": "c"}"#).unwrap();
+    let b = Value::from_str(r#"{"a": "bc"}"#).unwrap();
+    assert_ne!(a.content_hash(), b.content_hash());
+}
+
+#[test]
+fn test_content_hash_stable() {
+    assert_eq!(Value::Null.content_hash(), 0xaf63e34c8601f871);
+    assert_eq!(
+        Value::from_str(r#"{"a": [true]}"#).unwrap().content_hash(),
+        0x16adf877174826a3
+    );
+}
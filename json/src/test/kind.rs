@@ -0,0 +1,42 @@
+use std::str::FromStr;
+
+use crate::{JsonType, ParseJsonTypeError, Value};
+
+#[test]
+fn test_kind() {
+    let value = Value::from_str(r#"[null, true, 1, -2.5, "a", [], {}]"#).unwrap();
+    let kinds = value.as_array().unwrap().iter().map(Value::kind);
+    assert_eq!(
+        kinds.collect::<Vec<_>>(),
+        [
+            JsonType::Null,
+            JsonType::Bool,
+            JsonType::Number,
+            JsonType::Number,
+            JsonType::String,
+            JsonType::Array,
+            JsonType::Object
+        ]
+    );
+}
+
+#[test]
+fn test_json_type_names() {
+    let types = [
+        JsonType::Null,
+        JsonType::Bool,
+        JsonType::Number,
+        JsonType::String,
+        JsonType::Array,
+        JsonType::Object,
+    ];
+
+    for i in types {
+        assert_eq!(i.to_string().parse::<JsonType>(), Ok(i));
+    }
+    assert_eq!(JsonType::Bool.to_string(), "bool");
+    assert_eq!(JsonType::from_str("boolean"), Ok(JsonType::Bool));
+    assert_eq!(JsonType::from_str("integer"), Ok(JsonType::Number));
+    assert_eq!(JsonType::from_str("Object"), Err(ParseJsonTypeError));
+    assert_eq!(JsonType::from_str(""), Err(ParseJsonTypeError));
+}
@@ -0,0 +1,1000 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    sync::Arc,
+};
+
+use crate::parser::Parser;
+
+use super::*;
+
+mod callback;
+mod coerce;
+mod csv;
+mod document;
+mod file;
+mod from;
+mod from_json;
+mod group;
+mod hash;
+mod kind;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parser;
+mod patch;
+mod query;
+mod schema;
+mod serialize;
+mod tokenizer;
+mod toml;
+mod walk;
+mod yaml;
+
+#[test]
+fn test_empty() {
+    let mut parser = Parser::new("");
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(0)));
+}
+
+#[test]
+fn test_null() {
+    let mut parser = Parser::new("null");
+    assert_eq!(parser.parse(), Ok(Value::Null));
+}
+
+#[test]
+fn test_null_fail() {
+    let mut parser = Parser::new("nul");
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(3)));
+}
+
+#[test]
+fn test_bool() {
+    let mut parser = Parser::new("true");
+    assert_eq!(parser.parse(), Ok(Value::Bool(true)));
+
+    let mut parser = Parser::new("false");
+    assert_eq!(parser.parse(), Ok(Value::Bool(false)));
+}
+
+#[test]
+fn test_bool_fail() {
+    let mut parser = Parser::new("tru");
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(3)));
+
+    let mut parser = Parser::new("fals");
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(4)));
+}
+
+#[test]
+fn test_number() {
+    let mut parser = Parser::new("123");
+    assert_eq!(parser.parse(), Ok(Value::Number(Number::UInt(123))));
+
+    let mut parser = Parser::new("-123");
+    assert_eq!(parser.parse(), Ok(Value::Number(Number::Int(-123))));
+
+    let mut parser = Parser::new("123.456");
+    assert_eq!(parser.parse(), Ok(Value::Number(Number::Float(123.456))));
+}
+
+#[test]
+fn test_number_fail() {
+    let mut parser = Parser::new("123d");
+    assert!(matches!(parser.parse(), Err(Error::InvalidNumber { .. })));
+
+    let mut parser = Parser::new("123.456.789");
+    assert!(matches!(parser.parse(), Err(Error::InvalidNumber { .. })));
+}
+
+#[test]
+fn test_number_fail_pos() {
+    let mut parser = Parser::new("[1, 2x]");
+    assert!(matches!(
+        parser.parse(),
+        Err(Error::InvalidNumber { pos: 4, .. })
+    ));
+
+    let mut parser = Parser::new(r#"{"a": 1.2.3}"#);
+    assert!(matches!(
+        parser.parse(),
+        Err(Error::InvalidNumber { pos: 6, .. })
+    ));
+}
+
+#[test]
+fn test_string() {
+    let mut parser = Parser::new(r#""hello""#);
+    assert_eq!(parser.parse(), Ok(Value::String("hello".to_string())));
+}
+
+#[test]
+fn test_string_fail() {
+    let mut parser = Parser::new(r#""hello"#);
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(6)));
+}
+
+#[test]
+fn test_string_escape_fail() {
+    let mut parser = Parser::new(r#""bad \q escape""#);
+    assert_eq!(
+        parser.parse(),
+        Err(Error::InvalidEscape { pos: 5, found: 'q' })
+    );
+
+    let mut parser = Parser::new(r#"["ok", {"k": "é \x"}]"#);
+    assert_eq!(
+        parser.parse(),
+        Err(Error::InvalidEscape {
+            pos: 17,
+            found: 'x'
+        })
+    );
+}
+
+#[test]
+fn test_array() {
+    let mut parser = Parser::new(r#"["hello", "world"]"#);
+    assert_eq!(
+        parser.parse(),
+        Ok(Value::Array(vec![
+            Value::String("hello".to_string()),
+            Value::String("world".to_string())
+        ]))
+    );
+}
+
+#[test]
+fn test_array_fail() {
+    let mut parser = Parser::new(r#"["hello", "world""#);
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(17)));
+}
+
+#[test]
+fn test_nested_array() {
+    let mut parser = Parser::new(r#"[["hello", "world"], ["hello", "world"]]"#);
+    assert_eq!(
+        parser.parse(),
+        Ok(Value::Array(vec![
+            Value::Array(vec![
+                Value::String("hello".to_string()),
+                Value::String("world".to_string())
+            ]),
+            Value::Array(vec![
+                Value::String("hello".to_string()),
+                Value::String("world".to_string())
+            ])
+        ]))
+    );
+}
+
+#[test]
+fn test_nested_array_fail() {
+    let mut parser = Parser::new(r#"[["hello", "world"], ["hello", "world"]"#);
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(39)));
+}
+
+#[test]
+fn test_object() {
+    let mut parser = Parser::new(r#"{"hello": "world"}"#);
+    let mut map = BTreeMap::new();
+    map.insert("hello".to_string(), Value::String("world".to_string()));
+    assert_eq!(parser.parse(), Ok(Value::Object(map)));
+}
+
+#[test]
+fn test_object_fail() {
+    let mut parser = Parser::new(r#"{"hello": "world""#);
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(17)));
+}
+
+#[test]
+fn test_string_escape() {
+    let value = Value::from_str(r#""hello \"world\"""#).unwrap();
+    assert_eq!(value.as_string().unwrap(), "hello \"world\"");
+
+    let value = Value::from_str(r#"{"hello":"\"world\""}"#).unwrap();
+    assert_eq!(
+        value
+            .as_object()
+            .unwrap()
+            .get("hello")
+            .unwrap()
+            .as_string()
+            .unwrap(),
+        "\"world\""
+    );
+}
+
+#[test]
+fn test_api() {
+    let value = Value::from_str(r#"{"hello": "world"}"#).unwrap();
+    assert_eq!(
+        value
+            .as_object()
+            .unwrap()
+            .get("hello")
+            .unwrap()
+            .as_string()
+            .unwrap(),
+        "world"
+    );
+}
+
+#[test]
+fn test_to_string() {
+    let value = Value::from_str(r#"{"hello": "world"}"#).unwrap();
+    assert_eq!(value.to_string(), r#"{"hello":"world"}"#);
+
+    let value = Value::from_str(r#"{"hello": "world", "foo": "bar"}"#).unwrap();
+    assert_eq!(value.to_string(), r#"{"foo":"bar","hello":"world"}"#);
+
+    let value = Value::from_str(r#"[{"hello": "world"}, {"foo": "bar"}]"#).unwrap();
+    assert_eq!(value.to_string(), r#"[{"hello":"world"},{"foo":"bar"}]"#);
+}
+
+#[test]
+fn test_pointer_mut() {
+    let mut value = Value::from_str(r#"{"a": [1, {"b": 2}]}"#).unwrap();
+    *value.pointer_mut("/a/1/b").unwrap() = Value::from("x");
+    assert_eq!(value.to_string(), r#"{"a":[1,{"b":"x"}]}"#);
+
+    assert!(value.pointer_mut("/a/2").is_none());
+    assert!(value.pointer_mut("a").is_none());
+}
+
+#[test]
+fn test_get_path_mut() {
+    let mut value = Value::from_str(r#"{"a": {"b": {"c": 1}}, "d": [{"e": true}]}"#).unwrap();
+    *value.get_path_mut("a.b.c").unwrap() = Value::from(2);
+    value
+        .get_path_mut("d.0")
+        .unwrap()
+        .as_mut_object()
+        .unwrap()
+        .clear();
+    assert_eq!(value.to_string(), r#"{"a":{"b":{"c":2}},"d":[{}]}"#);
+
+    assert!(value.get_path_mut("a.b.x").is_none());
+    assert!(value.get_path_mut("a.b.c.d").is_none());
+    assert!(value.get_path_mut("d.01").is_none());
+    assert_eq!(value.get_path_mut("a.x"), None);
+    assert_eq!(value.to_string(), r#"{"a":{"b":{"c":2}},"d":[{}]}"#);
+    assert!(value.get_path_mut("").unwrap().is_object());
+}
+
+#[test]
+fn test_round_trip() {
+    let value =
+        Value::from_str(r#"{"a": [1, -2, 3.5, "x\"y\\z\n/", null, true], "b": {}}"#).unwrap();
+    assert!(value.round_trip());
+    assert!(Value::from("\\\"").round_trip());
+    assert!(Value::Number(Number::Int(-5)).round_trip());
+}
+
+#[test]
+fn test_brackets_in_strings() {
+    assert_eq!(Value::from_str(r#"["]"]"#).unwrap(), Value::from(vec!["]"]));
+    assert_eq!(
+        Value::from_str(r#"["a]", 1]"#).unwrap(),
+        Value::Array(vec![Value::from("a]"), Value::from(1)])
+    );
+    assert_eq!(Value::from_str(r#"["["]"#).unwrap(), Value::from(vec!["["]));
+    assert!(Value::from(vec!["]", "x"]).round_trip());
+    assert!(Value::from(vec![Value::from(vec!["[["]), Value::from("]")]).round_trip());
+}
+
+#[test]
+fn test_round_trip_fail() {
+    assert!(!Value::Number(Number::Float(1.0)).round_trip());
+    assert!(!Value::Number(Number::Int(5)).round_trip());
+}
+
+#[test]
+fn test_contains() {
+    let value = Value::from_str(r#"{"a": [1, {"b": "deep"}], "c": null}"#).unwrap();
+
+    assert!(value.contains(&Value::from("deep")));
+    assert!(value.contains(&Value::from([("b", "deep")])));
+    assert!(value.contains(&Value::Null));
+    assert!(value.contains(&value));
+    assert!(!value.contains(&Value::from("missing")));
+    assert!(!value.contains(&Value::from("b")));
+    assert!(!value.contains(&Value::from(2)));
+}
+
+#[test]
+fn test_stats() {
+    let value = Value::from_str(
+        r#"{"name": "half-stack", "tags": ["json", "web", null], "meta": {"stars": 5, "ok": true, "deps": [[1, 2]]}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        value.stats(),
+        Stats {
+            nulls: 1,
+            bools: 1,
+            numbers: 3,
+            strings: 3,
+            arrays: 3,
+            objects: 2,
+            keys: 6,
+            string_bytes: 17,
+            key_bytes: 23,
+            max_depth: 4,
+            max_array_len: 3,
+            max_object_len: 3,
+        }
+    );
+    assert_eq!(value.stats().nodes(), 13);
+
+    assert_eq!(Value::Null.stats().max_depth, 0);
+    assert_eq!(Value::Array(vec![]).stats().max_depth, 0);
+}
+
+#[test]
+fn test_value_ord() {
+    let mut values = Value::from_str(
+        r#"[{"b": 1}, "b", [1, 2], 2.5, null, true, {"a": 2}, "a", [1], false, -3, {"a": 1, "b": 1}, [], 2]"#,
+    )
+    .unwrap()
+    .as_array()
+    .unwrap()
+    .clone();
+    values.sort();
+
+    assert_eq!(
+        Value::Array(values).to_string(),
+        r#"[null,false,true,-3,2,2.5,"a","b",[],[1],[1,2],{"a":1,"b":1},{"a":2},{"b":1}]"#
+    );
+}
+
+#[test]
+fn test_number_ord() {
+    let mut numbers = vec![
+        Number::Float(f64::NAN),
+        Number::UInt(u64::MAX),
+        Number::Float(1.0),
+        Number::Int(1),
+        Number::UInt(1),
+        Number::Float(-0.5),
+        Number::Int(i64::MIN),
+        Number::Float(f64::NEG_INFINITY),
+        Number::Float(18_446_744_073_709_551_615.0),
+        Number::Float(0.0),
+    ];
+    numbers.sort();
+
+    assert_eq!(
+        numbers,
+        [
+            Number::Float(f64::NEG_INFINITY),
+            Number::Int(i64::MIN),
+            Number::Float(-0.5),
+            Number::Float(0.0),
+            Number::UInt(1),
+            Number::Int(1),
+            Number::Float(1.0),
+            // u64::MAX rounds up to 2^64 as a float
+            Number::UInt(u64::MAX),
+            Number::Float(18_446_744_073_709_551_615.0),
+            Number::Float(f64::NAN),
+        ]
+    );
+
+    assert_eq!(Number::Float(-0.0), Number::Float(0.0));
+    assert_eq!(Number::Float(f64::NAN), Number::Float(f64::NAN));
+    assert_ne!(Number::Int(1), Number::UInt(1));
+}
+
+#[test]
+fn test_value_btreemap_key() {
+    let mut map = BTreeMap::new();
+    map.insert(Value::from("x"), 1);
+    map.insert(Value::from(vec![1, 2]), 2);
+    map.insert(Value::Null, 3);
+    map.insert(Value::from(5), 4);
+    map.insert(Value::from("x"), 5);
+
+    assert_eq!(map.len(), 4);
+    assert_eq!(map[&Value::from("x")], 5);
+    assert_eq!(map.into_values().collect::<Vec<_>>(), [3, 4, 5, 2]);
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(Value::default(), Value::Null);
+
+    let mut value = Value::from(vec![1, 2]);
+    let taken = std::mem::take(&mut value);
+    assert_eq!(value, Value::Null);
+    assert_eq!(taken, Value::from(vec![1, 2]));
+}
+
+#[test]
+fn test_from_iter_array() {
+    let value = (0..3).map(|n| Value::from(n as i64)).collect::<Value>();
+    assert_eq!(value, Value::from(vec![0, 1, 2]));
+    assert_eq!(value.to_string(), "[0,1,2]");
+
+    let empty = std::iter::empty::<Value>().collect::<Value>();
+    assert_eq!(empty, Value::Array(Vec::new()));
+}
+
+#[test]
+fn test_from_iter_object() {
+    let value = ["b", "a", "b"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, k)| (k.to_owned(), Value::from(i)))
+        .collect::<Value>();
+    assert_eq!(value.to_string(), r#"{"a":1,"b":2}"#);
+}
+
+#[test]
+fn test_extend_array() {
+    let mut value = Value::from(vec![1, 2]);
+    value.extend(Value::from(vec![2, 3]));
+    assert_eq!(value.to_string(), "[1,2,2,3]");
+
+    value.extend(Value::Array(Vec::new()));
+    assert_eq!(value.to_string(), "[1,2,2,3]");
+}
+
+#[test]
+fn test_extend_object() {
+    let mut value = Value::from_str(r#"{"a": 1, "b": {"x": 1}}"#).unwrap();
+    value.extend(Value::from_str(r#"{"b": {"y": 2}, "c": 3}"#).unwrap());
+    assert_eq!(value.to_string(), r#"{"a":1,"b":{"y":2},"c":3}"#);
+}
+
+#[test]
+fn test_extend_mismatched() {
+    let mut value = Value::from(vec![1]);
+    value.extend(Value::from([("a", 1)]));
+    value.extend(Value::from(2));
+    assert_eq!(value.to_string(), "[1]");
+
+    let mut value = Value::from("x");
+    value.extend(Value::from("y"));
+    assert_eq!(value, Value::from("x"));
+}
+
+#[test]
+fn test_increment() {
+    let mut value = Value::from_str(r#"{"hits": 41, "temp": -1, "avg": 1.5}"#).unwrap();
+    assert!(value.pointer_mut("/hits").unwrap().increment(1.0));
+    assert!(value.pointer_mut("/temp").unwrap().increment(-2.0));
+    assert!(value.pointer_mut("/avg").unwrap().increment(1.0));
+    assert_eq!(value.to_string(), r#"{"avg":2.5,"hits":42,"temp":-3}"#);
+    assert_eq!(
+        value.pointer("/hits"),
+        Some(&Value::Number(Number::UInt(42)))
+    );
+
+    // Fractions and results too big for an integer become floats
+    let mut value = Value::from(1);
+    assert!(value.increment(0.5));
+    assert_eq!(value, Value::Number(Number::Float(1.5)));
+    let mut value = Value::from(u128::MAX);
+    assert!(value.increment(1.0));
+    assert_eq!(value, Value::Number(Number::Float(u128::MAX as f64 + 1.0)));
+
+    let mut value = Value::from(u64::MAX);
+    assert!(value.increment(1.0));
+    assert_eq!(value, Value::Number(Number::UInt128(u64::MAX as u128 + 1)));
+
+    for mut value in [Value::Null, Value::from("1"), Value::from(vec![1])] {
+        let before = value.clone();
+        assert!(!value.increment(1.0));
+        assert_eq!(value, before);
+    }
+}
+
+#[test]
+fn test_number_missing_digits() {
+    let cases = ["-", ".", "-.", "1.", ".5", "-.5", "-1.", "1.e5", "[1.]"];
+    for i in cases {
+        let err = Value::from_str(i).unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidNumber {
+                pos: i.starts_with('[') as usize,
+                error: ParseNumberError::MissingDigits
+            },
+            "{i}"
+        );
+    }
+
+    assert_eq!(
+        Value::from_str("-").unwrap_err().to_string(),
+        "invalid number at 0: missing digits"
+    );
+    assert_eq!(Value::from_str("-0.5"), Ok(Value::from(-0.5)));
+    assert_eq!(Value::from_str("1.0e5"), Ok(Value::from(1.0e5)));
+}
+
+#[test]
+fn test_number_exponent() {
+    let raw = ParseOptions {
+        raw_numbers: true,
+        ..Default::default()
+    };
+    for (input, expected) in [("1e5", 1e5), ("-2E3", -2e3), ("3e-2", 3e-2), ("4E+1", 4e1)] {
+        assert_eq!(Value::from_str(input), Ok(Value::from(expected)), "{input}");
+        assert_eq!(
+            Value::from_str_with(input, &raw),
+            Ok(Value::Number(Number::Raw(input.to_owned()))),
+            "{input}"
+        );
+    }
+    assert_eq!(Value::from_str("[1e2, 2]").unwrap().to_string(), "[100,2]");
+}
+
+#[test]
+fn test_raw_numbers() {
+    let options = ParseOptions {
+        raw_numbers: true,
+        ..Default::default()
+    };
+    let big = "123456789012345678901234567890";
+
+    let value = Value::from_str_with("1.10", &options).unwrap();
+    assert_eq!(value, Value::Number(Number::Raw("1.10".to_owned())));
+    assert_eq!(value.to_string(), "1.10");
+
+    let value = Value::from_str_with(big, &options).unwrap();
+    assert_eq!(value.to_string(), big);
+    // Without raw numbers, integers this wide lose precision as floats
+    let wide = big.repeat(2);
+    assert_eq!(
+        Value::from_str(&wide),
+        Ok(Value::Number(Number::Float(wide.parse().unwrap())))
+    );
+    assert_eq!(
+        Value::from_str_with(&wide, &options).unwrap().to_string(),
+        wide
+    );
+
+    let input = r#"{"a":[1.10,-0.0,2.5e10],"b":123456789012345678901234567890}"#;
+    assert_eq!(
+        Value::from_str_with(input, &options).unwrap().to_string(),
+        input
+    );
+}
+
+#[test]
+fn test_raw_numbers_fail() {
+    let options = ParseOptions {
+        raw_numbers: true,
+        ..Default::default()
+    };
+    for i in ["-", "1.", ".5", "1e", "1x", "-inf", "1.2.3"] {
+        assert!(
+            matches!(
+                Value::from_str_with(i, &options),
+                Err(Error::InvalidNumber { pos: 0, .. })
+            ),
+            "{i}"
+        );
+    }
+}
+
+#[test]
+fn test_raw_numbers_convert() {
+    let raw = |x: &str| Value::Number(Number::Raw(x.to_owned()));
+    assert_eq!(raw("42").coerce_u64(), Some(42));
+    assert_eq!(raw("-42").coerce_i64(), Some(-42));
+    assert_eq!(raw("1.10").coerce_f64(), Some(1.1));
+    assert_eq!(u8::from_json(&raw("42")), Ok(42));
+    assert!(u8::from_json(&raw("1.5")).is_err());
+    assert_eq!(f64::from_json(&raw("2.5e1")), Ok(25.0));
+
+    // Ordered by value, with the text breaking ties
+    assert!(raw("9") < raw("10"));
+    assert!(raw("1.1") < raw("1.10"));
+    assert!(Value::from(1.1) < raw("1.10"));
+    assert!(raw("2") > Value::from(1));
+    assert_ne!(raw("1"), Value::from(1));
+}
+
+#[test]
+fn test_number_128() {
+    let big = u64::MAX as u128 + 1;
+    let value = Value::from_str(&big.to_string()).unwrap();
+    assert_eq!(value, Value::Number(Number::UInt128(big)));
+    assert_eq!(value.to_string(), "18446744073709551616");
+    assert_eq!(value, Value::from(big));
+
+    let max = Value::from_str(&u128::MAX.to_string()).unwrap();
+    assert_eq!(max, Value::Number(Number::UInt128(u128::MAX)));
+    assert_eq!(max.to_string(), u128::MAX.to_string());
+
+    let small = i64::MIN as i128 - 1;
+    let value = Value::from_str(&small.to_string()).unwrap();
+    assert_eq!(value, Value::Number(Number::Int128(small)));
+    assert_eq!(value, Value::from(small));
+
+    // Still 64 bits when they fit
+    assert_eq!(
+        Value::from_str("18446744073709551615"),
+        Ok(Value::from(u64::MAX))
+    );
+    assert_eq!(Value::from(5_u128), Value::from(5));
+
+    // Wider than 128 bits falls back to a float
+    assert_eq!(
+        Value::from_str("340282366920938463463374607431768211456"),
+        Ok(Value::Number(Number::Float(2f64.powi(128))))
+    );
+    assert_eq!(
+        Value::from_str("-170141183460469231731687303715884105729"),
+        Ok(Value::Number(Number::Float(-(2f64.powi(127)))))
+    );
+}
+
+#[test]
+fn test_number_128_order() {
+    let big = Number::UInt128(u64::MAX as u128 + 1);
+    assert!(Number::UInt(u64::MAX) < big);
+    assert!(Number::Int(-1) < big);
+    assert!(Number::Int128(i128::MIN) < Number::Int(i64::MIN));
+    assert!(Number::Float(1.8e19) < big);
+    assert!(Number::Float(1.9e19) > big);
+    assert!(Number::UInt128(u128::MAX) < Number::Float(u128::MAX as f64));
+    assert!(Number::Int128(i128::MIN) > Number::Float(f64::NEG_INFINITY));
+    assert_ne!(Number::UInt128(5), Number::UInt(5));
+
+    assert_eq!(
+        Value::Number(Number::UInt128(5)).content_hash(),
+        Value::from(5).content_hash()
+    );
+    assert_ne!(
+        Value::Number(big.clone()).content_hash(),
+        Value::from(0).content_hash()
+    );
+    assert_eq!(
+        u128::from_json(&Value::Number(big)),
+        Ok(u64::MAX as u128 + 1)
+    );
+}
+
+#[test]
+fn test_approx_eq() {
+    let a =
+        Value::from_str(r#"{"a": [0.30000000000000004, 1, "x"], "b": {"c": 2.0000001}}"#).unwrap();
+    let b = Value::from_str(r#"{"a": [0.3, 1, "x"], "b": {"c": 2}}"#).unwrap();
+    assert_ne!(a, b);
+    assert!(a.approx_eq(&b, 1e-6));
+    assert!(b.approx_eq(&a, 1e-6));
+    assert!(!a.approx_eq(&b, 1e-9));
+    assert!(Value::from(f64::NAN).approx_eq(&Value::from(f64::NAN), 0.0));
+    assert!(Value::from(f64::INFINITY).approx_eq(&Value::from(f64::INFINITY), 0.0));
+}
+
+#[test]
+fn test_approx_eq_exact_leaves() {
+    let cases = [
+        (r#"{"a": 1}"#, r#"{"b": 1}"#),
+        (r#"{"a": 1}"#, r#"{"a": 1, "b": 2}"#),
+        ("[1.0]", "[1.0, 2.0]"),
+        ("1", "2"),
+        (r#""1.0""#, r#""1.00""#),
+        ("1.5", r#""1.5""#),
+    ];
+    for (a, b) in cases {
+        let (a, b) = (Value::from_str(a).unwrap(), Value::from_str(b).unwrap());
+        assert!(!a.approx_eq(&b, 1.0), "{a} {b}");
+    }
+}
+
+#[test]
+fn test_string_backslashes() {
+    let cases = [
+        (r#""\\""#, "\\"),
+        (r#""\\\\""#, "\\\\"),
+        (r#""a\\""#, "a\\"),
+        (r#""\\\"""#, "\\\""),
+        (r#""\"\\""#, "\"\\"),
+        (r#""\\\\\\n""#, "\\\\\\n"),
+    ];
+    for (json, string) in cases {
+        let value = Value::from_str(json).unwrap();
+        assert_eq!(value, Value::from(string), "{json}");
+        assert_eq!(value.to_string(), json);
+        assert!(value.round_trip(), "{json}");
+    }
+
+    let value = Value::from_str(r#"["\\", {"\\": "\\\\"}, "a\\"]"#).unwrap();
+    assert_eq!(value.pointer("/1/\\"), Some(&Value::from("\\\\")));
+    assert!(value.round_trip());
+}
+
+#[test]
+fn test_string_unterminated() {
+    for i in [r#"""#, r#""\""#, r#""abc"#, r#""a\\\""#, r#""\"#] {
+        assert_eq!(
+            Value::from_str(i),
+            Err(Error::UnexpectedEnd(i.len())),
+            "{i}"
+        );
+    }
+}
+
+#[test]
+fn test_string_trailing_backslash() {
+    for i in [
+        r#""\"#,
+        r#""abc\"#,
+        r#""\\\"#,
+        r#"["\"#,
+        r#"{"a": "\"#,
+        r#"{"\"#,
+    ] {
+        let mut parser = Parser::new(i);
+        assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(i.len())), "{i}");
+    }
+}
+
+#[test]
+fn test_hash_value() {
+    let value = Value::from_str(r#"{"b": [1, {"c": null}], "a": "x"}"#).unwrap();
+    let hashed = value.clone().convert::<Hashed>();
+    let GenericValue::Object(map) = &hashed else {
+        panic!("expected an object");
+    };
+    let _: &HashMap<String, HashValue> = map;
+    assert_eq!(map["a"], HashValue::String("x".to_owned()));
+    assert_eq!(
+        map["b"],
+        HashValue::Array(vec![
+            HashValue::Number(Number::UInt(1)),
+            HashValue::Object(HashMap::from([("c".to_owned(), HashValue::Null)]))
+        ])
+    );
+
+    let built = HashValue::Object(HashMap::from([("a".to_owned(), HashValue::Bool(true))]));
+    assert_eq!(built.convert::<Ordered>(), Value::from([("a", true)]));
+    assert_eq!(hashed.convert::<Ordered>(), value);
+    assert_eq!(HashValue::default(), HashValue::Null);
+}
+
+#[test]
+fn test_interned_keys() {
+    let interner = KeyInterner::new();
+    let options = ParseOptions {
+        interner: Some(interner.clone()),
+        ..Default::default()
+    };
+    let first = r#"{"id": 1, "user": {"name": "a", "id": 2}, "q\"k": 0}"#;
+    let second = r#"{"id": 3, "user": {"name": "b"}, "q\"k": 1, "tags": []}"#;
+    let a = InternedValue::from_str_with(first, &options).unwrap();
+    let b = InternedValue::from_str_with(second, &options).unwrap();
+
+    // Both documents share one copy of each key, including keys with escapes
+    let keys = |x: &InternedValue| match x {
+        GenericValue::Object(x) => x.keys().cloned().collect::<Vec<_>>(),
+        _ => panic!("expected an object"),
+    };
+    let (a_keys, b_keys) = (keys(&a), keys(&b));
+    assert_eq!(a_keys, ["id", "q\"k", "user"].map(Arc::from));
+    assert!(Arc::ptr_eq(&a_keys[0], &b_keys[0]));
+    assert!(Arc::ptr_eq(&a_keys[1], &b_keys[1]));
+    assert!(Arc::ptr_eq(&a_keys[2], &b_keys[3]));
+    let user = |x: &InternedValue| match x {
+        GenericValue::Object(x) => keys(&x["user"]),
+        _ => unreachable!(),
+    };
+    assert!(Arc::ptr_eq(&user(&a)[1], &user(&b)[0]));
+    assert!(Arc::ptr_eq(&user(&a)[0], &a_keys[0]));
+    assert_eq!(interner.len(), 5);
+
+    assert_eq!(a.convert::<Ordered>(), Value::from_str(first).unwrap());
+    assert_eq!(b.convert::<Ordered>(), Value::from_str(second).unwrap());
+
+    // Without an interner, keys aren't shared
+    let c = InternedValue::from_str_with(first, &ParseOptions::default()).unwrap();
+    assert!(!Arc::ptr_eq(&keys(&c)[0], &a_keys[0]));
+    assert_eq!(c, InternedValue::from_str_with(first, &options).unwrap());
+}
+
+#[test]
+fn test_write_into() {
+    let value =
+        Value::from_str(r#"{"name": "ferris", "tags": ["a\n", 1.5, null], "n": -3}"#).unwrap();
+    let json = value.to_string();
+    assert_eq!(value.serialized_len(), json.len());
+
+    let mut buf = [0; 64];
+    let len = value.write_into(&mut buf).unwrap();
+    assert_eq!(&buf[..len], json.as_bytes());
+
+    let mut exact = vec![0; json.len()];
+    assert_eq!(value.write_into(&mut exact), Ok(json.len()));
+    assert_eq!(exact, json.as_bytes());
+
+    let mut small = vec![0; json.len() - 1];
+    assert_eq!(
+        value.write_into(&mut small),
+        Err(BufferTooSmall { needed: json.len() })
+    );
+    assert_eq!(
+        Value::Null.write_into(&mut []),
+        Err(BufferTooSmall { needed: 4 })
+    );
+}
+
+#[test]
+fn test_sort_keys() {
+    let json = r#"[{"b": 1, "a": {"z": [{"y": 2, "x": 3}], "c": null}}, "d"]"#;
+    let mut value = Value::from_str(json).unwrap();
+    let before = value.to_string();
+    let original = value.clone();
+
+    value.sort_keys();
+    assert_eq!(value, original);
+    assert_eq!(value.to_string(), before);
+    assert_eq!(
+        before,
+        r#"[{"a":{"c":null,"z":[{"x":3,"y":2}]},"b":1},"d"]"#
+    );
+}
+
+#[test]
+fn test_pointer_many() {
+    let value = Value::from_str(r#"{"id": 7, "user": {"name": "ferris"}, "tags": []}"#).unwrap();
+    assert_eq!(
+        value.pointer_many(&["/user/name", "/tags/0", "/id"]),
+        vec![Some(&Value::from("ferris")), None, Some(&Value::from(7))]
+    );
+    assert!(value.pointer_many(&[]).is_empty());
+}
+
+#[test]
+fn test_large_object() {
+    let entries = (0..5000)
+        .map(|i| (format!("key{{{i}}}:"), format!("}}value: {{{i}")))
+        .collect::<Vec<_>>();
+    let json = format!(
+        "{{{}}}",
+        entries
+            .iter()
+            .map(|(k, v)| format!(r#""{k}": "{v}""#))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let value = Value::from_str(&json).unwrap();
+    assert_eq!(value, Value::from_entries(entries));
+    assert_eq!(value.as_object().unwrap().len(), 5000);
+    assert_eq!(
+        value.pointer("/key{42}:"),
+        Some(&Value::from("}value: {42"))
+    );
+
+    let nested = Value::from_str(r#"{"a": {"}": "{"}, "b": [{"c:": "}}"}], "d": "{"}"#).unwrap();
+    assert_eq!(
+        nested,
+        Value::from([
+            ("a", Value::from([("}", "{")])),
+            ("b", Value::from(vec![Value::from([("c:", "}}")])])),
+            ("d", Value::from("{")),
+        ])
+    );
+    assert_eq!(
+        Value::from_str(r#"{"a": "}""#),
+        Err(Error::UnexpectedEnd(9))
+    );
+}
+
+#[test]
+fn test_max_number_len() {
+    let long = format!("[1, {}]", "9".repeat(10_000));
+    assert_eq!(
+        Value::from_str(&long),
+        Err(Error::InvalidNumber {
+            pos: 4,
+            error: ParseNumberError::TooLong(1024)
+        })
+    );
+
+    let options = ParseOptions {
+        max_number_len: 5,
+        ..Default::default()
+    };
+    assert_eq!(
+        Value::from_str_with("-1.25", &options),
+        Ok(Value::from(-1.25))
+    );
+    assert_eq!(
+        Value::from_str_with("-1.255", &options),
+        Err(Error::InvalidNumber {
+            pos: 0,
+            error: ParseNumberError::TooLong(5)
+        })
+    );
+
+    let options = ParseOptions {
+        max_number_len: 20_000,
+        raw_numbers: true,
+        ..Default::default()
+    };
+    assert!(Value::from_str_with(&long, &options).is_ok());
+}
+
+#[test]
+fn test_max_string_len() {
+    let options = ParseOptions {
+        max_string_len: 4,
+        ..Default::default()
+    };
+    let parse = |x| Value::from_str_with(x, &options);
+
+    assert_eq!(parse(r#""abcd""#), Ok(Value::from("abcd")));
+    assert_eq!(parse(r#"["a\nb"]"#), Ok(Value::from(vec!["a\nb"])));
+    assert_eq!(
+        parse(r#"["abcde"]"#),
+        Err(Error::StringTooLong { pos: 1, max: 4 })
+    );
+    assert_eq!(
+        parse(r#"{"abc\n": 1}"#),
+        Err(Error::StringTooLong { pos: 1, max: 4 })
+    );
+
+    // The limit is checked as the string is read, so long unterminated strings fail the same way
+    let long = format!("\"{}", "a".repeat(10_000));
+    assert_eq!(parse(&long), Err(Error::StringTooLong { pos: 0, max: 4 }));
+    assert!(Value::from_str(&format!("{long}\"")).is_ok());
+}
+
+#[test]
+fn test_max_nodes() {
+    let options = ParseOptions {
+        max_nodes: 4,
+        ..Default::default()
+    };
+    let parse = |x| Value::from_str_with(x, &options);
+
+    assert!(parse("[1, [2]]").is_ok());
+    assert!(parse(r#"{"a": 1, "b": [null]}"#).is_ok());
+    assert_eq!(
+        parse("[1, 2, 3, 4, 5]"),
+        Err(Error::TooManyNodes { pos: 10, max: 4 })
+    );
+
+    let huge = format!("[{}]", "0,".repeat(1_000_000));
+    let options = ParseOptions {
+        max_nodes: 1000,
+        ..Default::default()
+    };
+    assert_eq!(
+        Value::from_str_with(&huge, &options),
+        Err(Error::TooManyNodes {
+            pos: 1999,
+            max: 1000
+        })
+    );
+}
+
+#[test]
+fn test_debug() {
+    let value = Value::from_str(r#"{"a": [1, -2, 1.5, null, true], "b": "x\n", "c": {}}"#).unwrap();
+    assert_eq!(
+        format!("{value:?}"),
+        r#"{"a": [1u64, -2i64, 1.5f64, null, true], "b": "x\n", "c": {}}"#
+    );
+    assert_eq!(
+        format!("{:#?}", Value::from_str(r#"{"a": [1]}"#).unwrap()),
+        "{\n    \"a\": [\n        1u64,\n    ],\n}"
+    );
+
+    let big = Value::from(u128::MAX);
+    assert_eq!(format!("{big:?}"), format!("{}u128", u128::MAX));
+    assert_eq!(format!("{:?}", Value::from(2.0)), "2.0f64");
+    assert_eq!(
+        format!("{:?}", Value::Number(Number::Raw("1.10".into()))),
+        "raw(1.10)"
+    );
+    assert_ne!(format!("{value:?}"), value.to_string());
+}
@@ -0,0 +1,66 @@
+use std::str::FromStr;
+
+use crate::{parse_large_array, Error, Value};
+
+fn generate(count: usize) -> String {
+    let items = (0..count)
+        .map(|i| {
+            format!(
+                r#"{{"id": "{i}", "name": "item \"{i}\"", "tags": ["a,b", "[c]"], "ok": {}}}"#,
+                i % 3 == 0
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(",\n  "))
+}
+
+#[test]
+fn test_parse_large_array() {
+    let input = generate(100_000);
+    let parallel = parse_large_array(&input).unwrap();
+    assert_eq!(parallel, Value::from_str(&input).unwrap());
+    assert_eq!(parallel.as_array().unwrap().len(), 100_000);
+}
+
+#[test]
+fn test_parse_large_array_small() {
+    assert_eq!(parse_large_array("[]"), Ok(Value::Array(Vec::new())));
+    assert_eq!(parse_large_array(" [ ] "), Ok(Value::Array(Vec::new())));
+    assert_eq!(
+        parse_large_array(r#"["a"]"#),
+        Ok(Value::Array(vec![Value::String("a".to_owned())]))
+    );
+    assert_eq!(
+        parse_large_array(r#"{"a": [null]}"#),
+        Value::from_str(r#"{"a": [null]}"#)
+    );
+}
+
+#[test]
+fn test_parse_large_array_matches_serial() {
+    for input in [
+        "[1,,2]",
+        "[1,2,]",
+        "[,1]",
+        "[1 2]",
+        "[,]",
+        r#"[0, {"a": 1} "b"]"#,
+        "[true] x",
+    ] {
+        assert_eq!(parse_large_array(input), Value::from_str(input), "{input}");
+    }
+}
+
+#[test]
+fn test_parse_large_array_errors() {
+    assert_eq!(
+        parse_large_array(r#"[null, nul]"#),
+        Err(Error::UnexpectedEnd(10))
+    );
+    assert_eq!(
+        parse_large_array(r#"[true, false"#),
+        Err(Error::UnexpectedEnd(12))
+    );
+    assert_eq!(parse_large_array("[1, }]"), Err(Error::UnexpectedChar(4)));
+    assert_eq!(parse_large_array("[1, x]"), Err(Error::UnexpectedChar(4)));
+}
@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use crate::{Error, Number, Parser, Value};
+
+#[test]
+fn test_parser_multiple_values() {
+    let mut parser = Parser::new(r#"1 ["a"] {"b": null}  "#);
+    let mut values = Vec::new();
+    while !parser.is_finished() {
+        values.push(parser.parse().unwrap());
+    }
+
+    assert_eq!(
+        values,
+        [
+            Value::Number(Number::UInt(1)),
+            Value::from(vec!["a"]),
+            Value::from([("b", Value::Null)])
+        ]
+    );
+    assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(21)));
+}
+
+#[test]
+fn test_parser_position() {
+    let mut parser = Parser::new(r#"{"a": true} trailing"#);
+    assert_eq!(parser.pos(), 0);
+
+    parser.parse().unwrap();
+    assert_eq!(parser.pos(), 11);
+    assert_eq!(parser.remaining(), " trailing");
+    assert!(!parser.is_finished());
+}
+
+#[test]
+fn test_parser_empty() {
+    for i in ["", "   ", "\n"] {
+        let mut parser = Parser::new(i);
+        assert!(parser.is_finished());
+        assert_eq!(parser.parse(), Err(Error::UnexpectedEnd(i.len())));
+    }
+}
+
+#[test]
+fn test_parser_unexpected_char() {
+    let cases = [
+        ("}", 0),
+        ("x", 0),
+        ("@", 0),
+        ("  ]", 2),
+        ("[@]", 1),
+        (r#"{"a": x}"#, 6),
+    ];
+
+    for (input, pos) in cases {
+        assert_eq!(
+            Value::from_str(input),
+            Err(Error::UnexpectedChar(pos)),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_parser_empty_containers() {
+    for i in ["[]", "[ ]", "[\n\t]", " [  ] "] {
+        assert_eq!(Value::from_str(i), Ok(Value::Array(Vec::new())), "{i:?}");
+    }
+    for i in ["{}", "{ }", "{\n}", " {  } "] {
+        assert_eq!(
+            Value::from_str(i),
+            Ok(Value::Object(Default::default())),
+            "{i:?}"
+        );
+    }
+
+    let value = Value::from_str(r#"[ [ ], { }, {"a": [ ]} ]"#).unwrap();
+    assert_eq!(value.to_string(), r#"[[],{},{"a":[]}]"#);
+}
+
+#[test]
+fn test_parser_whitespace_before_close() {
+    let value = Value::from_str("[1 , 2\n]").unwrap();
+    assert_eq!(value.to_string(), "[1,2]");
+
+    let value = Value::from_str("{ \"a\" : 1 }").unwrap();
+    assert_eq!(value.to_string(), r#"{"a":1}"#);
+}
+
+#[test]
+fn test_parser_empty_unterminated() {
+    assert_eq!(Value::from_str("["), Err(Error::UnexpectedEnd(1)));
+    assert_eq!(Value::from_str("{ "), Err(Error::UnexpectedEnd(2)));
+    assert_eq!(Value::from_str("{ 1: 2}"), Err(Error::UnexpectedChar(2)));
+}
+
+#[test]
+fn test_parser_non_json_whitespace() {
+    let cases = [
+        ("[1,\u{000B}2]", 3),
+        ("[1,\u{00A0}2]", 3),
+        ("[1,\u{2028}2]", 3),
+        ("{\"a\":\u{00A0}1}", 5),
+        ("{\"a\"\u{000C}: 1}", 5),
+        ("\u{2028}[]", 0),
+    ];
+
+    for (input, pos) in cases {
+        assert_eq!(
+            Value::from_str(input),
+            Err(Error::UnexpectedChar(pos)),
+            "{input:?}"
+        );
+    }
+
+    let value = Value::from_str(" \t\r\n[\"\u{000B}\u{00A0}\u{2028}\"]\r\n").unwrap();
+    assert_eq!(
+        value.as_array().unwrap()[0],
+        Value::from("\u{000B}\u{00A0}\u{2028}")
+    );
+    assert!(!Parser::new("1 \u{00A0}").is_finished());
+}
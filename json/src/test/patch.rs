@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::{apply_patch, create_patch, Number, PatchError, Value};
+
+fn parse(s: &str) -> Value {
+    Value::from_str(s).unwrap()
+}
+
+fn round_trip(from: &Value, to: &Value) {
+    let patch = create_patch(from, to);
+    assert_eq!(
+        apply_patch(from, &patch).as_ref(),
+        Ok(to),
+        "from: {from}\nto: {to}\npatch: {patch}"
+    );
+}
+
+#[test]
+fn test_apply_patch() {
+    let doc = parse(r#"{"a": {"b": ["c", "d"]}, "e": null}"#);
+    let patch = parse(
+        r#"[
+            {"op": "add", "path": "/a/b/1", "value": "x"},
+            {"op": "add", "path": "/a/b/-", "value": "y"},
+            {"op": "remove", "path": "/e"},
+            {"op": "replace", "path": "/a/f", "value": true},
+            {"op": "test", "path": "/a/b/0", "value": "c"}]"#,
+    );
+    assert_eq!(apply_patch(&doc, &patch), Err(PatchError::PathNotFound(3)));
+
+    let patch = parse(
+        r#"[
+            {"op": "add", "path": "/a/b/1", "value": "x"},
+            {"op": "add", "path": "/a/b/-", "value": "y"},
+            {"op": "remove", "path": "/e"},
+            {"op": "copy", "from": "/a/b", "path": "/g"},
+            {"op": "move", "from": "/a/b/0", "path": "/h"},
+            {"op": "test", "path": "/h", "value": "c"}]"#,
+    );
+    assert_eq!(
+        apply_patch(&doc, &patch),
+        Ok(parse(
+            r#"{"a": {"b": ["x", "d", "y"]}, "g": ["c", "x", "d", "y"], "h": "c"}"#
+        ))
+    );
+}
+
+#[test]
+fn test_apply_patch_errors() {
+    let doc = parse(r#"{"a": ["b"]}"#);
+    let cases = [
+        (r#"{}"#, PatchError::InvalidPatch(None)),
+        (
+            r#"[{"op": "nop", "path": ""}]"#,
+            PatchError::InvalidPatch(Some(0)),
+        ),
+        (
+            r#"[{"op": "add", "path": "/x"}]"#,
+            PatchError::InvalidPatch(Some(0)),
+        ),
+        (
+            r#"[{"op": "remove", "path": "a"}]"#,
+            PatchError::InvalidPatch(Some(0)),
+        ),
+        (
+            r#"[{"op": "remove", "path": "/a/1"}]"#,
+            PatchError::PathNotFound(0),
+        ),
+        (
+            r#"[{"op": "add", "path": "/a/2", "value": null}]"#,
+            PatchError::PathNotFound(0),
+        ),
+        (
+            r#"[{"op": "move", "from": "/a", "path": "/a/0"}]"#,
+            PatchError::PathNotFound(0),
+        ),
+        (
+            r#"[{"op": "test", "path": "/a/0", "value": "c"}]"#,
+            PatchError::TestFailed(0),
+        ),
+    ];
+
+    for (patch, err) in cases {
+        assert_eq!(apply_patch(&doc, &parse(patch)), Err(err), "{patch}");
+    }
+}
+
+#[test]
+fn test_create_patch() {
+    let from = parse(r#"{"a": [1, 2, 3], "b": {"c": "d"}, "e": true}"#);
+    let to = parse(r#"{"a": [1, 5], "b": {"f": "d"}, "g": null}"#);
+
+    assert_eq!(
+        create_patch(&from, &to),
+        parse(
+            r#"[
+                {"op": "replace", "path": "/a/1", "value": 5},
+                {"op": "remove", "path": "/a/2"},
+                {"op": "remove", "path": "/b/c"},
+                {"op": "add", "path": "/b/f", "value": "d"},
+                {"op": "remove", "path": "/e"},
+                {"op": "add", "path": "/g", "value": null}]"#
+        )
+    );
+    round_trip(&from, &to);
+    round_trip(&to, &from);
+    assert_eq!(create_patch(&from, &from), Value::Array(Vec::new()));
+}
+
+#[test]
+fn test_create_patch_hand_written() {
+    let cases = [
+        ("null", r#"{"a": 1}"#),
+        (r#"["a", "b", "c"]"#, r#"["a"]"#),
+        (r#"[]"#, r#"[[], {}, "x"]"#),
+        (r#"{"a/b": {"~c": 1}}"#, r#"{"a/b": {"~c": 2}}"#),
+        (r#"{"a": [{"b": [1]}]}"#, r#"{"a": [{"b": [1, 2]}, true]}"#),
+        (r#"{"a": "b"}"#, r#"["a", "b"]"#),
+    ];
+
+    for (from, to) in cases {
+        round_trip(&parse(from), &parse(to));
+        round_trip(&parse(to), &parse(from));
+    }
+}
+
+/// A tiny deterministic PRNG, so the generated documents are the same every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, max: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % max
+    }
+
+    fn value(&mut self, depth: u8) -> Value {
+        let kind = if depth == 0 {
+            self.next(4)
+        } else {
+            self.next(6)
+        };
+        match kind {
+            0 => Value::Null,
+            1 => Value::Bool(self.next(2) == 0),
+            2 => Value::Number(Number::UInt(self.next(4))),
+            3 => Value::String(["a", "b", "~/"][self.next(3) as usize].to_owned()),
+            4 => Value::Array((0..self.next(4)).map(|_| self.value(depth - 1)).collect()),
+            _ => Value::Object(
+                (0..self.next(4))
+                    .map(|_| {
+                        (
+                            ["a", "b", "c", "d/e"][self.next(4) as usize].to_owned(),
+                            self.value(depth - 1),
+                        )
+                    })
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_create_patch_generated() {
+    let mut rng = Rng(0x5eed);
+    for _ in 0..2_000 {
+        let from = rng.value(4);
+        let to = rng.value(4);
+        round_trip(&from, &to);
+    }
+}
@@ -0,0 +1,88 @@
+use std::str::FromStr;
+
+use crate::{
+    from_query_string, to_query_string, to_query_string_with, ArrayFormat, QueryError, Value,
+};
+
+fn parse(s: &str) -> Value {
+    Value::from_str(s).unwrap()
+}
+
+#[test]
+fn test_to_query_string() {
+    let value = parse(r#"{"q": "rust json", "page": 2, "tags": ["a", "b"]}"#);
+    assert_eq!(
+        to_query_string(&value).unwrap(),
+        "page=2&q=rust%20json&tags=a&tags=b"
+    );
+    assert_eq!(
+        to_query_string_with(&value, ArrayFormat::Brackets).unwrap(),
+        "page=2&q=rust%20json&tags[]=a&tags[]=b"
+    );
+
+    let value = parse(r#"{"filter": {"name": "a&b=c", "tags": []}, "empty": null, "ok": true}"#);
+    assert_eq!(
+        to_query_string(&value).unwrap(),
+        "empty&filter[name]=a%26b%3Dc&ok=true"
+    );
+}
+
+#[test]
+fn test_to_query_string_fail() {
+    assert_eq!(to_query_string(&parse("[1]")), Err(QueryError::NotObject));
+    assert_eq!(
+        to_query_string(&parse(r#"{"a": {"b": [[1]]}}"#)),
+        Err(QueryError::NestedArray("a[b]".to_owned()))
+    );
+}
+
+#[test]
+fn test_from_query_string() {
+    assert_eq!(
+        from_query_string("a[b]=1&c=2&c=3&d[]=4&e"),
+        parse(r#"{"a": {"b": "1"}, "c": ["2", "3"], "d": ["4"], "e": null}"#)
+    );
+    assert_eq!(
+        from_query_string(
+            "user[name]=Ferris&user[langs][]=rust&user[langs][]=c&user[address][city]=Oslo"
+        ),
+        parse(
+            r#"{"user": {"name": "Ferris", "langs": ["rust", "c"], "address": {"city": "Oslo"}}}"#
+        )
+    );
+    assert_eq!(from_query_string(""), Value::Object(Default::default()));
+}
+
+#[test]
+fn test_from_query_string_malformed() {
+    assert_eq!(
+        from_query_string("a[b=1&[c]=2&d]=3&e[f]g=4&bad=%zz%4&plus=a+b"),
+        parse(
+            r#"{"a[b": "1", "[c]": "2", "d]": "3", "e[f]g": "4", "bad": "%zz%4", "plus": "a b"}"#
+        )
+    );
+}
+
+#[test]
+fn test_from_query_string_interop() {
+    // jQuery's `$.param`, which encodes the brackets
+    assert_eq!(
+        from_query_string("ids%5B%5D=1&ids%5B%5D=2&sort%5Bfield%5D=created_at&sort%5Bdir%5D=desc"),
+        parse(r#"{"ids": ["1", "2"], "sort": {"field": "created_at", "dir": "desc"}}"#)
+    );
+    // A browser form submission
+    assert_eq!(
+        from_query_string("q=caf%C3%A9+au+lait&lang=fr&lang=en&submit="),
+        parse(r#"{"q": "café au lait", "lang": ["fr", "en"], "submit": ""}"#)
+    );
+}
+
+#[test]
+fn test_query_string_round_trip() {
+    let value = parse(
+        r#"{"q": "a b&c=d/é", "tags": ["x"], "filter": {"min": "1", "sizes": ["s", "m"], "deep": {"k": "v"}}, "none": null}"#,
+    );
+
+    let query = to_query_string_with(&value, ArrayFormat::Brackets).unwrap();
+    assert_eq!(from_query_string(&query), value);
+}
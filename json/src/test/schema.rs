@@ -0,0 +1,165 @@
+use std::str::FromStr;
+
+use crate::{infer_schema, JsonType, Schema, ValidationErrorKind, Value};
+
+fn matches(schema: &Value, value: &Value) -> bool {
+    value.validate(&Schema::from_value(schema).unwrap()).is_ok()
+}
+
+fn samples() -> Vec<Value> {
+    [
+        r#"{"id": 1, "name": "Ferris", "tags": ["crab", "rust"], "owner": {"name": "a", "age": 30}}"#,
+        r#"{"id": 2, "name": "Corro", "tags": [], "owner": {"name": "b"}, "score": 1.5}"#,
+        r#"{"id": "3", "name": null, "tags": ["x", 5], "owner": {"name": "c", "age": 41}, "score": 2}"#,
+    ]
+    .into_iter()
+    .map(|x| Value::from_str(x).unwrap())
+    .collect()
+}
+
+#[test]
+fn test_infer_schema() {
+    let schema = infer_schema(&samples());
+    let expected = Value::from_str(
+        r#"{
+            "type": "object",
+            "properties": {
+                "id": {"type": ["integer", "string"]},
+                "name": {"type": ["null", "string"]},
+                "owner": {
+                    "type": "object",
+                    "properties": {"age": {"type": "integer"}, "name": {"type": "string"}},
+                    "required": ["name"]},
+                "score": {"type": "number"},
+                "tags": {"type": "array", "items": {"type": ["integer", "string"]}}},
+            "required": ["id", "name", "owner", "tags"]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(schema, expected);
+}
+
+#[test]
+fn test_infer_schema_validates_samples() {
+    let samples = samples();
+    let schema = infer_schema(&samples);
+    for i in &samples {
+        assert!(matches(&schema, i), "{i}");
+    }
+
+    let missing = Value::from_str(r#"{"id": 4, "name": "x", "tags": []}"#).unwrap();
+    let wrong =
+        Value::from_str(r#"{"id": 4, "name": "x", "tags": [true], "owner": {"name": "d"}}"#)
+            .unwrap();
+    assert!(!matches(&schema, &missing));
+    assert!(!matches(&schema, &wrong));
+}
+
+#[test]
+fn test_infer_schema_scalars() {
+    let values = [Value::from(1), Value::from(2.5), Value::Null];
+    assert_eq!(
+        infer_schema(&values),
+        Value::from([("type", vec!["null", "number"])])
+    );
+    assert_eq!(infer_schema(&[]), Value::Object(Default::default()));
+    assert!(matches(&infer_schema(&[]), &Value::from("anything")));
+}
+
+#[test]
+fn test_validate() {
+    let schema = Schema::of(JsonType::Object)
+        .required("name", Schema::of(JsonType::String))
+        .required("port", Schema::of(JsonType::Number))
+        .property(
+            "tags",
+            Schema::of(JsonType::Array).items(Schema::of(JsonType::String)),
+        )
+        .property("extra", Schema::default());
+
+    let value =
+        Value::from_str(r#"{"name": "api", "port": 80, "tags": ["a", "b"], "other": null}"#)
+            .unwrap();
+    assert_eq!(value.validate(&schema), Ok(()));
+}
+
+#[test]
+fn test_validate_errors() {
+    let schema = Schema::of(JsonType::Object)
+        .required("name", Schema::of(JsonType::String))
+        .required("port", Schema::of(JsonType::Number))
+        .property(
+            "tags",
+            Schema::of(JsonType::Array).items(Schema::of(JsonType::String)),
+        );
+
+    let value = Value::from_str(r#"{"name": "api", "tags": ["a", 5]}"#).unwrap();
+    let errors = value.validate(&schema).unwrap_err();
+    assert_eq!(errors.len(), 2);
+
+    assert_eq!(errors[0].path().as_str(), "/port");
+    assert_eq!(errors[0].kind(), &ValidationErrorKind::Missing);
+    assert_eq!(errors[0].to_string(), "missing field at /port");
+
+    assert_eq!(errors[1].path().as_str(), "/tags/1");
+    assert_eq!(
+        errors[1].kind(),
+        &ValidationErrorKind::WrongType {
+            expected: vec![JsonType::String],
+            found: JsonType::Number
+        }
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "expected string, found number at /tags/1"
+    );
+
+    let errors = Value::from(vec![1]).validate(&schema).unwrap_err();
+    assert_eq!(errors[0].to_string(), "expected object, found array at /");
+}
+
+#[test]
+fn test_schema_from_value() {
+    let schema = Value::from_str(
+        r#"{"type": "object", "properties": {"id": {"type": ["integer", "null"]}, "ok": {"type": "boolean"}}, "required": ["id"]}"#,
+    )
+    .unwrap();
+    let schema = Schema::from_value(&schema).unwrap();
+
+    assert_eq!(
+        schema,
+        Schema::of(JsonType::Object)
+            .required(
+                "id",
+                Schema {
+                    types: vec![JsonType::Number, JsonType::Null],
+                    integer: true,
+                    ..Default::default()
+                }
+            )
+            .property("ok", Schema::of(JsonType::Bool))
+    );
+
+    let errors = Value::from_str(r#"{"id": 1.5}"#).unwrap().validate(&schema);
+    assert_eq!(
+        errors.unwrap_err()[0].to_string(),
+        "expected integer at /id"
+    );
+    assert!(Value::from_str(r#"{"id": null}"#)
+        .unwrap()
+        .validate(&schema)
+        .is_ok());
+
+    for i in [
+        r#"{"type": "float"}"#,
+        r#"{"type": 5}"#,
+        r#"{"required": "id"}"#,
+        "[]",
+    ] {
+        assert_eq!(
+            Schema::from_value(&Value::from_str(i).unwrap()),
+            None,
+            "{i}"
+        );
+    }
+}
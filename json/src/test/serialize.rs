@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+use crate::{SerializeOptions, Value};
+
+#[test]
+fn test_to_string_with_sorted() {
+    let value = Value::from_str(r#"{"b": 1, "a": [{"d": null, "c": "x"}], "e": true}"#).unwrap();
+    let out = value.to_string_with(&SerializeOptions::default());
+
+    assert_eq!(out, r#"{"a":[{"c":"x","d":null}],"b":1,"e":true}"#);
+    assert_eq!(out, value.to_string());
+}
+
+#[test]
+fn test_to_string_with_key_order() {
+    let value = Value::from_str(
+        r#"{"name": "Ferris", "id": 1, "tags": [{"value": 2, "id": 3}], "age": 7}"#,
+    )
+    .unwrap();
+    let options = SerializeOptions {
+        sort_keys: false,
+        key_order: vec!["id".to_owned(), "name".to_owned(), "missing".to_owned()],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        value.to_string_with(&options),
+        r#"{"id":1,"name":"Ferris","age":7,"tags":[{"id":3,"value":2}]}"#
+    );
+
+    // Key order is ignored while sorting
+    let options = SerializeOptions {
+        sort_keys: true,
+        ..options
+    };
+    assert_eq!(value.to_string_with(&options), value.to_string());
+}
+
+#[test]
+fn test_to_string_with_precision() {
+    let value = Value::from_str("[3.14159, 2.0, -0.005, 10, -3, 1.5e300]").unwrap();
+    let options = SerializeOptions {
+        precision: Some(2),
+        ..Default::default()
+    };
+
+    let out = value.to_string_with(&options);
+    assert!(out.starts_with("[3.14,2.00,-0.01,10,-3,15"), "{out}");
+    assert!(out.ends_with(".00]"), "{out}");
+
+    let options = SerializeOptions {
+        precision: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(
+        Value::from(vec![0.5, 1.5, 2.75]).to_string_with(&options),
+        "[0,2,3]"
+    );
+}
+
+#[test]
+fn test_to_string_with_shortest_float() {
+    let value = Value::from(vec![0.1, 1.0 / 3.0, 2.5e-8, 2.5]);
+    let out = value.to_string_with(&SerializeOptions::default());
+    assert_eq!(out, "[0.1,0.3333333333333333,0.000000025,2.5]");
+    assert_eq!(out, value.to_string());
+
+    let parsed = Value::from_str(&out).unwrap();
+    assert_eq!(parsed.as_array().unwrap()[1], Value::from(1.0 / 3.0));
+}
+
+#[test]
+fn test_to_string_pretty() {
+    let value = Value::from_str(
+        r#"{"name": "ferris", "ids": [1, 2, 3], "tags": ["a", "b", "c", "d"], "nested": [[1]], "empty": [], "obj": {}}"#,
+    )
+    .unwrap();
+    let options = SerializeOptions {
+        indent: Some(2),
+        inline_array_max_len: 3,
+        ..Default::default()
+    };
+
+    let out = value.to_string_with(&options);
+    let expected = r#"{
+  "empty": [],
+  "ids": [1, 2, 3],
+  "name": "ferris",
+  "nested": [
+    [1]
+  ],
+  "obj": {},
+  "tags": [
+    "a",
+    "b",
+    "c",
+    "d"
+  ]
+}"#;
+    assert_eq!(out, expected);
+    assert_eq!(Value::from_str(&out).unwrap(), value);
+
+    // Without a max, every non-empty array is expanded
+    let options = SerializeOptions {
+        indent: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(
+        Value::from(vec![1, 2]).to_string_with(&options),
+        "[\n 1,\n 2\n]"
+    );
+    assert_eq!(
+        value.to_string_pretty().lines().nth(4),
+        Some(r#"  "nested": ["#)
+    );
+}
@@ -0,0 +1,206 @@
+use std::str::FromStr;
+
+use crate::{extract, minify, validate, Error, Token, Tokenizer, Value};
+
+#[test]
+fn test_tokens() {
+    let mut tokenizer = Tokenizer::new(r#" {"a\"b": [1.5, true]} "#);
+    let mut tokens = Vec::new();
+    while let Some(x) = tokenizer.next_token().unwrap() {
+        tokens.push(x);
+    }
+
+    assert_eq!(
+        tokens,
+        [
+            (1, Token::ObjectStart),
+            (2, Token::String(r#""a\"b""#)),
+            (8, Token::Colon),
+            (10, Token::ArrayStart),
+            (11, Token::Number("1.5")),
+            (14, Token::Comma),
+            (16, Token::Bool(true)),
+            (20, Token::ArrayEnd),
+            (21, Token::ObjectEnd),
+        ]
+    );
+    assert_eq!(tokenizer.pos(), 23);
+}
+
+#[test]
+fn test_skip_value() {
+    let mut tokenizer = Tokenizer::new(r#"[1, {"a": []}] "next""#);
+    tokenizer.skip_value().unwrap();
+    assert_eq!(tokenizer.pos(), 14);
+    assert_eq!(
+        tokenizer.next_token(),
+        Ok(Some((15, Token::String("\"next\""))))
+    );
+}
+
+#[test]
+fn test_minify() {
+    let input = r#"
+    {
+        "name": "half stack",
+        "escapes": "tab\t quote\" slash\/ backslash\\ end",
+        "nums": [1, -2.5, 3.5e2],
+        "nested": {"empty": {}, "list": [[], [null, false]]}}"#;
+
+    let minified = minify(input).unwrap();
+    assert_eq!(
+        minified,
+        r#"{"name":"half stack","escapes":"tab\t quote\" slash\/ backslash\\ end","nums":[1,-2.5,3.5e2],"nested":{"empty":{},"list":[[],[null,false]]}}"#
+    );
+    assert_eq!(
+        Value::from_str(&minified).unwrap(),
+        Value::from_str(input).unwrap()
+    );
+    assert_eq!(minify(" 5 ").unwrap(), "5");
+    assert_eq!(minify("[ 1e5 ]").unwrap(), "[1e5]");
+}
+
+#[test]
+fn test_validate() {
+    let valid = [
+        "null",
+        " [ ] ",
+        "{}",
+        r#"{"a": [1, {"b": "c"}], "d": -0.5e-3}"#,
+        r#""\\""#,
+        "[1e5, -2E3]",
+    ];
+    for i in valid {
+        assert_eq!(validate(i), Ok(()), "{i}");
+    }
+}
+
+#[test]
+fn test_validate_errors() {
+    let cases = [
+        ("", Error::UnexpectedEnd(0)),
+        ("[1 2]", Error::UnexpectedChar(3)),
+        ("[1,]", Error::UnexpectedChar(3)),
+        ("[1,,2]", Error::UnexpectedChar(3)),
+        (r#"{"a" 1}"#, Error::UnexpectedChar(5)),
+        (r#"{"a": 1,}"#, Error::UnexpectedChar(8)),
+        ("{1: 2}", Error::UnexpectedChar(1)),
+        ("[1}", Error::UnexpectedChar(2)),
+        ("[[1]", Error::UnexpectedEnd(4)),
+        ("nul", Error::UnexpectedEnd(3)),
+        ("nulk", Error::UnexpectedChar(3)),
+        ("1 2", Error::UnexpectedChar(2)),
+        ("]", Error::UnexpectedChar(0)),
+        (r#""abc"#, Error::UnexpectedEnd(4)),
+        (r#""a\"#, Error::UnexpectedEnd(3)),
+        (r#""\q""#, Error::InvalidEscape { pos: 1, found: 'q' }),
+    ];
+
+    for (input, error) in cases {
+        assert_eq!(validate(input).unwrap_err(), error, "{input}");
+        assert_eq!(minify(input).unwrap_err(), error, "{input}");
+    }
+
+    assert!(matches!(
+        validate("[1.2.3]"),
+        Err(Error::InvalidNumber { pos: 1, .. })
+    ));
+}
+
+#[test]
+fn test_extract() {
+    let items = (0..1000)
+        .map(|i| {
+            format!(
+                r#"{{"id": {i}, "name": "item {i}", "tags": ["a", {{"b": [{i}]}}], "ok": true}}"#
+            )
+        })
+        .collect::<Vec<_>>();
+    let input = format!(
+        r#"{{"result": {{"count": 1000, "items": [{}]}}, "after": null}}"#,
+        items.join(", ")
+    );
+    let parsed = Value::from_str(&input).unwrap();
+
+    for pointer in [
+        "/result/items/500",
+        "/result/items/500/name",
+        "/result/items/731/tags/1/b/0",
+        "/result/items/999/ok",
+        "/result/count",
+        "/after",
+        "",
+    ] {
+        assert_eq!(
+            extract(&input, pointer).unwrap().as_ref(),
+            parsed.pointer(pointer),
+            "{pointer}"
+        );
+    }
+}
+
+#[test]
+fn test_extract_keys() {
+    let input = r#"{"a/b": 1, "m~n": 2, "q\"uote": 3, "": 4, "x": {"y": [5]}}"#;
+    assert_eq!(extract(input, "/a~1b"), Ok(Some(Value::from(1))));
+    assert_eq!(extract(input, "/m~0n"), Ok(Some(Value::from(2))));
+    assert_eq!(extract(input, "/q\"uote"), Ok(Some(Value::from(3))));
+    assert_eq!(extract(input, "/"), Ok(Some(Value::from(4))));
+    assert_eq!(extract(input, "/x/y/0"), Ok(Some(Value::from(5))));
+}
+
+#[test]
+fn test_extract_duplicate_keys() {
+    let input = r#"{"a": 1, "b": {"c": 2}, "a": [3], "b": {"d": 4}, "e": 5}"#;
+    let parsed = Value::from_str(input).unwrap();
+    for pointer in ["/a", "/a/0", "/b", "/b/c", "/b/d", "/e"] {
+        assert_eq!(
+            extract(input, pointer).unwrap().as_ref(),
+            parsed.pointer(pointer),
+            "{pointer}"
+        );
+    }
+    assert_eq!(extract(input, "/a/0"), Ok(Some(Value::from(3))));
+}
+
+#[test]
+fn test_extract_not_found() {
+    let input = r#"{"a": [1, 2], "b": {}, "c": "text"}"#;
+    let cases = [
+        "/missing", "/a/2", "/a/-", "/a/01", "/a/x", "/b/c", "/c/0", "/a/0/0", "no-slash",
+    ];
+
+    for i in cases {
+        assert_eq!(extract(input, i), Ok(None), "{i}");
+    }
+    assert_eq!(extract("[]", "/0"), Ok(None));
+}
+
+#[test]
+fn test_extract_malformed() {
+    assert_eq!(
+        extract(r#"{"a": [1 2], "b": 3}"#, "/b"),
+        Err(Error::UnexpectedChar(9))
+    );
+    assert_eq!(
+        extract(r#"{"a": 1 "b": 3}"#, "/b"),
+        Err(Error::UnexpectedChar(8))
+    );
+    assert_eq!(
+        extract(r#"{"a": [1, 2"#, "/b"),
+        Err(Error::UnexpectedEnd(11))
+    );
+    assert_eq!(extract(r#"{"a" 1}"#, "/a"), Err(Error::UnexpectedChar(5)));
+    assert_eq!(
+        extract(r#"{"a": tru}"#, "/a"),
+        Err(Error::UnexpectedChar(9))
+    );
+    assert_eq!(extract("", "/a"), Err(Error::UnexpectedEnd(0)));
+
+    // Nothing after the target's object is read, but the rest of it is, to find duplicate keys
+    assert_eq!(extract(r#"[{"a": 1}, ]"#, "/0/a"), Ok(Some(Value::from(1))));
+    assert_eq!(
+        extract(r#"{"a": 1, "b": ]"#, "/a"),
+        Err(Error::UnexpectedChar(14))
+    );
+}
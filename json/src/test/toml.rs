@@ -0,0 +1,178 @@
+use std::str::FromStr;
+
+use crate::{from_toml, to_toml, JsonPointerBuf, Number, TomlError, Value};
+
+const CARGO: &str = r#"# A realistic Cargo manifest
+[package]
+name = "half-stack"
+version = "0.1.0"
+edition = "2021"
+authors = ["Connor Slade <connor@connorcode.com>"]
+description = """
+A multi-line \
+  description with "quotes""""
+keywords = [
+    "json", # Trailing comments are fine
+    'web',
+]
+
+[dependencies]
+afire = "1.2.0"
+serde = { version = "1.0", features = ["derive"], optional = true }
+json.path = "../json"
+
+[features]
+default = []
+mmap = ["memmap2"]
+
+[profile.release]
+opt-level = 3
+lto = true
+debug = false
+
+[[bench]]
+name = "parse_large_array"
+harness = false
+required-features = ["rayon"]
+
+[[bench]]
+name = "other"
+"#;
+
+#[test]
+fn test_from_toml() {
+    let value = from_toml(CARGO).unwrap();
+    assert_eq!(
+        value.pointer("/package/description"),
+        Some(&Value::from(r#"A multi-line description with "quotes""#))
+    );
+    assert_eq!(
+        value.pointer("/dependencies/serde/features/0"),
+        Some(&Value::from("derive"))
+    );
+    assert_eq!(
+        value.pointer("/dependencies/json/path"),
+        Some(&Value::from("../json"))
+    );
+    assert_eq!(
+        value.pointer("/package/keywords/1"),
+        Some(&Value::from("web"))
+    );
+    assert_eq!(
+        value.pointer("/profile/release/opt-level"),
+        Some(&Value::from(3))
+    );
+    assert_eq!(value.pointer("/bench/1/name"), Some(&Value::from("other")));
+    assert_eq!(
+        value.pointer("/features/default"),
+        Some(&Value::Array(Vec::new()))
+    );
+}
+
+#[test]
+fn test_from_toml_scalars() {
+    let value = from_toml(
+        "int = +1_000\nneg = -17\nhex = 0xDEAD_beef\noct = 0o755\nbin = 0b1101\n\
+         float = 6.626e-34\nexp = 5e+22\nfrac = -0.01\ninf = -inf\n\
+         basic = \"tab\\there \\u00E9 \\U0001F980\"\nliteral = 'C:\\Users\\*'\n\
+         multi = '''\nline one\nline two'''\n\
+         odt = 1979-05-27T07:32:00-08:00\nspaced = 1979-05-27 07:32:00.999Z\ndate = 1979-05-27\ntime = 07:32:00\n",
+    )
+    .unwrap();
+
+    let expected = Value::from_str(
+        r#"{"int": 1000, "neg": -17, "hex": 3735928559, "oct": 493, "bin": 13, "basic": "tab\there é 🦀",
+            "literal": "C:\\Users\\*", "multi": "line one\nline two", "odt": "1979-05-27T07:32:00-08:00",
+            "spaced": "1979-05-27 07:32:00.999Z", "date": "1979-05-27", "time": "07:32:00", "float": 1, "exp": 1, "frac": 1, "inf": 1}"#,
+    )
+    .unwrap();
+    let mut expected = expected.as_object().unwrap().clone();
+    expected.insert("float".to_owned(), Value::Number(Number::Float(6.626e-34)));
+    expected.insert("exp".to_owned(), Value::Number(Number::Float(5e22)));
+    expected.insert("frac".to_owned(), Value::Number(Number::Float(-0.01)));
+    expected.insert(
+        "inf".to_owned(),
+        Value::Number(Number::Float(f64::NEG_INFINITY)),
+    );
+
+    assert_eq!(value, Value::Object(expected));
+}
+
+#[test]
+fn test_from_toml_fail() {
+    assert_eq!(from_toml("a = "), Err(TomlError::Syntax(4)));
+    assert_eq!(from_toml("a = 1 b = 2"), Err(TomlError::Syntax(6)));
+    assert_eq!(from_toml("a = \"open"), Err(TomlError::Syntax(9)));
+    assert_eq!(from_toml("a = \"\\x\""), Err(TomlError::Syntax(5)));
+    assert_eq!(from_toml("a = 1.\n"), Err(TomlError::Syntax(4)));
+    assert_eq!(from_toml("a = [1, 2"), Err(TomlError::Syntax(9)));
+    assert_eq!(from_toml("a = 1\na = 2"), Err(TomlError::Redefined(6)));
+    assert_eq!(from_toml("[a]\n[b]\n[a]"), Err(TomlError::Redefined(8)));
+    assert_eq!(from_toml("a = 1\n[a.b]"), Err(TomlError::Redefined(6)));
+    assert_eq!(
+        from_toml("a = { b = 1, b = 2 }"),
+        Err(TomlError::Redefined(13))
+    );
+}
+
+#[test]
+fn test_to_toml() {
+    let value = Value::from_str(
+        r#"{"name": "x", "weird key": "a\"b\n", "nums": [1, 2.0, -3], "mixed": [1, "a", {"b": true}],
+            "server": {"port": 8080, "tls": {"enabled": false}}, "deep": {"only": {"tables": {}}},
+            "users": [{"name": "a", "roles": {"admin": true}}, {"name": "b"}]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        to_toml(&value).unwrap(),
+        r#"mixed = [1, "a", { b = true }]
+name = "x"
+nums = [1, 2.0, -3]
+"weird key" = "a\"b\n"
+
+[deep.only.tables]
+
+[server]
+port = 8080
+
+[server.tls]
+enabled = false
+
+[[users]]
+name = "a"
+
+[users.roles]
+admin = true
+
+[[users]]
+name = "b"
+"#
+    );
+}
+
+#[test]
+fn test_to_toml_fail() {
+    let pointer = |x: &str| {
+        let mut out = JsonPointerBuf::new();
+        x.split('/').for_each(|x| out.push_key(x));
+        out
+    };
+
+    assert_eq!(to_toml(&Value::from(vec![1])), Err(TomlError::NotTable));
+    assert_eq!(
+        to_toml(&Value::from_str(r#"{"a": {"b": null}}"#).unwrap()),
+        Err(TomlError::Null(pointer("a/b")))
+    );
+    assert_eq!(
+        to_toml(&Value::from([("big", u64::MAX)])),
+        Err(TomlError::IntegerOutOfRange(pointer("big")))
+    );
+}
+
+#[test]
+fn test_toml_round_trip() {
+    let value = from_toml(CARGO).unwrap();
+    let toml = to_toml(&value).unwrap();
+    assert_eq!(from_toml(&toml).unwrap(), value, "{toml}");
+}
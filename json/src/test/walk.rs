@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+
+use crate::Value;
+
+#[test]
+fn test_nodes() {
+    let value: Value = r#"{"a": [null, {"b/c": true}], "d": "e"}"#.parse().unwrap();
+    let nodes = value
+        .nodes()
+        .map(|(p, x)| (p.to_string(), x.to_string()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        nodes,
+        [
+            ("", r#"{"a":[null,{"b\/c":true}],"d":"e"}"#),
+            ("/a", r#"[null,{"b\/c":true}]"#),
+            ("/a/0", "null"),
+            ("/a/1", r#"{"b\/c":true}"#),
+            ("/a/1/b~1c", "true"),
+            ("/d", r#""e""#),
+        ]
+        .map(|(p, x)| (p.to_owned(), x.to_owned()))
+    );
+
+    for (pointer, x) in value.nodes() {
+        assert_eq!(value.pointer(&pointer), Some(x));
+    }
+}
+
+#[test]
+fn test_leaves() {
+    let value: Value = r#"[[], {"a": "b"}, [false]]"#.parse().unwrap();
+    let leaves = value.leaves().map(|x| x.0.to_string()).collect::<Vec<_>>();
+    assert_eq!(leaves, ["/1/a", "/2/0"]);
+    assert_eq!(value.nodes().filter(|x| x.1.is_array()).count(), 3);
+}
+
+#[test]
+fn test_nodes_deep() {
+    const DEPTH: usize = 5_000;
+
+    let mut value = Value::Null;
+    for _ in 0..DEPTH {
+        value = Value::Array(vec![value]);
+    }
+
+    assert_eq!(value.nodes().count(), DEPTH + 1);
+    let (pointer, last) = value.nodes().last().unwrap();
+    assert_eq!(last, &Value::Null);
+    assert_eq!(pointer.len(), DEPTH * 2);
+
+    // Avoid a recursive drop of the deep value
+    while let Value::Array(mut a) = value {
+        value = a.pop().unwrap();
+    }
+}
+
+#[test]
+fn test_iter_pointers() {
+    let value: Value = r#"{"a": {"b": [1, 2]}, "c~": null}"#.parse().unwrap();
+    let index = value.iter_pointers().collect::<BTreeMap<_, _>>();
+
+    assert_eq!(
+        index.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["", "/a", "/a/b", "/a/b/0", "/a/b/1", "/c~0"]
+    );
+    assert_eq!(index[""], &value);
+    assert_eq!(index["/a/b/1"], &Value::from(2));
+    assert_eq!(index["/c~0"], &Value::Null);
+}
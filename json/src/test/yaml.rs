@@ -0,0 +1,158 @@
+use std::str::FromStr;
+
+use crate::{from_yaml, to_yaml, Number, Value, YamlError};
+
+fn parse(s: &str) -> Value {
+    Value::from_str(s).unwrap()
+}
+
+#[test]
+fn test_from_yaml() {
+    let yaml = r#"# Server config
+name: half-stack
+version: 1.2
+port: 8080
+debug: false
+owner: ~
+url: http://localhost:8080/path#anchor # A trailing comment
+"quoted key": 'it''s'
+tags: [web, "json", 3]
+limits: {cpu: 2, memory: null}
+servers:
+  - host: a.example.com
+    ports:
+    - 80
+    - 443
+  - host: b.example.com
+  -
+  - - nested
+    - list
+empty:
+"#;
+
+    assert_eq!(
+        from_yaml(yaml).unwrap(),
+        [parse(
+            r#"{"name": "half-stack", "version": 1.2, "port": 8080, "debug": false, "owner": null,
+                "url": "http://localhost:8080/path#anchor", "quoted key": "it's", "tags": ["web", "json", 3],
+                "limits": {"cpu": 2, "memory": null},
+                "servers": [{"host": "a.example.com", "ports": [80, 443]}, {"host": "b.example.com"}, null, ["nested", "list"]],
+                "empty": null}"#
+        )]
+    );
+}
+
+#[test]
+fn test_from_yaml_scalars() {
+    let yaml = "- 0x1F\n- 0o17\n- -12\n- +3.5e2\n- .inf\n- -.inf\n- 1_000\n- \"tab\\there \\u00e9\"\n- TRUE\n- Null\n- 1.\n- a: b: c";
+    let values = from_yaml(yaml).unwrap();
+    let values = values[0].as_array().unwrap();
+
+    assert_eq!(values[0], Value::from(31));
+    assert_eq!(values[1], Value::from(15));
+    assert_eq!(values[2], Value::from(-12));
+    assert_eq!(values[3], Value::Number(Number::Float(350.0)));
+    assert_eq!(values[4], Value::Number(Number::Float(f64::INFINITY)));
+    assert_eq!(values[5], Value::Number(Number::Float(f64::NEG_INFINITY)));
+    assert_eq!(values[6], Value::from("1_000"));
+    assert_eq!(values[7], Value::from("tab\there é"));
+    assert_eq!(values[8], Value::Bool(true));
+    assert_eq!(values[9], Value::Null);
+    assert_eq!(values[10], Value::Number(Number::Float(1.0)));
+    assert_eq!(values[11], parse(r#"{"a": "b: c"}"#));
+}
+
+#[test]
+fn test_from_yaml_multi_document() {
+    let yaml = "---\na: 1\n---\n- b\n--- plain text\n---\n...\n";
+    assert_eq!(
+        from_yaml(yaml).unwrap(),
+        [
+            parse(r#"{"a": 1}"#),
+            parse(r#"["b"]"#),
+            Value::from("plain text"),
+            Value::Null
+        ]
+    );
+
+    assert_eq!(from_yaml("a: 1\n---\nb: 2").unwrap().len(), 2);
+    assert_eq!(from_yaml("").unwrap(), []);
+    assert_eq!(from_yaml("# Just a comment\n").unwrap(), []);
+}
+
+#[test]
+fn test_from_yaml_fail() {
+    assert_eq!(from_yaml("a: &x 1"), Err(YamlError::Unsupported(3)));
+    assert_eq!(from_yaml("a: *x"), Err(YamlError::Unsupported(3)));
+    assert_eq!(from_yaml("a: !!str 1"), Err(YamlError::Unsupported(3)));
+    assert_eq!(from_yaml("a: |\n  text"), Err(YamlError::Unsupported(3)));
+    assert_eq!(from_yaml("? a\n: b"), Err(YamlError::Unsupported(0)));
+    assert_eq!(from_yaml("a: 1\na: 2"), Err(YamlError::DuplicateKey(5)));
+    assert_eq!(from_yaml("a: [1, 2"), Err(YamlError::Syntax(8)));
+    assert_eq!(from_yaml("a: \"open"), Err(YamlError::Syntax(3)));
+    assert_eq!(from_yaml("a: 1\n  b: 2"), Err(YamlError::Syntax(7)));
+    assert_eq!(
+        from_yaml("a:\n    b: 1\n  c: 2"),
+        Err(YamlError::Syntax(14))
+    );
+    assert_eq!(from_yaml("a: 1\n- b"), Err(YamlError::Syntax(5)));
+    assert_eq!(from_yaml("a:\n\tb: 1"), Err(YamlError::Syntax(3)));
+}
+
+#[test]
+fn test_norway_problem() {
+    let value = from_yaml("countries: [GB, NO, SE]\nanswer: no\nswitch: on").unwrap();
+    assert_eq!(
+        value[0],
+        parse(r#"{"countries": ["GB", "NO", "SE"], "answer": "no", "switch": "on"}"#)
+    );
+
+    let yaml = to_yaml(&value[0]);
+    assert_eq!(
+        yaml,
+        "answer: \"no\"\ncountries:\n  - GB\n  - \"NO\"\n  - SE\nswitch: \"on\"\n"
+    );
+    assert_eq!(from_yaml(&yaml).unwrap(), value);
+}
+
+#[test]
+fn test_to_yaml() {
+    let value = parse(
+        r#"{"name": "Ferris", "version": "1.0", "float": 1.5, "key: value": "a # b", "empty": "", "list": [],
+            "map": {}, "nested": [{"a": 1, "b": [true, null]}, [1, 2], "-dash"], "multi": "line\nbreak"}"#,
+    );
+
+    assert_eq!(
+        to_yaml(&value),
+        r#"empty: ""
+float: 1.5
+"key: value": "a # b"
+list: []
+map: {}
+multi: "line\nbreak"
+name: Ferris
+nested:
+  - a: 1
+    b:
+      - true
+      - null
+  - - 1
+    - 2
+  - "-dash"
+version: "1.0"
+"#
+    );
+    assert_eq!(to_yaml(&Value::from("text")), "text\n");
+    assert_eq!(to_yaml(&Value::Number(Number::Float(2.0))), "2.0\n");
+}
+
+#[test]
+fn test_yaml_round_trip() {
+    let value = parse(
+        r##"{"strings": ["yes", "Off", "~", "null", "true", "0x10", "1e3", "12:30", "#hash", "it's", "é", " pad "],
+            "numbers": [0, -5, 2.5, 18446744073709551615], "deep": {"a": {"b": [{"c": {}}]}}}"##,
+    );
+
+    let yaml = to_yaml(&value);
+    assert_eq!(from_yaml(&yaml).unwrap(), [value], "{yaml}");
+}
@@ -0,0 +1,385 @@
+//! A tokenizer for walking JSON text without building [`Value`](crate::Value)s.
+//!
+//! When reading whole values, the tokenizer follows the JSON grammar strictly,
+//! so unlike [`Parser`](crate::Parser), commas are required between elements rather than being treated as whitespace.
+//! Numbers and string escapes are checked with the same rules as the parser.
+
+use std::str::FromStr;
+
+use crate::{parser, pointer, Error, Number, Value};
+
+/// A single token of JSON text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool(bool),
+    /// A number, as written in the input.
+    Number(&'a str),
+    /// A string, as written in the input with its quotes and escapes.
+    String(&'a str),
+    /// `[`
+    ArrayStart,
+    /// `]`
+    ArrayEnd,
+    /// `{`
+    ObjectStart,
+    /// `}`
+    ObjectEnd,
+    /// `:`
+    Colon,
+    /// `,`
+    Comma,
+}
+
+/// Splits JSON text into [`Token`]s, without allocating.
+/// Ex. `{"a": [1]}` => `{`, `"a"`, `:`, `[`, `1`, `]`, `}`
+pub struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+/// What the grammar allows next while reading a value.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    Value,
+    /// A value or the end of an array, right after a `[`.
+    FirstValue,
+    Key,
+    /// A key or the end of an object, right after a `{`.
+    FirstKey,
+    Colon,
+    /// A comma or the end of the current container.
+    CommaOrEnd,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Array,
+    Object,
+}
+
+impl<'a> Token<'a> {
+    /// Gets the token as it was written in the input.
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Token::Null => "null",
+            Token::Bool(true) => "true",
+            Token::Bool(false) => "false",
+            Token::Number(x) | Token::String(x) => x,
+            Token::ArrayStart => "[",
+            Token::ArrayEnd => "]",
+            Token::ObjectStart => "{",
+            Token::ObjectEnd => "}",
+            Token::Colon => ":",
+            Token::Comma => ",",
+        }
+    }
+}
+
+impl<'a> Tokenizer<'a> {
+    /// Creates a tokenizer starting at the beginning of `input`.
+    pub fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    /// Gets the current position in the input, in bytes.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Reads the next token, skipping any whitespace before it.
+    /// Returns the position the token starts at with the token, or `None` at the end of the input.
+    pub fn next_token(&mut self) -> Result<Option<(usize, Token<'a>)>, Error> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let chr = match self.input.as_bytes().get(start) {
+            Some(x) => *x,
+            None => return Ok(None),
+        };
+
+        let token = match chr {
+            b'[' | b']' | b'{' | b'}' | b':' | b',' => {
+                self.pos += 1;
+                match chr {
+                    b'[' => Token::ArrayStart,
+                    b']' => Token::ArrayEnd,
+                    b'{' => Token::ObjectStart,
+                    b'}' => Token::ObjectEnd,
+                    b':' => Token::Colon,
+                    _ => Token::Comma,
+                }
+            }
+            b'n' => self.literal("null", Token::Null)?,
+            b't' => self.literal("true", Token::Bool(true))?,
+            b'f' => self.literal("false", Token::Bool(false))?,
+            b'"' => Token::String(self.string()?),
+            b'0'..=b'9' | b'-' | b'.' => Token::Number(self.number()?),
+            _ => return Err(Error::UnexpectedChar(start)),
+        };
+
+        Ok(Some((start, token)))
+    }
+
+    /// Reads past one complete value, checking that it is well-formed.
+    pub fn skip_value(&mut self) -> Result<(), Error> {
+        self.read_value(|_| {})
+    }
+
+    /// Reads one complete value, passing each of its tokens to `f`.
+    /// Only the stack of open containers is kept, so memory use depends on the nesting depth and not the size of the value.
+    pub(crate) fn read_value(&mut self, mut f: impl FnMut(Token<'a>)) -> Result<(), Error> {
+        let mut stack = Vec::new();
+        let mut expect = Expect::Value;
+
+        loop {
+            let (pos, token) = match self.next_token()? {
+                Some(x) => x,
+                None => return Err(Error::UnexpectedEnd(self.pos)),
+            };
+
+            expect = match (expect, token, stack.last()) {
+                (Expect::Value | Expect::FirstValue, Token::ArrayStart, _) => {
+                    stack.push(Container::Array);
+                    Expect::FirstValue
+                }
+                (Expect::Value | Expect::FirstValue, Token::ObjectStart, _) => {
+                    stack.push(Container::Object);
+                    Expect::FirstKey
+                }
+                (
+                    Expect::Value | Expect::FirstValue,
+                    Token::Null | Token::Bool(_) | Token::Number(_) | Token::String(_),
+                    _,
+                ) => Expect::CommaOrEnd,
+                (Expect::Key | Expect::FirstKey, Token::String(_), _) => Expect::Colon,
+                (Expect::Colon, Token::Colon, _) => Expect::Value,
+                (Expect::CommaOrEnd, Token::Comma, Some(Container::Array)) => Expect::Value,
+                (Expect::CommaOrEnd, Token::Comma, Some(Container::Object)) => Expect::Key,
+                (
+                    Expect::FirstValue | Expect::CommaOrEnd,
+                    Token::ArrayEnd,
+                    Some(Container::Array),
+                )
+                | (
+                    Expect::FirstKey | Expect::CommaOrEnd,
+                    Token::ObjectEnd,
+                    Some(Container::Object),
+                ) => {
+                    stack.pop();
+                    Expect::CommaOrEnd
+                }
+                _ => return Err(Error::UnexpectedChar(pos)),
+            };
+
+            f(token);
+            if expect == Expect::CommaOrEnd && stack.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Moves into the current value to just before its child `token`, a JSON Pointer reference token.
+    /// Siblings before the child are skipped over with [`Tokenizer::skip_value`].
+    /// Objects are read to the end, to find the last of any duplicate keys.
+    /// Returns `false` if the value is not a container or has no such child.
+    fn find_child(&mut self, token: &str) -> Result<bool, Error> {
+        let (pos, start) = self.require_token()?;
+
+        // Objects are searched by key, so the index is unused for them
+        let (end, index) = match start {
+            Token::ArrayStart => match pointer::index(token) {
+                Some(x) => (Token::ArrayEnd, x),
+                None => return Ok(false),
+            },
+            Token::ObjectStart => (Token::ObjectEnd, 0),
+            Token::Null | Token::Bool(_) | Token::Number(_) | Token::String(_) => return Ok(false),
+            _ => return Err(Error::UnexpectedChar(pos)),
+        };
+
+        if self.peek()? == Some(end) {
+            self.next_token()?;
+            return Ok(false);
+        }
+
+        let mut i = 0;
+        // Where the value of the last matching key starts, as the last duplicate wins like in `Value::from_str`
+        let mut last = None;
+        loop {
+            let found = match start {
+                Token::ObjectStart => {
+                    let (key_pos, key) = match self.require_token()? {
+                        (pos, Token::String(x)) => (pos + 1, &x[1..x.len() - 1]),
+                        (pos, _) => return Err(Error::UnexpectedChar(pos)),
+                    };
+                    match self.require_token()? {
+                        (_, Token::Colon) => {}
+                        (pos, _) => return Err(Error::UnexpectedChar(pos)),
+                    }
+
+                    match key.contains('\\') {
+                        true => parser::unescape(key, key_pos)? == token,
+                        false => key == token,
+                    }
+                }
+                _ => i == index,
+            };
+            match (found, start) {
+                (true, Token::ObjectStart) => last = Some(self.pos),
+                (true, _) => return Ok(true),
+                (false, _) => {}
+            }
+
+            self.skip_value()?;
+            match self.require_token()? {
+                (_, Token::Comma) => i += 1,
+                (_, x) if x == end => break,
+                (pos, _) => return Err(Error::UnexpectedChar(pos)),
+            }
+        }
+
+        match last {
+            Some(pos) => {
+                self.pos = pos;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Like [`Tokenizer::next_token`], but fails with [`Error::UnexpectedEnd`] at the end of the input.
+    fn require_token(&mut self) -> Result<(usize, Token<'a>), Error> {
+        match self.next_token()? {
+            Some(x) => Ok(x),
+            None => Err(Error::UnexpectedEnd(self.pos)),
+        }
+    }
+
+    /// Reads the next token without moving past it.
+    fn peek(&mut self) -> Result<Option<Token<'a>>, Error> {
+        let pos = self.pos;
+        let token = self.next_token()?;
+        self.pos = pos;
+        Ok(token.map(|x| x.1))
+    }
+
+    /// Checks that there is nothing but whitespace left in the input.
+    fn finish(&mut self) -> Result<(), Error> {
+        self.skip_whitespace();
+        match self.pos < self.input.len() {
+            true => Err(Error::UnexpectedChar(self.pos)),
+            false => Ok(()),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let bytes = self.input.as_bytes();
+        while self.pos < bytes.len() && matches!(bytes[self.pos], b' ' | b'\t' | b'\n' | b'\r') {
+            self.pos += 1;
+        }
+    }
+
+    fn literal(&mut self, literal: &str, token: Token<'a>) -> Result<Token<'a>, Error> {
+        let bytes = self.input.as_bytes();
+        for (i, chr) in literal.bytes().enumerate() {
+            match bytes.get(self.pos + i) {
+                None => return Err(Error::UnexpectedEnd(bytes.len())),
+                Some(x) if *x != chr => return Err(Error::UnexpectedChar(self.pos + i)),
+                _ => {}
+            }
+        }
+
+        self.pos += literal.len();
+        Ok(token)
+    }
+
+    fn number(&mut self) -> Result<&'a str, Error> {
+        // Scans the same characters as the parser, so `123d` is one invalid number
+        let bytes = self.input.as_bytes();
+        let start = self.pos;
+        while self.pos < bytes.len()
+            && (bytes[self.pos].is_ascii_alphanumeric()
+                || matches!(bytes[self.pos], b'-' | b'+' | b'.'))
+        {
+            self.pos += 1;
+        }
+
+        let num = &self.input[start..self.pos];
+        Number::from_str(num).map_err(|error| Error::InvalidNumber { pos: start, error })?;
+        Ok(num)
+    }
+
+    fn string(&mut self) -> Result<&'a str, Error> {
+        let bytes = self.input.as_bytes();
+        let start = self.pos;
+        self.pos += 1;
+
+        while self.pos < bytes.len() {
+            match bytes[self.pos] {
+                b'"' => {
+                    self.pos += 1;
+                    return Ok(&self.input[start..self.pos]);
+                }
+                b'\\' => match bytes.get(self.pos + 1) {
+                    Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => self.pos += 2,
+                    Some(_) => {
+                        return Err(Error::InvalidEscape {
+                            pos: self.pos,
+                            found: self.input[self.pos + 1..].chars().next().unwrap(),
+                        })
+                    }
+                    None => break,
+                },
+                _ => self.pos += 1,
+            }
+        }
+
+        Err(Error::UnexpectedEnd(bytes.len()))
+    }
+}
+
+/// Checks that `input` is a single well-formed JSON value, without building it.
+/// Runs in linear time, only using memory for the stack of open arrays and objects.
+pub fn validate(input: &str) -> Result<(), Error> {
+    let mut tokenizer = Tokenizer::new(input);
+    tokenizer.skip_value()?;
+    tokenizer.finish()
+}
+
+/// Removes all insignificant whitespace from `input`, without building it.
+/// Strings are copied exactly as written, including their escapes.
+/// Ex. `{ "a b": [1, 2] }` => `{"a b":[1,2]}`
+pub fn minify(input: &str) -> Result<String, Error> {
+    let mut tokenizer = Tokenizer::new(input);
+    let mut out = String::with_capacity(input.len());
+    tokenizer.read_value(|x| out.push_str(x.as_str()))?;
+    tokenizer.finish()?;
+    Ok(out)
+}
+
+/// Gets the value at a [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer in `input`, without parsing the rest of the document.
+/// Ex. `/result/items/0/id`
+///
+/// Values before the target are skipped over without being built, and nothing after it is read,
+/// so errors later in the document are not found.
+/// The exception is the rest of each object on the way to the target, which is skipped over to find the last of any duplicate keys,
+/// matching [`Value::from_str`](std::str::FromStr::from_str).
+/// Returns `None` if the pointer is malformed or nothing is at that location, like [`Value::pointer`].
+pub fn extract(input: &str, pointer: &str) -> Result<Option<Value>, Error> {
+    let tokens = match pointer::tokens(pointer) {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+
+    let mut tokenizer = Tokenizer::new(input);
+    for token in &tokens {
+        if !tokenizer.find_child(token)? {
+            return Ok(None);
+        }
+    }
+
+    let mut out = String::new();
+    tokenizer.read_value(|x| out.push_str(x.as_str()))?;
+    Value::from_str(&out).map(Some)
+}
@@ -0,0 +1,591 @@
+//! Converting between [`Value`]s and [TOML](https://toml.io/en/v1.0.0) documents.
+//!
+//! This is a small native implementation covering tables, arrays of tables, inline tables,
+//! strings, integers, floats, booleans and dotted keys.
+//! TOML datetimes have no JSON equivalent, so they are read as strings.
+
+use std::collections::BTreeMap;
+
+use crate::{JsonPointerBuf, Number, Value};
+
+type Table = BTreeMap<String, Value>;
+
+/// Errors that can occur while converting to or from TOML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TomlError {
+    /// The input isn't valid TOML.
+    /// Contains the position of the problem.
+    Syntax(usize),
+    /// A key or table was defined more than once.
+    /// Contains the position of the second definition.
+    Redefined(usize),
+    /// The value isn't an object, so it can't be written as a TOML document.
+    NotTable,
+    /// A null was found, which TOML has no way to write.
+    /// Contains the pointer to the null.
+    Null(JsonPointerBuf),
+    /// An integer is too big for TOML, which only allows 64-bit signed integers.
+    /// Contains the pointer to the integer.
+    IntegerOutOfRange(JsonPointerBuf),
+}
+
+/// Parses a TOML document into a [`Value::Object`].
+/// Ex. `[package]\nname = "json"` => `{"package": {"name": "json"}}`
+///
+/// Dates and times are kept as strings, with a space between the date and time left as it is.
+/// Parsing is a little more lenient than the spec, so some invalid documents (like integers with leading zeros) are accepted.
+pub fn from_toml(input: &str) -> Result<Value, TomlError> {
+    let mut parser = Parser { input, pos: 0 };
+    parser.document().map(Value::Object)
+}
+
+/// Converts an object into a TOML document.
+/// Objects are written as tables and non-empty arrays of objects as arrays of tables,
+/// except inside other arrays, where inline tables are used.
+///
+/// Fails with [`TomlError::NotTable`] if the value isn't an object,
+/// and with [`TomlError::Null`] or [`TomlError::IntegerOutOfRange`] for values TOML can't hold.
+pub fn to_toml(value: &Value) -> Result<String, TomlError> {
+    let table = value.as_object().ok_or(TomlError::NotTable)?;
+    let mut out = String::new();
+    write_table(&mut out, &mut Vec::new(), &JsonPointerBuf::new(), table)?;
+    Ok(out)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn document(&mut self) -> Result<Table, TomlError> {
+        let mut root = Table::new();
+        let mut table = Vec::new();
+        let mut defined = Vec::<Vec<String>>::new();
+
+        loop {
+            self.skip_blank();
+            if self.pos == self.input.len() {
+                break;
+            }
+
+            let start = self.pos;
+            if self.eat("[[") {
+                let path = self.header("]]")?;
+                let (last, parents) = path.split_last().unwrap();
+                let parent = resolve(&mut root, parents, start)?;
+                match parent
+                    .entry(last.to_owned())
+                    .or_insert(Value::Array(Vec::new()))
+                {
+                    Value::Array(a) => a.push(Value::Object(Table::new())),
+                    _ => return Err(TomlError::Redefined(start)),
+                }
+
+                // Each new table in the array can define its sub-tables again
+                defined.retain(|x| !x.starts_with(&path));
+                table = path;
+            } else if self.eat("[") {
+                let path = self.header("]")?;
+                if defined.contains(&path) {
+                    return Err(TomlError::Redefined(start));
+                }
+
+                resolve(&mut root, &path, start)?;
+                defined.push(path.clone());
+                table = path;
+            } else {
+                let (keys, value) = self.key_value()?;
+                insert(resolve(&mut root, &table, start)?, keys, value, start)?;
+            }
+
+            self.end_of_line()?;
+        }
+
+        Ok(root)
+    }
+
+    fn header(&mut self, close: &str) -> Result<Vec<String>, TomlError> {
+        let keys = self.keys()?;
+        self.skip_spaces();
+        self.expect(close)?;
+        Ok(keys)
+    }
+
+    fn key_value(&mut self) -> Result<(Vec<String>, Value), TomlError> {
+        let keys = self.keys()?;
+        self.skip_spaces();
+        self.expect("=")?;
+        self.skip_spaces();
+        Ok((keys, self.value()?))
+    }
+
+    /// Parses a dotted key, like `a."b.c".d`.
+    fn keys(&mut self) -> Result<Vec<String>, TomlError> {
+        let mut keys = Vec::new();
+        loop {
+            self.skip_spaces();
+            keys.push(self.key()?);
+            self.skip_spaces();
+            if !self.eat(".") {
+                return Ok(keys);
+            }
+        }
+    }
+
+    fn key(&mut self) -> Result<String, TomlError> {
+        if matches!(self.peek(), Some('"' | '\'')) {
+            return self.string();
+        }
+
+        let len = self
+            .rest()
+            .find(|x: char| !is_bare(x))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error());
+        }
+
+        let key = self.rest()[..len].to_owned();
+        self.pos += len;
+        Ok(key)
+    }
+
+    fn value(&mut self) -> Result<Value, TomlError> {
+        match self.peek().ok_or_else(|| self.error())? {
+            '"' | '\'' => self.string().map(Value::String),
+            '[' => self.array(),
+            '{' => self.inline_table(),
+            _ if self.eat("true") => Ok(Value::Bool(true)),
+            _ if self.eat("false") => Ok(Value::Bool(false)),
+            _ => self.scalar(),
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, TomlError> {
+        self.expect("[")?;
+        let mut out = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.eat("]") {
+                break;
+            }
+
+            out.push(self.value()?);
+            self.skip_blank();
+            if !self.eat(",") {
+                self.expect("]")?;
+                break;
+            }
+        }
+
+        Ok(Value::Array(out))
+    }
+
+    fn inline_table(&mut self) -> Result<Value, TomlError> {
+        self.expect("{")?;
+        let mut out = Table::new();
+        self.skip_spaces();
+        if self.eat("}") {
+            return Ok(Value::Object(out));
+        }
+
+        loop {
+            self.skip_spaces();
+            let start = self.pos;
+            let (keys, value) = self.key_value()?;
+            insert(&mut out, keys, value, start)?;
+            self.skip_spaces();
+            if !self.eat(",") {
+                self.expect("}")?;
+                return Ok(Value::Object(out));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, TomlError> {
+        let (delimiter, multiline) = match () {
+            _ if self.eat(r#"""""#) => ('"', true),
+            _ if self.eat("'''") => ('\'', true),
+            _ if self.eat("\"") => ('"', false),
+            _ if self.eat("'") => ('\'', false),
+            _ => return Err(self.error()),
+        };
+
+        // A newline right after the opening delimiter isn't part of the string
+        if multiline && !self.eat("\n") {
+            self.eat("\r\n");
+        }
+
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            let chr = self.next_char()?;
+            match chr {
+                x if x == delimiter && !multiline => return Ok(out),
+                x if x == delimiter && self.rest().starts_with(&x.to_string().repeat(2)) => {
+                    // Up to two quotes can come right before the closing delimiter
+                    let quotes = self.rest().chars().take_while(|&c| c == x).count().min(4);
+                    self.pos += quotes;
+                    (0..quotes - 2).for_each(|_| out.push(x));
+                    return Ok(out);
+                }
+                '\\' if delimiter == '"' => {
+                    if multiline && self.line_ending_backslash() {
+                        continue;
+                    }
+                    out.push(self.escape(start)?);
+                }
+                '\n' if !multiline => return Err(TomlError::Syntax(start)),
+                x => out.push(x),
+            }
+        }
+    }
+
+    /// Skips over a backslash at the end of a line and the whitespace after it, which is trimmed in multi-line strings.
+    fn line_ending_backslash(&mut self) -> bool {
+        let rest = self.rest().trim_start_matches([' ', '\t']);
+        if !rest.starts_with('\n') && !rest.starts_with("\r\n") {
+            return false;
+        }
+
+        let rest = rest.trim_start_matches([' ', '\t', '\r', '\n']);
+        self.pos = self.input.len() - rest.len();
+        true
+    }
+
+    /// Parses the escape after a `\\` at `start`.
+    fn escape(&mut self, start: usize) -> Result<char, TomlError> {
+        let len = match self.next_char()? {
+            'b' => return Ok('\x08'),
+            't' => return Ok('\t'),
+            'n' => return Ok('\n'),
+            'f' => return Ok('\x0C'),
+            'r' => return Ok('\r'),
+            '"' => return Ok('"'),
+            '\\' => return Ok('\\'),
+            'u' => 4,
+            'U' => 8,
+            _ => return Err(TomlError::Syntax(start)),
+        };
+
+        let hex = self.rest().get(..len).ok_or(TomlError::Syntax(start))?;
+        let chr = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.bytes().all(|x| x.is_ascii_hexdigit()))
+            .and_then(char::from_u32)
+            .ok_or(TomlError::Syntax(start))?;
+        self.pos += len;
+        Ok(chr)
+    }
+
+    /// Parses a number or a date and time.
+    fn scalar(&mut self) -> Result<Value, TomlError> {
+        let start = self.pos;
+        let token = |s: &str| {
+            s.find(|x: char| !(is_bare(x) || matches!(x, '+' | '.' | ':')))
+                .unwrap_or(s.len())
+        };
+
+        let mut len = token(self.rest());
+        let text = self.rest()[..len].to_owned();
+        if is_datetime(&text) {
+            // The date and time can be separated with a space instead of a `T`
+            let rest = &self.rest()[len..];
+            if len == 10 && rest.starts_with(' ') && is_datetime(&rest[1..]) {
+                len += 1 + token(&rest[1..]);
+            }
+
+            let text = self.rest()[..len].to_owned();
+            self.pos += len;
+            return Ok(Value::String(text));
+        }
+
+        self.pos += len;
+        number(&text).ok_or(TomlError::Syntax(start))
+    }
+
+    /// Skips spaces, newlines and comments.
+    fn skip_blank(&mut self) {
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some('#') => self.skip_comment(),
+                Some('\n') => self.pos += 1,
+                Some('\r') if self.eat("\r\n") => {}
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        let rest = self.rest();
+        let line = rest.find('\n').map_or(rest, |x| &rest[..x]);
+        self.pos += line.strip_suffix('\r').unwrap_or(line).len();
+    }
+
+    /// Requires a newline or the end of the input, allowing spaces and a comment before it.
+    fn end_of_line(&mut self) -> Result<(), TomlError> {
+        self.skip_spaces();
+        if self.peek() == Some('#') {
+            self.skip_comment();
+        }
+
+        match self.pos == self.input.len() || self.eat("\n") || self.eat("\r\n") {
+            true => Ok(()),
+            false => Err(self.error()),
+        }
+    }
+
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next_char(&mut self) -> Result<char, TomlError> {
+        let chr = self.peek().ok_or_else(|| self.error())?;
+        self.pos += chr.len_utf8();
+        Ok(chr)
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        let found = self.rest().starts_with(s);
+        if found {
+            self.pos += s.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), TomlError> {
+        match self.eat(s) {
+            true => Ok(()),
+            false => Err(self.error()),
+        }
+    }
+
+    fn error(&self) -> TomlError {
+        TomlError::Syntax(self.pos)
+    }
+}
+
+/// Gets the table at `path`, creating any tables that don't exist yet.
+/// Arrays of tables refer to their last table.
+fn resolve<'a>(
+    mut table: &'a mut Table,
+    path: &[String],
+    pos: usize,
+) -> Result<&'a mut Table, TomlError> {
+    for key in path {
+        let value = match table
+            .entry(key.to_owned())
+            .or_insert(Value::Object(Table::new()))
+        {
+            Value::Array(a) => a.last_mut().ok_or(TomlError::Redefined(pos))?,
+            x => x,
+        };
+        table = value.as_mut_object().ok_or(TomlError::Redefined(pos))?;
+    }
+
+    Ok(table)
+}
+
+/// Inserts a value at a dotted key, creating the tables leading up to it.
+fn insert(table: &mut Table, keys: Vec<String>, value: Value, pos: usize) -> Result<(), TomlError> {
+    let (last, parents) = keys.split_last().unwrap();
+    let table = resolve(table, parents, pos)?;
+    if table.contains_key(last) {
+        return Err(TomlError::Redefined(pos));
+    }
+
+    table.insert(last.to_owned(), value);
+    Ok(())
+}
+
+fn number(text: &str) -> Option<Value> {
+    let text = text.replace('_', "");
+    let float = match text.trim_start_matches('+') {
+        "inf" => Some(f64::INFINITY),
+        "-inf" => Some(f64::NEG_INFINITY),
+        "nan" | "-nan" => Some(f64::NAN),
+        _ => None,
+    };
+    if let Some(x) = float {
+        return Some(Value::Number(Number::Float(x)));
+    }
+
+    for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+        if let Some(digits) = text.strip_prefix(prefix) {
+            return i64::from_str_radix(digits, radix).ok().map(Value::from);
+        }
+    }
+
+    // Rust also accepts things like `1.` and `.5`, which TOML doesn't
+    let digits = text.trim_start_matches(['+', '-']);
+    if !digits.starts_with(|x: char| x.is_ascii_digit())
+        || text.contains(".e")
+        || text.ends_with('.')
+    {
+        return None;
+    }
+
+    if text.contains(['.', 'e', 'E']) {
+        return text.parse().ok().map(|x| Value::Number(Number::Float(x)));
+    }
+
+    text.parse::<i64>().ok().map(Value::from)
+}
+
+fn is_bare(chr: char) -> bool {
+    chr.is_ascii_alphanumeric() || matches!(chr, '_' | '-')
+}
+
+/// Checks if a token looks like a date (`1979-05-27`) or a time (`07:32:00`).
+fn is_datetime(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let digits = |range: std::ops::Range<usize>| bytes[range].iter().all(u8::is_ascii_digit);
+
+    (bytes.len() >= 10 && bytes[4] == b'-' && bytes[7] == b'-' && digits(0..4))
+        || (bytes.len() >= 8 && bytes[2] == b':' && bytes[5] == b':' && digits(0..2))
+}
+
+fn write_table(
+    out: &mut String,
+    path: &mut Vec<String>,
+    pointer: &JsonPointerBuf,
+    table: &Table,
+) -> Result<(), TomlError> {
+    // Plain values have to come before any sub-tables, or they would end up in the last sub-table
+    for (k, v) in table.iter().filter(|x| !is_table(x.1)) {
+        let mut pointer = pointer.clone();
+        pointer.push_key(k);
+        out.push_str(&format!("{} = {}\n", key(k), inline(v, &pointer)?));
+    }
+
+    for (k, v) in table.iter().filter(|x| is_table(x.1)) {
+        let mut pointer = pointer.clone();
+        pointer.push_key(k);
+        path.push(key(k));
+
+        match v {
+            Value::Object(o) => {
+                // Tables holding only other tables don't need their own header
+                if o.is_empty() || o.values().any(|x| !is_table(x)) {
+                    header(out, &format!("[{}]", path.join(".")));
+                }
+                write_table(out, path, &pointer, o)?;
+            }
+            Value::Array(a) => {
+                for (i, x) in a.iter().enumerate() {
+                    let mut pointer = pointer.clone();
+                    pointer.push_index(i);
+                    header(out, &format!("[[{}]]", path.join(".")));
+                    write_table(out, path, &pointer, x.as_object().unwrap())?;
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        path.pop();
+    }
+
+    Ok(())
+}
+
+/// Checks if a value is written with a table header, which is the case for objects and non-empty arrays of objects.
+fn is_table(value: &Value) -> bool {
+    match value {
+        Value::Object(_) => true,
+        Value::Array(a) => !a.is_empty() && a.iter().all(Value::is_object),
+        _ => false,
+    }
+}
+
+fn header(out: &mut String, header: &str) {
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(header);
+    out.push('\n');
+}
+
+/// Writes a value on a single line, using inline tables for objects.
+fn inline(value: &Value, pointer: &JsonPointerBuf) -> Result<String, TomlError> {
+    Ok(match value {
+        Value::Null => return Err(TomlError::Null(pointer.clone())),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(Number::UInt(x)) if i64::try_from(*x).is_err() => {
+            return Err(TomlError::IntegerOutOfRange(pointer.clone()))
+        }
+        Value::Number(Number::UInt128(x)) if i64::try_from(*x).is_err() => {
+            return Err(TomlError::IntegerOutOfRange(pointer.clone()))
+        }
+        Value::Number(Number::Int128(x)) if i64::try_from(*x).is_err() => {
+            return Err(TomlError::IntegerOutOfRange(pointer.clone()))
+        }
+        Value::Number(Number::Float(x)) if x.is_nan() => "nan".to_owned(),
+        Value::Number(Number::Float(x)) if x.is_infinite() => {
+            (if *x > 0.0 { "inf" } else { "-inf" }).to_owned()
+        }
+        // Debug formatting always includes a `.` or exponent, so the value is read back as a float
+        Value::Number(Number::Float(x)) => format!("{x:?}"),
+        Value::Number(x) => x.to_string(),
+        Value::String(s) => quote(s),
+        Value::Array(a) => {
+            let items = a.iter().enumerate().map(|(i, x)| {
+                let mut pointer = pointer.clone();
+                pointer.push_index(i);
+                inline(x, &pointer)
+            });
+            format!("[{}]", items.collect::<Result<Vec<_>, _>>()?.join(", "))
+        }
+        Value::Object(o) if o.is_empty() => "{}".to_owned(),
+        Value::Object(o) => {
+            let entries = o.iter().map(|(k, x)| {
+                let mut pointer = pointer.clone();
+                pointer.push_key(k);
+                Ok(format!("{} = {}", key(k), inline(x, &pointer)?))
+            });
+            format!(
+                "{{ {} }}",
+                entries.collect::<Result<Vec<_>, _>>()?.join(", ")
+            )
+        }
+    })
+}
+
+/// Writes a key bare if it can be, otherwise quoted.
+fn key(key: &str) -> String {
+    match !key.is_empty() && key.chars().all(is_bare) {
+        true => key.to_owned(),
+        false => quote(key),
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::from('"');
+    for i in s.chars() {
+        match i {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\x08' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\x0C' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            x if x.is_control() => out.push_str(&format!("\\u{:04X}", x as u32)),
+            x => out.push(x),
+        }
+    }
+
+    out.push('"');
+    out
+}
@@ -1,16 +1,35 @@
 use std::{
+    borrow::Cow,
+    cmp::Ordering,
     collections::BTreeMap,
     fmt::{self, Display},
-    str::FromStr,
+    fs,
+    io::Read,
+    path::Path,
+    str::{self, FromStr},
 };
 
-use crate::{parser::Parser, Error, Number};
+use crate::{
+    map::{MapKind, Ordered},
+    parser::{ParseOptions, Parser},
+    path::{JsonPath, PathSegment},
+    pointer,
+    serialize::{self, SerializeOptions},
+    Error, Number,
+};
+
+/// A JSON element, using a [`BTreeMap`] for objects.
+/// See [`GenericValue`] for the variants.
+/// Defaults to [`Value::Null`].
+pub type Value = GenericValue<Ordered>;
 
-/// A JSON element.
+/// A JSON element, generic over the map used to store objects.
 /// Can be a null, bool, number, string, array or object.
-#[derive(Debug, PartialEq, Eq, Hash)]
-pub enum Value {
+/// Usually used through [`Value`], or [`HashValue`](crate::HashValue) for unordered objects.
+#[derive(Default)]
+pub enum GenericValue<K: MapKind> {
     /// A null value.
+    #[default]
     Null,
     /// A boolean value.
     /// `true` or `false`.
@@ -22,10 +41,10 @@ pub enum Value {
     String(String),
     /// An array value.
     /// Contains a vector of `Value`s.
-    Array(Vec<Value>),
+    Array(Vec<GenericValue<K>>),
     /// An object value.
-    /// Contains a map of `String`s to `Value`s.
-    Object(BTreeMap<String, Value>),
+    /// Contains a map of keys to `Value`s, both chosen by the [`MapKind`].
+    Object(K::Map),
 }
 
 macro_rules! impl_is {
@@ -60,7 +79,95 @@ macro_rules! impl_as {
     };
 }
 
+macro_rules! impl_from {
+    ($($type:ty),* => |$x:ident| $body:expr) => {
+        $(
+            impl From<$type> for Value {
+                #[allow(clippy::unnecessary_cast)]
+                fn from($x: $type) -> Self {
+                    $body
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(bool => |x| Value::Bool(x));
+impl_from!(String => |x| Value::String(x));
+impl_from!(&str => |x| Value::String(x.to_owned()));
+impl_from!(Number => |x| Value::Number(x));
+impl_from!(u8, u16, u32, u64, usize => |x| Value::Number(Number::UInt(x as u64)));
+impl_from!(f32, f64 => |x| Value::Number(Number::Float(x as f64)));
+impl_from!(BTreeMap<String, Value> => |x| Value::Object(x));
+
+// Non-negative integers are stored as `UInt`, the same as the parser does, so they compare equal to parsed values
+impl_from!(i8, i16, i32, i64, isize => |x| match x {
+    x if x < 0 => Value::Number(Number::Int(x as i64)),
+    x => Value::Number(Number::UInt(x as u64)),
+});
+
+// 128 bit integers only use the 128 bit variants if they don't fit in 64 bits, again matching the parser
+impl_from!(u128 => |x| match u64::try_from(x) {
+    Ok(x) => Value::Number(Number::UInt(x)),
+    Err(_) => Value::Number(Number::UInt128(x)),
+});
+impl_from!(i128 => |x| match (u64::try_from(x), i64::try_from(x)) {
+    (Ok(x), _) => Value::Number(Number::UInt(x)),
+    (_, Ok(x)) => Value::Number(Number::Int(x)),
+    (Err(_), Err(_)) if x > 0 => Value::Number(Number::UInt128(x as u128)),
+    _ => Value::Number(Number::Int128(x)),
+});
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(x: Vec<T>) -> Self {
+        Value::Array(x.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(x: Option<T>) -> Self {
+        x.map(Into::into).unwrap_or(Value::Null)
+    }
+}
+
+/// Builds an object from an array of key-value pairs.
+/// Ex. `Value::from([("a", 1), ("b", 2)])`
+impl<K: Into<String>, V: Into<Value>, const N: usize> From<[(K, V); N]> for Value {
+    fn from(x: [(K, V); N]) -> Self {
+        Value::from_entries(x)
+    }
+}
+
+/// Collects values into a [`Value::Array`].
+/// Ex. `(0..3).map(Value::from).collect::<Value>()` => `[0, 1, 2]`
+impl FromIterator<Value> for Value {
+    fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
+        Value::Array(iter.into_iter().collect())
+    }
+}
+
+/// Collects key-value pairs into a [`Value::Object`].
+/// If a key appears more than once, the last value is kept.
+impl FromIterator<(String, Value)> for Value {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        Value::Object(iter.into_iter().collect())
+    }
+}
+
 impl Value {
+    /// Builds an object from an iterator of key-value pairs, like JavaScript's `Object.fromEntries`.
+    /// If a key appears more than once, the last value is kept.
+    pub fn from_entries<K: Into<String>, V: Into<Value>>(
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> Value {
+        Value::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
     /// Checks if the value is null.
     pub fn is_null(&self) -> bool {
         matches!(self, Value::Null)
@@ -81,42 +188,476 @@ impl Value {
     impl_as!(mut, as_mut_string, Value::String, String);
     impl_as!(mut, as_mut_array, Value::Array, Vec<Value>);
     impl_as!(mut, as_mut_object, Value::Object, BTreeMap<String, Value>);
+
+    /// Looks up a value by a [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer.
+    /// Ex. `/users/0/name`
+    /// Returns `None` if the pointer is malformed or nothing is at that location.
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let mut value = self;
+        for token in pointer::tokens(pointer)? {
+            value = match value {
+                Value::Object(o) => o.get(&token)?,
+                Value::Array(a) => a.get(pointer::index(&token)?)?,
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Looks up several values with [`Value::pointer`], returning the result for each pointer in order.
+    /// Ex. `value.pointer_many(&["/id", "/user/name", "/tags/0"])`
+    pub fn pointer_many(&self, pointers: &[&str]) -> Vec<Option<&Value>> {
+        pointers.iter().map(|x| self.pointer(x)).collect()
+    }
+
+    /// Checks if `needle` is anywhere in the value, including the value itself.
+    /// Ex. `{"a": [1, {"b": 2}]}` contains `2`, `{"b": 2}` and `[1, {"b": 2}]`
+    pub fn contains(&self, needle: &Value) -> bool {
+        match self {
+            x if x == needle => true,
+            Value::Array(a) => a.iter().any(|x| x.contains(needle)),
+            Value::Object(o) => o.values().any(|x| x.contains(needle)),
+            _ => false,
+        }
+    }
+
+    /// Checks if two values are equal, allowing floats to differ by up to `epsilon`.
+    /// Ex. `[0.30000000000000004]` is approximately equal to `[0.3]` with an epsilon of `1e-9`
+    ///
+    /// A float can also match an integer within `epsilon`, but all other values must be exactly equal.
+    pub fn approx_eq(&self, other: &Value, epsilon: f64) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) if a == b => true,
+            (Value::Number(Number::Float(_)), Value::Number(_))
+            | (Value::Number(_), Value::Number(Number::Float(_))) => self
+                .coerce_f64()
+                .zip(other.coerce_f64())
+                .is_some_and(|(a, b)| (a - b).abs() <= epsilon),
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.approx_eq(b, epsilon))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|(a, b)| a.0 == b.0 && a.1.approx_eq(b.1, epsilon))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Shallowly combines `other` into the value.
+    /// Arrays have the elements of `other` appended, and objects have its entries inserted, with `other` winning on key collisions.
+    /// Ex. `{"a": 1, "b": 2}` extended with `{"b": 3, "c": 4}` => `{"a": 1, "b": 3, "c": 4}`
+    ///
+    /// Nested values are replaced rather than combined.
+    /// If the values aren't both arrays or both objects, nothing is changed.
+    pub fn extend(&mut self, other: Value) {
+        match (self, other) {
+            (Value::Array(a), Value::Array(b)) => a.extend(b),
+            (Value::Object(a), Value::Object(b)) => a.extend(b),
+            _ => {}
+        }
+    }
+
+    /// Adds `by` to a number in place, returning false and changing nothing if the value isn't a number.
+    /// Ex. `value.pointer_mut("/hits").unwrap().increment(1.0)` turns `{"hits": 41}` into `{"hits": 42}`
+    ///
+    /// Integers stay integers if `by` is a whole number and the result fits in 128 bits, using the same variant the parser would.
+    /// Otherwise the result is a [`Number::Float`].
+    pub fn increment(&mut self, by: f64) -> bool {
+        let int = match self {
+            Value::Number(Number::UInt(x)) => Some(*x as i128),
+            Value::Number(Number::Int(x)) => Some(*x as i128),
+            Value::Number(Number::UInt128(x)) => i128::try_from(*x).ok(),
+            Value::Number(Number::Int128(x)) => Some(*x),
+            Value::Number(Number::Raw(x)) => x.parse().ok(),
+            Value::Number(Number::Float(_)) => None,
+            _ => return false,
+        };
+
+        // `as` saturates, so `by` is only used as an integer if it is exactly representable
+        let whole = by.fract() == 0.0 && by.abs() < i128::MAX as f64;
+        *self = match int
+            .filter(|_| whole)
+            .and_then(|x| x.checked_add(by as i128))
+        {
+            Some(x) => Value::from(x),
+            None => Value::from(self.coerce_f64().unwrap_or(f64::NAN) + by),
+        };
+        true
+    }
+
+    /// Recursively puts the keys of every object, including those nested in arrays, in sorted order.
+    /// Objects in a [`Value`] are stored in a [`BTreeMap`], so they are always sorted and this changes nothing,
+    /// but canonicalizing code can call it without depending on how objects are stored.
+    pub fn sort_keys(&mut self) {
+        match self {
+            Value::Array(a) => a.iter_mut().for_each(Value::sort_keys),
+            Value::Object(o) => o.values_mut().for_each(Value::sort_keys),
+            _ => {}
+        }
+    }
+
+    /// Like [`Value::pointer`], but returns a mutable reference.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        self.walk_mut(pointer::tokens(pointer)?)
+    }
+
+    /// Looks up a value by a dotted path, returning a mutable reference.
+    /// Array elements are selected with their index.
+    /// Ex. `users.0.name`
+    /// Returns `None` if nothing is at that location, as missing segments are never created.
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut Value> {
+        match path {
+            "" => Some(self),
+            _ => self.walk_mut(path.split('.')),
+        }
+    }
+
+    /// Follows the object keys and array indices in `tokens`, returning a mutable reference to the value at the end.
+    pub(crate) fn walk_mut<T: AsRef<str>>(
+        &mut self,
+        tokens: impl IntoIterator<Item = T>,
+    ) -> Option<&mut Value> {
+        let mut value = self;
+        for token in tokens {
+            let token = token.as_ref();
+            value = match value {
+                Value::Object(o) => o.get_mut(token)?,
+                Value::Array(a) => a.get_mut(pointer::index(token)?)?,
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Checks if the value survives being serialized and parsed again unchanged.
+    /// Known values that don't:
+    /// - Floats with no fractional part, like `1.0`, which are written as `1` and parsed back as an integer
+    /// - Non-negative [`Number::Int`]s, which are parsed back as [`Number::UInt`]s
+    /// - Non-finite floats, which are written as `NaN` or `inf` and can't be parsed
+    pub fn round_trip(&self) -> bool {
+        Value::from_str(&self.to_string()).is_ok_and(|x| &x == self)
+    }
+
+    /// Gets the length in bytes of the value serialized with [`Value::to_string`], without building the string.
+    pub fn serialized_len(&self) -> usize {
+        let mut counter = Counter(0);
+        serialize::write(&mut counter, self, &SerializeOptions::default()).unwrap();
+        counter.0
+    }
+
+    /// Serializes the value into `buf`, the same as [`Value::to_string`], and returns the number of bytes written.
+    /// Ex. `let len = value.write_into(&mut buf)?; &buf[..len]`
+    /// Fails with [`BufferTooSmall`] if the output doesn't fit, in which case the contents of `buf` are unspecified.
+    /// Use [`Value::serialized_len`] to size the buffer ahead of time.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+        let mut writer = SliceWriter { buf, len: 0 };
+        match serialize::write(&mut writer, self, &SerializeOptions::default()) {
+            Ok(()) => Ok(writer.len),
+            Err(_) => Err(BufferTooSmall {
+                needed: self.serialized_len(),
+            }),
+        }
+    }
+
+    /// Reads and parses the file at `path`.
+    /// Fails with [`Error::Io`] if the file can't be read and [`Error::InvalidUtf8`] if it isn't valid UTF-8.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Value, Error> {
+        let bytes = fs::read(path)?;
+        Value::from_str(str::from_utf8(&bytes)?)
+    }
+
+    /// Reads `reader` to the end and parses it.
+    /// Fails with [`Error::Io`] if reading fails and [`Error::InvalidUtf8`] if the input isn't valid UTF-8.
+    pub fn from_reader(mut reader: impl Read) -> Result<Value, Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Value::from_str(str::from_utf8(&bytes)?)
+    }
+
+    /// Parses `input` with the given options.
+    /// Ex. `Value::from_str_with("1.10", &ParseOptions { raw_numbers: true, ..Default::default() })`
+    pub fn from_str_with(input: &str, options: &ParseOptions) -> Result<Value, Error> {
+        Parser::with_options(input, options.clone()).parse()
+    }
+
+    /// Parses `input`, passing every value through `reviver` before it is added to its parent.
+    /// Values are visited bottom-up, so children are revived before the containers holding them.
+    /// Works like the second argument to JavaScript's `JSON.parse`.
+    pub fn from_str_with_reviver(
+        input: &str,
+        mut reviver: impl FnMut(&JsonPath, Value) -> Value,
+    ) -> Result<Value, Error> {
+        fn revive(
+            path: &mut JsonPath,
+            value: Value,
+            f: &mut impl FnMut(&JsonPath, Value) -> Value,
+        ) -> Value {
+            let value = match value {
+                Value::Array(a) => Value::Array(
+                    a.into_iter()
+                        .enumerate()
+                        .map(|(i, x)| {
+                            path.push(PathSegment::Index(i));
+                            let out = revive(path, x, f);
+                            path.pop();
+                            out
+                        })
+                        .collect(),
+                ),
+                Value::Object(o) => Value::Object(
+                    o.into_iter()
+                        .map(|(k, x)| {
+                            path.push(PathSegment::Key(k));
+                            let out = revive(path, x, f);
+                            match path.pop() {
+                                Some(PathSegment::Key(k)) => (k, out),
+                                _ => unreachable!(),
+                            }
+                        })
+                        .collect(),
+                ),
+                x => x,
+            };
+
+            f(path, value)
+        }
+
+        let value = Value::from_str(input)?;
+        Ok(revive(&mut JsonPath::new(), value, &mut reviver))
+    }
+
+    /// Serializes the value, passing every value through `replacer` before it is written.
+    /// Values are visited top-down, so the children of a replaced value are the children of the replacement.
+    /// Returning `None` omits an object entry, writes `null` for an array element and `null` for the root.
+    /// Works like the second argument to JavaScript's `JSON.stringify`.
+    pub fn to_string_with_replacer(
+        &self,
+        mut replacer: impl for<'a> FnMut(&JsonPath, &'a Value) -> Option<Cow<'a, Value>>,
+    ) -> String {
+        fn replace<F>(path: &mut JsonPath, value: Option<Cow<Value>>, out: &mut String, f: &mut F)
+        where
+            F: for<'a> FnMut(&JsonPath, &'a Value) -> Option<Cow<'a, Value>>,
+        {
+            let value = match value {
+                Some(x) => x,
+                None => return out.push_str("null"),
+            };
+
+            match value.as_ref() {
+                Value::Array(a) => {
+                    out.push('[');
+                    for (i, x) in a.iter().enumerate() {
+                        if i > 0 {
+                            out.push(',');
+                        }
+                        path.push(PathSegment::Index(i));
+                        replace(path, f(path, x), out, f);
+                        path.pop();
+                    }
+                    out.push(']');
+                }
+                Value::Object(o) => {
+                    out.push('{');
+                    let mut first = true;
+                    for (k, x) in o {
+                        path.push(PathSegment::Key(k.to_owned()));
+                        if let Some(x) = f(path, x) {
+                            if !first {
+                                out.push(',');
+                            }
+                            first = false;
+                            out.push_str(&format!(r#""{}":"#, escape(k)));
+                            replace(path, Some(x), out, f);
+                        }
+                        path.pop();
+                    }
+                    out.push('}');
+                }
+                x => out.push_str(&x.to_string()),
+            }
+        }
+
+        let mut out = String::new();
+        let mut path = JsonPath::new();
+        let root = replacer(&path, self);
+        replace(&mut path, root, &mut out, &mut replacer);
+        out
+    }
+
+    /// Counts the nodes, nesting depth and sizes of the value in one traversal.
+    /// Useful for rejecting oversized documents before doing any real work on them.
+    pub fn stats(&self) -> Stats {
+        fn visit(value: &Value, depth: usize, stats: &mut Stats) {
+            stats.max_depth = stats.max_depth.max(depth);
+
+            match value {
+                Value::Null => stats.nulls += 1,
+                Value::Bool(_) => stats.bools += 1,
+                Value::Number(_) => stats.numbers += 1,
+                Value::String(s) => {
+                    stats.strings += 1;
+                    stats.string_bytes += s.len();
+                }
+                Value::Array(a) => {
+                    stats.arrays += 1;
+                    stats.max_array_len = stats.max_array_len.max(a.len());
+                    a.iter().for_each(|x| visit(x, depth + 1, stats));
+                }
+                Value::Object(o) => {
+                    stats.objects += 1;
+                    stats.max_object_len = stats.max_object_len.max(o.len());
+                    for (k, v) in o {
+                        stats.keys += 1;
+                        stats.key_bytes += k.len();
+                        visit(v, depth + 1, stats);
+                    }
+                }
+            }
+        }
+
+        let mut stats = Stats::default();
+        visit(self, 0, &mut stats);
+        stats
+    }
 }
 
-impl Display for Value {
+/// The error returned by [`Value::write_into`] when the buffer is too small for the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The number of bytes the output needs.
+    pub needed: usize,
+}
+
+// Counts the bytes written, for `Value::serialized_len`
+struct Counter(usize);
+
+impl fmt::Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+// Writes into a fixed buffer, failing once it is full, for `Value::write_into`
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Size metrics of a [`Value`], from [`Value::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    /// The number of null values.
+    pub nulls: usize,
+    /// The number of bool values.
+    pub bools: usize,
+    /// The number of number values.
+    pub numbers: usize,
+    /// The number of string values, not including object keys.
+    pub strings: usize,
+    /// The number of arrays.
+    pub arrays: usize,
+    /// The number of objects.
+    pub objects: usize,
+    /// The number of object keys, across every object.
+    pub keys: usize,
+    /// The total length in bytes of every string value.
+    pub string_bytes: usize,
+    /// The total length in bytes of every object key.
+    pub key_bytes: usize,
+    /// The deepest nesting level, where the root is at depth 0.
+    /// Ex. `1` => 0, `[1]` => 1, `{"a": [1]}` => 2
+    pub max_depth: usize,
+    /// The length of the longest array.
+    pub max_array_len: usize,
+    /// The number of entries in the largest object.
+    pub max_object_len: usize,
+}
+
+impl Stats {
+    /// Gets the total number of values, including containers.
+    pub fn nodes(&self) -> usize {
+        self.nulls + self.bools + self.numbers + self.strings + self.arrays + self.objects
+    }
+}
+
+/// Escapes a string so it can be put between quotes in JSON output.
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('/', "\\/")
+        .replace('\u{0008}', "\\b")
+        .replace('\u{000C}', "\\f")
+        .replace('\u{000A}', "\\n")
+        .replace('\u{000D}', "\\r")
+        .replace('\u{0009}', "\\t")
+}
+
+/// Writes the value in a compact JSON-like form, with the type of each number as a suffix, so `dbg!` output stays readable.
+/// Ex. `{"a": [1u64, -2i64, 1.5f64, null, "x"]}`
+/// Like most `Debug` impls, `{:#?}` puts each array element and object entry on its own line.
+impl<K: MapKind> fmt::Debug for GenericValue<K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fn escape(s: &str) -> String {
-            s.replace('\\', "\\\\")
-                .replace('"', "\\\"")
-                .replace('/', "\\/")
-                .replace('\u{0008}', "\\b")
-                .replace('\u{000C}', "\\f")
-                .replace('\u{000A}', "\\n")
-                .replace('\u{000D}', "\\r")
-                .replace('\u{0009}', "\\t")
+        match self {
+            GenericValue::Null => f.write_str("null"),
+            GenericValue::Bool(x) => write!(f, "{x}"),
+            GenericValue::Number(Number::UInt(x)) => write!(f, "{x}u64"),
+            GenericValue::Number(Number::Int(x)) => write!(f, "{x}i64"),
+            GenericValue::Number(Number::UInt128(x)) => write!(f, "{x}u128"),
+            GenericValue::Number(Number::Int128(x)) => write!(f, "{x}i128"),
+            GenericValue::Number(Number::Float(x)) => write!(f, "{x:?}f64"),
+            GenericValue::Number(Number::Raw(x)) => write!(f, "raw({x})"),
+            GenericValue::String(x) => fmt::Debug::fmt(x, f),
+            GenericValue::Array(x) => f.debug_list().entries(x).finish(),
+            GenericValue::Object(x) => fmt::Debug::fmt(x, f),
         }
+    }
+}
 
-        match self {
-            Self::Null => write!(f, "null"),
-            Self::Bool(b) => write!(f, "{b}"),
-            Self::Number(n) => write!(f, "{n}"),
-            Self::String(s) => write!(f, r#""{}""#, escape(s)),
-            Self::Array(a) => write!(
-                f,
-                "[{}]",
-                a.iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>()
-                    .join(",")
-            ),
-            Self::Object(o) => write!(
-                f,
-                "{{{}}}",
-                o.iter()
-                    .map(|x| format!(r#""{}":{}"#, escape(x.0), x.1))
-                    .collect::<Vec<_>>()
-                    .join(",")
-            ),
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        serialize::write(f, self, &SerializeOptions::default())
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Values of different types are ordered `Null < Bool < Number < String < Array < Object`.
+/// Values of the same type are compared by their contents:
+/// - Numbers by value, see [`Number`]'s `Ord` impl
+/// - Strings lexicographically by bytes
+/// - Arrays lexicographically, element by element
+/// - Objects lexicographically by their key-value pairs, in sorted key order
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Number(a), Value::Number(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Array(a), Value::Array(b)) => a.cmp(b),
+            (Value::Object(a), Value::Object(b)) => a.cmp(b),
+            (a, b) => a.kind().cmp(&b.kind()),
         }
     }
 }
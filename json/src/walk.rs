@@ -0,0 +1,55 @@
+use crate::{JsonPointerBuf, Value};
+
+/// A depth-first iterator over every node of a [`Value`].
+/// Created with [`Value::nodes`].
+pub struct Nodes<'a> {
+    stack: Vec<(JsonPointerBuf, &'a Value)>,
+}
+
+impl<'a> Iterator for Nodes<'a> {
+    type Item = (JsonPointerBuf, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pointer, value) = self.stack.pop()?;
+
+        // Children are pushed in reverse so they come off the stack in document order
+        match value {
+            Value::Array(a) => self.stack.extend(a.iter().enumerate().rev().map(|(i, x)| {
+                let mut pointer = pointer.clone();
+                pointer.push_index(i);
+                (pointer, x)
+            })),
+            Value::Object(o) => self.stack.extend(o.iter().rev().map(|(k, x)| {
+                let mut pointer = pointer.clone();
+                pointer.push_key(k);
+                (pointer, x)
+            })),
+            _ => {}
+        }
+
+        Some((pointer, value))
+    }
+}
+
+impl Value {
+    /// Iterates over every node in the value, including the root and interior arrays and objects.
+    /// Nodes are yielded depth-first in document order, each with the JSON Pointer to it.
+    /// This uses an explicit stack, so deeply nested values won't overflow the call stack.
+    pub fn nodes(&self) -> Nodes<'_> {
+        Nodes {
+            stack: vec![(JsonPointerBuf::new(), self)],
+        }
+    }
+
+    /// Like [`Value::nodes`], but with the pointers as `String`s, which is handy for building a flat index of a document.
+    /// Ex. `{"a": [1]}` yields `("", {"a": [1]})`, `("/a", [1])` and `("/a/0", 1)`
+    pub fn iter_pointers(&self) -> impl Iterator<Item = (String, &Value)> {
+        self.nodes().map(|(pointer, x)| (pointer.into(), x))
+    }
+
+    /// Like [`Value::nodes`], but only yields scalars (null, bool, number and string values).
+    pub fn leaves(&self) -> impl Iterator<Item = (JsonPointerBuf, &Value)> {
+        self.nodes()
+            .filter(|x| !matches!(x.1, Value::Array(_) | Value::Object(_)))
+    }
+}
@@ -0,0 +1,626 @@
+//! Converting between [`Value`]s and the JSON compatible subset of [YAML](https://yaml.org/spec/1.2.2).
+//!
+//! Block mappings and sequences, single line flow collections, quoted and plain scalars, comments and multi-document streams are supported.
+//! Plain scalars are resolved with the YAML 1.2 core schema, so `no` and `on` are strings and not booleans.
+//! Anchors, aliases, tags, block scalars (`|` and `>`) and complex keys are rejected with [`YamlError::Unsupported`].
+
+use std::collections::BTreeMap;
+
+use crate::{Number, Value};
+
+/// Errors that can occur while parsing YAML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YamlError {
+    /// The input isn't valid YAML.
+    /// Contains the position of the problem.
+    Syntax(usize),
+    /// The input uses a YAML feature outside of the supported subset, like an anchor or tag.
+    /// Contains the position of the feature.
+    Unsupported(usize),
+    /// A key appeared more than once in the same mapping.
+    /// Contains the position of the second key.
+    DuplicateKey(usize),
+}
+
+type YamlResult<T> = Result<T, YamlError>;
+
+/// Parses a stream of YAML documents, separated by `---` lines.
+/// Ex. `a: 1\n---\n- b` => `[{"a": 1}, ["b"]]`
+///
+/// A document with nothing in it is null and an input with no documents at all gives an empty list.
+pub fn from_yaml(input: &str) -> YamlResult<Vec<Value>> {
+    let mut documents = vec![(false, Vec::new())];
+    let mut offset = 0;
+
+    for raw in input.split('\n') {
+        let pos = offset;
+        offset += raw.len() + 1;
+
+        let text = strip_comment(raw.strip_suffix('\r').unwrap_or(raw)).trim_end();
+        let content = text.trim_start_matches(' ');
+        if content.is_empty() {
+            continue;
+        }
+        if content.starts_with('\t') {
+            return Err(YamlError::Syntax(pos + text.len() - content.len()));
+        }
+
+        // A `---` starts a new document, unless it's the first thing in the stream
+        if text == "---" || text.starts_with("--- ") || text == "..." {
+            let (explicit, lines) = documents.last().unwrap();
+            if *explicit || !lines.is_empty() {
+                documents.push((false, Vec::new()));
+            }
+            documents.last_mut().unwrap().0 = text != "...";
+
+            let rest = text[3..].trim_start();
+            if !rest.is_empty() {
+                let indent = text.len() - rest.len();
+                let line = Line::new(rest, indent, pos + indent);
+                documents.last_mut().unwrap().1.push(line);
+            }
+            continue;
+        }
+
+        let indent = text.len() - content.len();
+        documents
+            .last_mut()
+            .unwrap()
+            .1
+            .push(Line::new(content, indent, pos + indent));
+    }
+
+    documents
+        .into_iter()
+        .filter(|(explicit, lines)| *explicit || !lines.is_empty())
+        .map(|(_, lines)| Document { lines, idx: 0 }.parse())
+        .collect()
+}
+
+/// Converts a value into a block style YAML document.
+/// Ex. `{"a": [1, {"b": null}]}` => `a:\n  - 1\n  - b: null\n`
+///
+/// Strings are only quoted when they would otherwise be read as something else,
+/// which includes the YAML 1.1 booleans like `no`, strings that look like numbers and strings containing `: `.
+pub fn to_yaml(value: &Value) -> String {
+    let mut out = String::new();
+    match value {
+        Value::Array(a) if !a.is_empty() => write_block(&mut out, value, 0),
+        Value::Object(o) if !o.is_empty() => write_block(&mut out, value, 0),
+        x => {
+            out.push_str(&scalar(x));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+#[derive(Clone, Copy)]
+struct Line<'a> {
+    text: &'a str,
+    indent: usize,
+    pos: usize,
+}
+
+impl<'a> Line<'a> {
+    fn new(text: &'a str, indent: usize, pos: usize) -> Self {
+        Self { text, indent, pos }
+    }
+
+    fn is_sequence_item(&self) -> bool {
+        self.text == "-" || self.text.starts_with("- ")
+    }
+}
+
+struct Document<'a> {
+    lines: Vec<Line<'a>>,
+    idx: usize,
+}
+
+impl Document<'_> {
+    fn parse(mut self) -> YamlResult<Value> {
+        let indent = match self.lines.first() {
+            Some(x) => x.indent,
+            None => return Ok(Value::Null),
+        };
+
+        let value = self.block(indent)?;
+        match self.lines.get(self.idx) {
+            Some(x) => Err(YamlError::Syntax(x.pos)),
+            None => Ok(value),
+        }
+    }
+
+    fn block(&mut self, indent: usize) -> YamlResult<Value> {
+        let line = self.lines[self.idx];
+        if line.is_sequence_item() {
+            return self.sequence(indent);
+        }
+
+        if split_key(line.text, line.pos)?.is_some() {
+            return self.mapping(indent);
+        }
+
+        self.idx += 1;
+        parse_scalar(line.text, line.pos)
+    }
+
+    fn sequence(&mut self, indent: usize) -> YamlResult<Value> {
+        let mut out = Vec::new();
+        while let Some(&line) = self.lines.get(self.idx) {
+            if line.indent != indent || !line.is_sequence_item() {
+                break;
+            }
+
+            let rest = line.text[1..].trim_start_matches(' ');
+            if rest.is_empty() {
+                self.idx += 1;
+                out.push(self.nested(indent)?);
+                continue;
+            }
+
+            // Whatever follows the `-` is treated as its own line, so `- a: 1` starts a mapping
+            let offset = line.text.len() - rest.len();
+            self.lines[self.idx] = Line::new(rest, indent + offset, line.pos + offset);
+            out.push(self.block(indent + offset)?);
+        }
+
+        Ok(Value::Array(out))
+    }
+
+    fn mapping(&mut self, indent: usize) -> YamlResult<Value> {
+        let mut out = BTreeMap::new();
+        while let Some(&line) = self.lines.get(self.idx) {
+            if line.indent != indent {
+                break;
+            }
+
+            let (key, rest, rest_pos) =
+                split_key(line.text, line.pos)?.ok_or(YamlError::Syntax(line.pos))?;
+            if out.contains_key(&key) {
+                return Err(YamlError::DuplicateKey(line.pos));
+            }
+
+            self.idx += 1;
+            let value = match (rest, self.lines.get(self.idx)) {
+                ("", Some(x)) if x.indent == indent && x.is_sequence_item() => {
+                    self.sequence(indent)?
+                }
+                ("", _) => self.nested(indent)?,
+                (rest, _) => parse_scalar(rest, rest_pos)?,
+            };
+            out.insert(key, value);
+        }
+
+        Ok(Value::Object(out))
+    }
+
+    /// Parses the block indented under the current line, if there is one.
+    fn nested(&mut self, indent: usize) -> YamlResult<Value> {
+        match self.lines.get(self.idx) {
+            Some(x) if x.indent > indent => self.block(x.indent),
+            _ => Ok(Value::Null),
+        }
+    }
+}
+
+/// Splits a `key: value` line into the key, the value text and the position of the value.
+/// Returns `None` if the line isn't a mapping entry.
+fn split_key(text: &str, pos: usize) -> YamlResult<Option<(String, &str, usize)>> {
+    let is_separator = |rest: &str| rest == ":" || rest.starts_with(": ");
+
+    let (key, after) = match text.chars().next() {
+        Some('?') if text.len() == 1 || text.starts_with("? ") => {
+            return Err(YamlError::Unsupported(pos))
+        }
+        Some('"' | '\'') => {
+            let mut flow = Flow::new(text, pos);
+            let key = flow.quoted()?;
+            let rest = text[flow.pos..].trim_start_matches(' ');
+            if !is_separator(rest) {
+                return Ok(None);
+            }
+            (key, &text[text.len() - rest.len() + 1..])
+        }
+        Some('[' | '{') => return Ok(None),
+        _ => {
+            let split = text
+                .find(": ")
+                .or_else(|| text.ends_with(':').then(|| text.len() - 1));
+            match split {
+                Some(i) => (text[..i].trim_end().to_owned(), &text[i + 1..]),
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let rest = after.trim_start_matches(' ');
+    Ok(Some((key, rest, pos + text.len() - rest.len())))
+}
+
+/// Parses the value part of a line, which can be a flow collection, a quoted string or a plain scalar.
+fn parse_scalar(text: &str, pos: usize) -> YamlResult<Value> {
+    match text.chars().next() {
+        Some('[' | '{' | '"' | '\'') => {
+            let mut flow = Flow::new(text, pos);
+            let value = flow.value()?;
+            flow.skip_spaces();
+            match flow.pos == text.len() {
+                true => Ok(value),
+                false => Err(YamlError::Syntax(pos + flow.pos)),
+            }
+        }
+        Some('&' | '*' | '!' | '|' | '>' | '%') => Err(YamlError::Unsupported(pos)),
+        Some('@' | '`') => Err(YamlError::Syntax(pos)),
+        _ => Ok(resolve(text)),
+    }
+}
+
+/// A parser for flow collections and quoted strings.
+struct Flow<'a> {
+    text: &'a str,
+    pos: usize,
+    base: usize,
+}
+
+impl<'a> Flow<'a> {
+    fn new(text: &'a str, base: usize) -> Self {
+        Self { text, pos: 0, base }
+    }
+
+    fn value(&mut self) -> YamlResult<Value> {
+        self.skip_spaces();
+        match self.peek() {
+            Some('[') => self.sequence(),
+            Some('{') => self.mapping(),
+            Some('"' | '\'') => self.quoted().map(Value::String),
+            Some('&' | '*' | '!') => Err(YamlError::Unsupported(self.base + self.pos)),
+            Some(_) => {
+                let text = self.plain();
+                match text.is_empty() {
+                    true => Err(self.error()),
+                    false => Ok(resolve(text)),
+                }
+            }
+            None => Err(self.error()),
+        }
+    }
+
+    fn sequence(&mut self) -> YamlResult<Value> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        loop {
+            self.skip_spaces();
+            if self.eat(']') {
+                return Ok(Value::Array(out));
+            }
+
+            out.push(self.value()?);
+            self.skip_spaces();
+            if !self.eat(',') {
+                self.expect(']')?;
+                return Ok(Value::Array(out));
+            }
+        }
+    }
+
+    fn mapping(&mut self) -> YamlResult<Value> {
+        self.pos += 1;
+        let mut out = BTreeMap::new();
+        loop {
+            self.skip_spaces();
+            if self.eat('}') {
+                return Ok(Value::Object(out));
+            }
+
+            let start = self.base + self.pos;
+            let key = match self.peek() {
+                Some('"' | '\'') => self.quoted()?,
+                _ => self.plain().to_owned(),
+            };
+            self.skip_spaces();
+            self.expect(':')?;
+            self.skip_spaces();
+
+            let value = match self.peek() {
+                Some(',' | '}') => Value::Null,
+                _ => self.value()?,
+            };
+            if out.insert(key, value).is_some() {
+                return Err(YamlError::DuplicateKey(start));
+            }
+
+            self.skip_spaces();
+            if !self.eat(',') {
+                self.expect('}')?;
+                return Ok(Value::Object(out));
+            }
+        }
+    }
+
+    /// Reads a plain scalar, which ends at a flow indicator or a `:` followed by a space or indicator.
+    fn plain(&mut self) -> &'a str {
+        let rest = &self.text[self.pos..];
+        let mut end = rest.len();
+        for (i, chr) in rest.char_indices() {
+            let next = rest[i + chr.len_utf8()..].chars().next();
+            if matches!(chr, ',' | '[' | ']' | '{' | '}')
+                || (chr == ':' && matches!(next, None | Some(' ' | ',' | ']' | '}')))
+            {
+                end = i;
+                break;
+            }
+        }
+
+        self.pos += end;
+        rest[..end].trim_end()
+    }
+
+    fn quoted(&mut self) -> YamlResult<String> {
+        let start = self.base + self.pos;
+        let quote = self.next().ok_or_else(|| self.error())?;
+        let mut out = String::new();
+
+        loop {
+            match self.next() {
+                None => return Err(YamlError::Syntax(start)),
+                Some('\'') if quote == '\'' && self.eat('\'') => out.push('\''),
+                Some(x) if x == quote => return Ok(out),
+                Some('\\') if quote == '"' => out.push(self.escape()?),
+                Some(x) => out.push(x),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> YamlResult<char> {
+        let start = self.base + self.pos - 1;
+        let len = match self.next() {
+            Some('0') => return Ok('\0'),
+            Some('b') => return Ok('\x08'),
+            Some('t' | '\t') => return Ok('\t'),
+            Some('n') => return Ok('\n'),
+            Some('f') => return Ok('\x0C'),
+            Some('r') => return Ok('\r'),
+            Some('e') => return Ok('\x1B'),
+            Some(x @ ('"' | '/' | '\\' | ' ')) => return Ok(x),
+            Some('x') => 2,
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(YamlError::Syntax(start)),
+        };
+
+        let hex = self.text[self.pos..]
+            .get(..len)
+            .ok_or(YamlError::Syntax(start))?;
+        let chr = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.bytes().all(|x| x.is_ascii_hexdigit()))
+            .and_then(char::from_u32)
+            .ok_or(YamlError::Syntax(start))?;
+        self.pos += len;
+        Ok(chr)
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let chr = self.peek()?;
+        self.pos += chr.len_utf8();
+        Some(chr)
+    }
+
+    fn eat(&mut self, chr: char) -> bool {
+        let found = self.peek() == Some(chr);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, chr: char) -> YamlResult<()> {
+        match self.eat(chr) {
+            true => Ok(()),
+            false => Err(self.error()),
+        }
+    }
+
+    fn error(&self) -> YamlError {
+        YamlError::Syntax(self.base + self.pos)
+    }
+}
+
+/// Removes a comment from the end of a line.
+/// A `#` only starts a comment at the start of the line or after whitespace, and never inside quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    let mut chars = line.char_indices();
+
+    while let Some((i, chr)) = chars.next() {
+        match (quote, chr) {
+            (None, '#') if prev == ' ' || prev == '\t' => return &line[..i],
+            // Quotes only open a string at the start of a token, so `it's` is plain text
+            (None, '"' | '\'') if matches!(prev, ' ' | '\t' | '[' | '{' | ',' | ':') => {
+                quote = Some(chr)
+            }
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some(x), _) if x == chr => quote = None,
+            _ => {}
+        }
+        prev = chr;
+    }
+
+    line
+}
+
+/// Resolves a plain scalar with the YAML 1.2 core schema.
+fn resolve(text: &str) -> Value {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => return Value::Null,
+        "true" | "True" | "TRUE" => return Value::Bool(true),
+        "false" | "False" | "FALSE" => return Value::Bool(false),
+        _ => {}
+    }
+
+    let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text);
+    let float = match unsigned {
+        ".inf" | ".Inf" | ".INF" if text.starts_with('-') => Some(f64::NEG_INFINITY),
+        ".inf" | ".Inf" | ".INF" => Some(f64::INFINITY),
+        ".nan" | ".NaN" | ".NAN" if text == unsigned => Some(f64::NAN),
+        _ => None,
+    };
+    if let Some(x) = float {
+        return Value::Number(Number::Float(x));
+    }
+
+    let integer = match text {
+        _ if is_digits(unsigned) => text.parse::<i64>().map(Value::from).ok(),
+        _ if text.starts_with("0x") => i64::from_str_radix(&text[2..], 16).map(Value::from).ok(),
+        _ if text.starts_with("0o") => i64::from_str_radix(&text[2..], 8).map(Value::from).ok(),
+        _ => None,
+    };
+    // Integers too big for an `i64` fall through to being floats or strings
+    let integer = integer.or_else(|| text.parse::<u64>().map(Value::from).ok());
+    if let Some(x) = integer {
+        return x;
+    }
+
+    if is_float(unsigned) {
+        if let Ok(x) = text.parse() {
+            return Value::Number(Number::Float(x));
+        }
+    }
+
+    Value::String(text.to_owned())
+}
+
+fn is_digits(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|x| x.is_ascii_digit())
+}
+
+/// Checks for `(\.[0-9]+|[0-9]+(\.[0-9]*)?)([eE][-+]?[0-9]+)?`, without the sign.
+fn is_float(text: &str) -> bool {
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(i) => (&text[..i], Some(&text[i + 1..])),
+        None => (text, None),
+    };
+
+    let mantissa = match mantissa.split_once('.') {
+        Some(("", frac)) => is_digits(frac),
+        Some((int, frac)) => is_digits(int) && (frac.is_empty() || is_digits(frac)),
+        None => is_digits(mantissa),
+    };
+
+    mantissa && exponent.is_none_or(|x| is_digits(x.strip_prefix(['-', '+']).unwrap_or(x)))
+}
+
+/// Writes a non-empty array or object as indented block lines.
+fn write_block(out: &mut String, value: &Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    let is_block = |x: &Value| match x {
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+        _ => false,
+    };
+
+    match value {
+        Value::Object(o) => {
+            for (k, v) in o {
+                out.push_str(&format!("{pad}{}:", string(k)));
+                match is_block(v) {
+                    true => {
+                        out.push('\n');
+                        write_block(out, v, indent + 2);
+                    }
+                    false => out.push_str(&format!(" {}\n", scalar(v))),
+                }
+            }
+        }
+        Value::Array(a) => {
+            for i in a {
+                if !is_block(i) {
+                    out.push_str(&format!("{pad}- {}\n", scalar(i)));
+                    continue;
+                }
+
+                // The first line of the nested block goes on the same line as the `-`
+                let mut nested = String::new();
+                write_block(&mut nested, i, indent + 2);
+                out.push_str(&format!("{pad}- {}", &nested[indent + 2..]));
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Writes a value that fits on one line.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_owned(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(Number::Float(x)) if x.is_nan() => ".nan".to_owned(),
+        Value::Number(Number::Float(x)) if x.is_infinite() => {
+            (if *x > 0.0 { ".inf" } else { "-.inf" }).to_owned()
+        }
+        // Debug formatting always includes a `.` or exponent, so the value is read back as a float
+        Value::Number(Number::Float(x)) => format!("{x:?}"),
+        Value::Number(x) => x.to_string(),
+        Value::String(s) => string(s),
+        Value::Array(_) => "[]".to_owned(),
+        Value::Object(_) => "{}".to_owned(),
+    }
+}
+
+/// Writes a string plain if it would be read back as the same string, otherwise double quoted.
+fn string(s: &str) -> String {
+    const YAML_1_1: &[&str] = &["y", "yes", "n", "no", "on", "off"];
+    const INDICATORS: &[char] = &[
+        '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@',
+        '`',
+    ];
+
+    // Older parsers also read things like `1_000` and `1:20` as numbers
+    let numeric = s.bytes().any(|x| x.is_ascii_digit())
+        && s.bytes()
+            .all(|x| x.is_ascii_hexdigit() || b"_:.+-xXoO".contains(&x));
+    let plain = !s.is_empty()
+        && resolve(s) == Value::String(s.to_owned())
+        && !YAML_1_1.contains(&s.to_lowercase().as_str())
+        && !numeric
+        && !s.starts_with(INDICATORS)
+        && !s.starts_with(char::is_whitespace)
+        && !s.ends_with(|x: char| x.is_whitespace() || x == ':')
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.contains(char::is_control);
+    if plain {
+        return s.to_owned();
+    }
+
+    let mut out = String::from('"');
+    for i in s.chars() {
+        match i {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            x if x.is_control() => out.push_str(&format!("\\u{:04X}", x as u32)),
+            x => out.push(x),
+        }
+    }
+
+    out.push('"');
+    out
+}
@@ -1,3 +1,6 @@
 pub mod math;
 pub mod units;
 pub mod web;
+
+#[cfg(test)]
+mod test;
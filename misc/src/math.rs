@@ -1,3 +1,6 @@
+/// Witnesses that make Miller–Rabin deterministic for every `u64`.
+const MR_WITNESSES: &[u64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
 /// Calculate the greatest common denomanator of `a` and `b`
 pub fn gcd(a: usize, b: usize) -> usize {
     if b == 0 {
@@ -5,3 +8,174 @@ pub fn gcd(a: usize, b: usize) -> usize {
     }
     gcd(b, a % b)
 }
+
+/// Calculate the greatest common denomanator of every number in `nums`.
+/// Returns 0 for an empty slice.
+pub fn gcd_all(nums: &[usize]) -> usize {
+    nums.iter().fold(0, |acc, &x| gcd(acc, x))
+}
+
+/// Calculate `base` to the power of `exp` modulo `modulus`.
+/// Intermediate products are done in `u128` so this can't overflow.
+pub fn pow_mod(base: u64, exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut exp = exp;
+    let mut out = 1;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = out * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    out as u64
+}
+
+/// Calculate `base` to the power of `exp`.
+/// Returns `None` if the result would overflow a `u64`.
+pub fn checked_pow(base: u64, exp: u32) -> Option<u64> {
+    let mut base = base;
+    let mut exp = exp;
+    let mut out = 1u64;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = out.checked_mul(base)?;
+        }
+        exp >>= 1;
+        // Only square when it will be used, so the last square can't overflow on its own
+        if exp > 0 {
+            base = base.checked_mul(base)?;
+        }
+    }
+
+    Some(out)
+}
+
+/// Checks if `n` is prime.
+/// Uses a deterministic Miller–Rabin test, so it is exact for the whole `u64` range.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    for &i in MR_WITNESSES {
+        if n.is_multiple_of(i) {
+            return n == i;
+        }
+    }
+
+    let mut d = n - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+
+    'witness: for &a in MR_WITNESSES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = ((x as u128 * x as u128) % n as u128) as u64;
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Finds every prime less than or equal to `n` with a Sieve of Eratosthenes.
+pub fn primes_up_to(n: usize) -> Vec<usize> {
+    if n < 2 {
+        return Vec::new();
+    }
+
+    let mut sieve = vec![true; n + 1];
+    sieve[0] = false;
+    sieve[1] = false;
+
+    let mut i = 2;
+    while i * i <= n {
+        if sieve[i] {
+            for j in (i * i..=n).step_by(i) {
+                sieve[j] = false;
+            }
+        }
+        i += 1;
+    }
+
+    sieve
+        .iter()
+        .enumerate()
+        .filter(|x| *x.1)
+        .map(|x| x.0)
+        .collect()
+}
+
+/// Calculate the integer square root of `n` (the floor of its square root).
+/// Uses Newton's method, so no floating point is involved.
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut x = n;
+    let mut y = x / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+
+    x
+}
+
+/// Calculate `n!`.
+/// Returns `None` if the result would overflow a `u64` (anything past `20!`).
+pub fn factorial(n: u64) -> Option<u64> {
+    (2..=n).try_fold(1u64, |acc, x| acc.checked_mul(x))
+}
+
+/// Calculate the binomial coefficient "`n` choose `k`".
+/// Returns `Some(0)` if `k > n` and `None` if the result would overflow a `u64`.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+
+    // C(n, k) == C(n, n - k), so use whichever needs fewer steps
+    let k = k.min(n - k);
+    let mut out = 1u64;
+    for i in 0..k {
+        // Dividing every step keeps `out` at C(n, i + 1), which is always an integer
+        let next = out as u128 * (n - i) as u128 / (i + 1) as u128;
+        out = u64::try_from(next).ok()?;
+    }
+
+    Some(out)
+}
+
+/// Calculate the `n`th Fibonacci number, where `fib(0) == 0` and `fib(1) == 1`.
+/// Returns `None` if the result would overflow a `u64` (anything past `fib(93)`).
+pub fn fib(n: u64) -> Option<u64> {
+    // Starting from fib(-1) means the last step computes fib(n) and nothing past it
+    let (mut prev, mut cur) = (1u64, 0u64);
+    for _ in 0..n {
+        (prev, cur) = (cur, prev.checked_add(cur)?);
+    }
+
+    Some(cur)
+}
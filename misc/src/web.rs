@@ -1,4 +1,7 @@
+use std::collections::BTreeMap;
+
 use afire::Request;
+use json::Value;
 
 pub fn real_ip(req: &Request) -> &str {
     let real = req.address.split_once(':').unwrap().0;
@@ -12,3 +15,254 @@ pub fn real_ip(req: &Request) -> &str {
         .map(|x| x.value.split_once(',').unwrap().0)
         .unwrap_or(real)
 }
+
+/// Parses the `Cookie` header of a request into a map of cookie names to values.
+/// Entries without an `=` are skipped.
+/// If a cookie is sent more than once, the last value is used.
+pub fn cookies(req: &Request) -> BTreeMap<String, String> {
+    let header = match req.header("Cookie") {
+        Some(x) => x,
+        None => return BTreeMap::new(),
+    };
+
+    header
+        .split(';')
+        .filter_map(|x| x.split_once('='))
+        .map(|(k, v)| (k.trim().to_owned(), v.trim().to_owned()))
+        .filter(|x| !x.0.is_empty())
+        .collect()
+}
+
+/// Parses the `Content-Type` header of a request.
+/// Returns the lowercased media type and the `charset` parameter, if there is one.
+/// Ex. `Application/JSON; charset="UTF-8"` => `("application/json", Some("utf-8"))`
+pub fn content_type(req: &Request) -> Option<(String, Option<String>)> {
+    let header = req.header("Content-Type")?;
+    let mut parts = header.split(';');
+
+    let media = parts.next()?.trim().to_lowercase();
+    if media.is_empty() {
+        return None;
+    }
+
+    let charset = parts
+        .filter_map(|x| x.split_once('='))
+        .find(|x| x.0.trim().eq_ignore_ascii_case("charset"))
+        .map(|x| x.1.trim().trim_matches('"').to_lowercase());
+
+    Some((media, charset))
+}
+
+/// Parses HTTP Basic auth credentials from the `Authorization` header of a request.
+/// Returns the username and password, or `None` if the header is missing or malformed.
+pub fn basic_auth(req: &Request) -> Option<(String, String)> {
+    let header = req.header("Authorization")?;
+    let (scheme, token) = header.trim().split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("Basic") {
+        return None;
+    }
+
+    let decoded = String::from_utf8(base64_decode(token.trim())?).ok()?;
+    let (user, pass) = decoded.split_once(':')?;
+    Some((user.to_owned(), pass.to_owned()))
+}
+
+/// Parses the `Accept-Language` header of a request into language tags and their quality weights.
+/// Tags without a `;q=` weight default to 1.0.
+/// The list is sorted by descending quality, keeping header order for equal weights.
+pub fn accept_languages(req: &Request) -> Vec<(String, f32)> {
+    let header = match req.header("Accept-Language") {
+        Some(x) => x,
+        None => return Vec::new(),
+    };
+
+    let mut out = header
+        .split(',')
+        .filter_map(|x| {
+            let mut parts = x.split(';');
+            let tag = parts.next()?.trim();
+            if tag.is_empty() {
+                return None;
+            }
+
+            let quality = parts
+                .filter_map(|x| x.trim().strip_prefix("q="))
+                .find_map(|x| x.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            Some((tag.to_owned(), quality))
+        })
+        .collect::<Vec<_>>();
+
+    out.sort_by(|a, b| b.1.total_cmp(&a.1));
+    out
+}
+
+/// Parses the `Range` header of a request against a resource of `len` bytes.
+/// Returns the inclusive start and end of each requested byte range, with ends clamped to `len - 1`.
+/// Ex. `bytes=0-499,1000-,-500` with a length of 2000 => `[(0, 499), (1000, 1999), (1500, 1999)]`
+///
+/// Ranges starting past the end of the resource are dropped.
+/// Returns `None` if the header is missing or malformed, or if none of the ranges can be satisfied.
+pub fn parse_range(req: &Request, len: u64) -> Option<Vec<(u64, u64)>> {
+    let header = req.header("Range")?;
+    let (unit, ranges) = header.trim().split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+
+    let mut out = Vec::new();
+    for i in ranges.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+        let (start, end) = i.split_once('-')?;
+        let range = match (start.trim(), end.trim()) {
+            // Suffix range, the last `end` bytes
+            ("", end) => {
+                let suffix = end.parse::<u64>().ok()?.min(len);
+                (suffix > 0).then(|| (len - suffix, len - 1))
+            }
+            (start, "") => {
+                let start = start.parse::<u64>().ok()?;
+                (start < len).then(|| (start, len - 1))
+            }
+            (start, end) => {
+                let (start, end) = (start.parse::<u64>().ok()?, end.parse::<u64>().ok()?);
+                if end < start {
+                    return None;
+                }
+                (start < len).then(|| (start, end.min(len - 1)))
+            }
+        };
+        out.extend(range);
+    }
+
+    (!out.is_empty()).then_some(out)
+}
+
+/// Parses a query string into a [`Value::Object`].
+/// Ex. `a=1&b=hello&b=world` => `{"a": "1", "b": ["hello", "world"]}`
+///
+/// Keys and values are percent-decoded, with invalid escapes left as they are.
+/// Repeated keys are collected into an array and keys without an `=` map to null.
+pub fn parse_query(query: &str) -> Value {
+    let mut out = BTreeMap::new();
+
+    for i in query.split('&').filter(|x| !x.is_empty()) {
+        let (key, value) = match i.split_once('=') {
+            Some((k, v)) => (decode(k), Value::String(decode(v))),
+            None => (decode(i), Value::Null),
+        };
+
+        match out.get_mut(&key) {
+            Some(Value::Array(a)) => a.push(value),
+            Some(x) => *x = Value::Array(vec![std::mem::replace(x, Value::Null), value]),
+            None => {
+                out.insert(key, value);
+            }
+        }
+    }
+
+    Value::Object(out)
+}
+
+/// Parses an `application/x-www-form-urlencoded` request body into a [`Value::Object`].
+/// Works the same as [`parse_query`], but ignores surrounding whitespace like a trailing newline.
+/// Ex. `name=Ferris&tags=crab&tags=rust` => `{"name": "Ferris", "tags": ["crab", "rust"]}`
+pub fn parse_form(body: &str) -> Value {
+    parse_query(body.trim())
+}
+
+/// Errors that can occur while percent-decoding.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A `%` was not followed by two hex digits.
+    /// Contains the byte index of the `%`.
+    InvalidEscape(usize),
+    /// The decoded bytes were not valid UTF-8.
+    InvalidUtf8,
+}
+
+/// Decodes `%XX` escapes in a URL-encoded string.
+/// As in form encoding, `+` is decoded into a space.
+/// Ex. `hello%20world+!` => `hello world !`
+pub fn percent_decode(s: &str) -> Result<String, DecodeError> {
+    String::from_utf8(decode_bytes(s)?).map_err(|_| DecodeError::InvalidUtf8)
+}
+
+/// Like [`percent_decode`], but invalid escapes are left as is and invalid UTF-8 is replaced.
+fn decode(s: &str) -> String {
+    match decode_bytes(s) {
+        Ok(x) => String::from_utf8_lossy(&x).into_owned(),
+        // Everything before the first invalid escape decodes fine, so keep the `%` and carry on after it
+        Err(DecodeError::InvalidEscape(i)) => {
+            format!("{}%{}", decode(&s[..i]), decode(&s[i + 1..]))
+        }
+        Err(DecodeError::InvalidUtf8) => unreachable!(),
+    }
+}
+
+/// Decodes the escapes in `s` into bytes, without checking they are valid UTF-8.
+fn decode_bytes(s: &str) -> Result<Vec<u8>, DecodeError> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                out.push(hex_byte(bytes, i + 1).ok_or(DecodeError::InvalidEscape(i))?);
+                i += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            x => {
+                out.push(x);
+                i += 1;
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Parses the two hex digits starting at `i` into a byte.
+fn hex_byte(bytes: &[u8], i: usize) -> Option<u8> {
+    let hex = bytes.get(i..i + 2)?;
+    if !hex.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+
+    u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()
+}
+
+/// Decodes standard (RFC 4648) base64, with or without padding.
+fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    fn value(chr: u8) -> Option<u32> {
+        Some(match chr {
+            b'A'..=b'Z' => chr - b'A',
+            b'a'..=b'z' => chr - b'a' + 26,
+            b'0'..=b'9' => chr - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as u32)
+    }
+
+    let s = s.trim_end_matches('=').as_bytes();
+    if s.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.chunks(4) {
+        let mut bits = 0;
+        for (i, &chr) in chunk.iter().enumerate() {
+            bits |= value(chr)? << (18 - i * 6);
+        }
+
+        let bytes = bits.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+
+    Some(out)
+}
@@ -0,0 +1,163 @@
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+    },
+    thread::{self, JoinHandle},
+};
+
+use crate::{Level, LogTarget};
+
+const LEVELS: [Level; 6] = [
+    Level::Off,
+    Level::Error,
+    Level::Warn,
+    Level::Info,
+    Level::Debug,
+    Level::Trace,
+];
+
+/// What a [`BackgroundTarget`] does with lines logged while its queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueuePolicy {
+    /// Waits for space in the queue, so no lines are lost.
+    #[default]
+    Block,
+    /// Drops the line and counts it.
+    /// The count is written as its own line once there is space again, or on the next flush.
+    Drop,
+}
+
+enum Message {
+    Line(Level, String),
+    Flush(SyncSender<()>),
+}
+
+/// A target that hands lines to a background thread, which writes them to another target.
+/// Made for every target of a logger with [`Logger::buffered`](crate::Logger::buffered).
+///
+/// Lines are written in the order they were logged, and logging only waits if the queue is full with [`QueuePolicy::Block`].
+/// Dropping the target writes any lines still queued before returning.
+pub struct BackgroundTarget {
+    sender: Option<SyncSender<Message>>,
+    thread: Option<JoinHandle<()>>,
+    policy: QueuePolicy,
+    dropped: AtomicUsize,
+    // `is_terminal` for each level, as the inner target is owned by the background thread
+    terminal: [bool; 6],
+}
+
+impl BackgroundTarget {
+    /// Creates a new [`BackgroundTarget`] writing to `target`, with room for `capacity` lines waiting to be written.
+    pub fn new(target: impl LogTarget + 'static, capacity: usize, policy: QueuePolicy) -> Self {
+        let terminal = LEVELS.map(|x| target.is_terminal(x));
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let thread = thread::spawn(move || drain(target, receiver));
+
+        Self {
+            sender: Some(sender),
+            thread: Some(thread),
+            policy,
+            dropped: AtomicUsize::new(0),
+            terminal,
+        }
+    }
+
+    /// Gets the number of lines dropped because the queue was full that haven't been reported yet.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    fn send(&self, message: Message) -> io::Result<()> {
+        let sender = self.sender.as_ref().expect("sender is only taken on drop");
+        let result = match self.policy {
+            QueuePolicy::Block => sender.send(message).map_err(|_| ()),
+            QueuePolicy::Drop => match sender.try_send(message) {
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+                x => x.map_err(|_| ()),
+            },
+        };
+        result.map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "log thread stopped"))
+    }
+
+    // Sends a line with the number of dropped lines, if there are any
+    fn report_dropped(&self, block: bool) -> io::Result<()> {
+        let dropped = self.dropped.swap(0, Ordering::Relaxed);
+        if dropped == 0 {
+            return Ok(());
+        }
+
+        let note = Message::Line(
+            Level::Warn,
+            format!("{dropped} log lines dropped as the queue was full"),
+        );
+        let sender = self.sender.as_ref().expect("sender is only taken on drop");
+        match block {
+            true => sender.send(note).map_err(|_| ()),
+            false => sender.try_send(note).map_err(|_| ()),
+        }
+        .map_err(|_| {
+            // Try again later
+            self.dropped.fetch_add(dropped, Ordering::Relaxed);
+            io::Error::new(io::ErrorKind::WouldBlock, "log queue full")
+        })
+    }
+}
+
+fn drain(mut target: impl LogTarget, receiver: Receiver<Message>) {
+    for message in receiver {
+        // Errors can't be returned to the logger, so they are ignored like any other target's
+        let _ = match message {
+            Message::Line(level, line) => target.write_line(level, &line),
+            Message::Flush(done) => {
+                let _ = target.flush();
+                done.send(()).map_err(|_| io::ErrorKind::Other.into())
+            }
+        };
+    }
+    let _ = target.flush();
+}
+
+impl LogTarget for BackgroundTarget {
+    fn write_line(&mut self, level: Level, line: &str) -> io::Result<()> {
+        // The line is sent first, so a report of dropped lines can only use space left over after it
+        let dropped = self.dropped();
+        self.send(Message::Line(level, line.to_owned()))?;
+        if self.dropped() == dropped {
+            let _ = self.report_dropped(false);
+        }
+        Ok(())
+    }
+
+    fn is_terminal(&self, level: Level) -> bool {
+        self.terminal[level as usize]
+    }
+
+    /// Waits for every line queued so far to be written and the inner target to be flushed.
+    fn flush(&mut self) -> io::Result<()> {
+        self.report_dropped(true)?;
+        let (done, wait) = mpsc::sync_channel(1);
+        let sender = self.sender.as_ref().expect("sender is only taken on drop");
+        sender
+            .send(Message::Flush(done))
+            .ok()
+            .and_then(|_| wait.recv().ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "log thread stopped"))
+    }
+}
+
+impl Drop for BackgroundTarget {
+    fn drop(&mut self) {
+        let _ = self.report_dropped(true);
+
+        // Closing the channel lets the thread finish the queue and exit
+        drop(self.sender.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,29 @@
+/// When log lines are colored.
+/// Defaults to [`ColorMode::Auto`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Always color, even when not writing to a terminal.
+    Always,
+    /// Never color.
+    Never,
+    /// Color when writing to a terminal, unless the `NO_COLOR` environment variable is set.
+    /// Setting `CLICOLOR_FORCE` forces color on, even when not writing to a terminal.
+    #[default]
+    Auto,
+}
+
+impl ColorMode {
+    /// Decides if a line should be colored.
+    /// `is_terminal` is if the line is going to a terminal and `var` gets an environment variable.
+    pub(crate) fn enabled(self, is_terminal: bool, var: impl Fn(&str) -> Option<String>) -> bool {
+        // Both variables only count when set to something other than an empty string
+        let var = |name| var(name).filter(|x| !x.is_empty());
+
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto if var("CLICOLOR_FORCE").is_some_and(|x| x != "0") => true,
+            ColorMode::Auto => is_terminal && var("NO_COLOR").is_none(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::Level;
+
+/// Per-target levels, parsed from `RUST_LOG` style directives.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Filter {
+    /// The level for targets without a matching directive, if one was given.
+    pub default: Option<Level>,
+    /// Target prefixes with their levels.
+    pub directives: Vec<(String, Level)>,
+}
+
+impl Filter {
+    /// Parses comma separated directives.
+    /// Ex. `info,server::http=debug,json=off`
+    ///
+    /// Each directive is either a level for every target, a target to enable every level for, or `target=level`.
+    /// Invalid directives are ignored.
+    pub fn parse(s: &str) -> Self {
+        let mut out = Self::default();
+        for i in s.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            match i.split_once('=') {
+                Some((target, level)) => {
+                    if let (false, Ok(level)) = (target.trim().is_empty(), level.parse()) {
+                        out.directives.push((target.trim().to_owned(), level));
+                    }
+                }
+                None => match i.parse() {
+                    Ok(level) => out.default = Some(level),
+                    Err(_) => out.directives.push((i.to_owned(), Level::Trace)),
+                },
+            }
+        }
+
+        out
+    }
+
+    /// Gets the level for `target` from the directive with the longest matching prefix.
+    /// Prefixes only match whole path segments, so `json` matches `json::parser` but not `jsonc`.
+    pub fn level(&self, target: &str) -> Option<Level> {
+        let matches = |prefix: &str| {
+            target
+                .strip_prefix(prefix)
+                .is_some_and(|x| x.is_empty() || x.starts_with("::"))
+        };
+
+        self.directives
+            .iter()
+            .filter(|x| matches(&x.0))
+            .max_by_key(|x| x.0.len())
+            .map(|x| x.1)
+    }
+}
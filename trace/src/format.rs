@@ -0,0 +1,178 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use json::{SerializeOptions, Value};
+
+use crate::{Level, Style};
+
+/// How log lines are written.
+/// Defaults to [`LogFormat::Text`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human readable lines, with any fields added as `key=value` pairs.
+    /// Ex. `[INFO] request done status=200 user="ferris"`
+    #[default]
+    Text,
+    /// One JSON object per line, never colored.
+    /// Ex. `{"ts":"2024-01-02T03:04:05.678Z","level":"INFO","msg":"request done","fields":{"status":200,"user":"ferris"}}`
+    ///
+    /// A `target` key is also added for logs with a target.
+    Json,
+}
+
+/// Builds the fields for [`Logger::log_with`](crate::Logger::log_with) from `key => value` pairs, converting each value into a [`Value`].
+/// Ex. `kv!("status" => 200, "user" => "ferris")`
+#[macro_export]
+macro_rules! kv {
+    ($($key:expr => $value:expr),* $(,)?) => {
+        [$(($key, $crate::__json::Value::from($value))),*]
+    };
+}
+
+/// A formatter, turning a [`Record`] into a log line.
+/// Set with [`Logger::formatter`](crate::Logger::formatter), and shared with any [children](crate::Logger::child).
+pub type Formatter = Arc<dyn Fn(&Record) -> String + Send + Sync>;
+
+/// Everything about a single log, given to the [`Formatter`].
+#[derive(Debug, Clone)]
+pub struct Record<'a> {
+    /// The level the message was logged at.
+    pub level: Level,
+    /// Where the message came from, usually a module path, or empty if no target was given.
+    pub target: &'a str,
+    /// The message.
+    pub msg: &'a str,
+    /// Key-value fields attached to the message.
+    pub fields: &'a [(&'a str, Value)],
+    /// When the message was logged.
+    pub time: SystemTime,
+    /// Where in the source the message was logged, if known and the logger has [`Logger::source_location`](crate::Logger::source_location) on.
+    pub location: Option<Location>,
+    /// Whether the line should be colored, decided by the logger's [`ColorMode`](crate::ColorMode) and target.
+    /// Formatters can use [`Record::color`] and [`Record::reset`] to color parts of the line.
+    pub colored: bool,
+    /// The style for the record's level, set with [`Logger::style`](crate::Logger::style).
+    pub style: Style,
+}
+
+/// A place in the source code, captured by the logging macros.
+/// Ex. `Location { file: "src/http.rs", line: 142, module: "server::http" }`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// The file, as given by `file!()`.
+    pub file: &'static str,
+    /// The line in the file, as given by `line!()`.
+    pub line: u32,
+    /// The module path, as given by `module_path!()`.
+    pub module: &'static str,
+}
+
+impl Record<'_> {
+    /// Gets the escape code for the record's [`Style`], or an empty string if the line shouldn't be colored.
+    pub fn color(&self) -> String {
+        match self.colored {
+            true => self.style.to_string(),
+            false => String::new(),
+        }
+    }
+
+    /// Gets the code to reset the color, or an empty string if the line shouldn't be colored.
+    pub fn reset(&self) -> &'static str {
+        match self.colored {
+            true => "\x1b[0m",
+            false => "",
+        }
+    }
+
+    /// Gets the level's name in its style, followed by the reset code, or just the name if the line shouldn't be colored.
+    /// Ex. `\x1b[1;31mERROR\x1b[0m`
+    pub fn styled_level(&self) -> String {
+        format!("{}{}{}", self.color(), self.level, self.reset())
+    }
+
+    /// Gets the time the record was logged as an RFC 3339 timestamp in UTC.
+    /// Ex. `2024-01-02T03:04:05.678Z`
+    pub fn timestamp(&self) -> String {
+        timestamp(self.time)
+    }
+}
+
+impl LogFormat {
+    /// Formats `record` in this format.
+    pub fn format(self, record: &Record) -> String {
+        match self {
+            LogFormat::Text => text(record),
+            LogFormat::Json => json(record),
+        }
+    }
+}
+
+/// Writes a log line in the [`LogFormat::Text`] format.
+fn text(record: &Record) -> String {
+    let fields = record.fields.iter().map(|(k, v)| format!(" {k}={v}"));
+    let location = record.location.map(|x| format!(" {}:{}", x.file, x.line));
+    format!(
+        "[{}{}] {}{}{}{}",
+        record.level.as_str(),
+        location.unwrap_or_default(),
+        record.color(),
+        record.msg,
+        record.reset(),
+        fields.collect::<String>()
+    )
+}
+
+/// Writes a log line in the [`LogFormat::Json`] format.
+fn json(record: &Record) -> String {
+    let mut line = vec![
+        ("ts", Value::from(record.timestamp())),
+        ("level", Value::from(record.level.as_str())),
+        ("msg", Value::from(record.msg)),
+        ("fields", Value::from_entries(record.fields.iter().cloned())),
+    ];
+    if !record.target.is_empty() {
+        line.push(("target", Value::from(record.target)));
+    }
+    if let Some(location) = record.location {
+        line.push(("file", Value::from(location.file)));
+        line.push(("line", Value::from(location.line)));
+    }
+
+    Value::from_entries(line).to_string_with(&SerializeOptions {
+        sort_keys: false,
+        key_order: ["ts", "level", "target", "file", "line", "msg", "fields"]
+            .map(Into::into)
+            .to_vec(),
+        ..Default::default()
+    })
+}
+
+/// Formats `time` as an RFC 3339 timestamp in UTC, with milliseconds.
+/// Ex. `2024-01-02T03:04:05.678Z`
+pub(crate) fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs) = (secs / 86400, secs % 86400);
+
+    // Converts days since the epoch to a date in the proleptic Gregorian calendar,
+    // from http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days as i64 + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        since_epoch.subsec_millis()
+    )
+}
@@ -0,0 +1,169 @@
+//! A global logger, used by the [`error!`](crate::error), [`warn!`](crate::warn), [`info!`](crate::info),
+//! [`debug!`](crate::debug) and [`trace!`](crate::trace) macros.
+//!
+//! Until [`init`] is called, a [`Logger::default`] is used.
+
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex, OnceLock, PoisonError,
+    },
+};
+
+use crate::{Level, Location, Logger};
+
+static LOGGER: OnceLock<Mutex<Logger>> = OnceLock::new();
+
+/// The most verbose level the global logger could log, kept outside of the lock so disabled levels can be checked cheaply.
+/// Starts at the level of [`Logger::default`].
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Debug as u8);
+
+fn logger() -> &'static Mutex<Logger> {
+    LOGGER.get_or_init(Default::default)
+}
+
+/// Sets the global logger, replacing any previous one.
+pub fn init(logger: Logger) {
+    LEVEL.store(logger.max_level() as u8, Ordering::Relaxed);
+    *self::logger()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = logger;
+}
+
+/// Sets the level of the global logger.
+pub fn set_level(level: Level) {
+    let mut logger = logger().lock().unwrap_or_else(PoisonError::into_inner);
+    logger.level = level;
+    LEVEL.store(logger.max_level() as u8, Ordering::Relaxed);
+}
+
+/// Writes out any lines the global logger's targets are holding on to, see [`Logger::flush`].
+/// The global logger is never dropped, so a [buffered](Logger::buffered) one should be flushed before the program exits.
+pub fn flush() -> io::Result<()> {
+    logger()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .flush()
+}
+
+/// Checks if messages at `level` could be logged by the global logger.
+/// With per-target [`Logger::filters`], this is true if the level is enabled for any target.
+pub fn enabled(level: Level) -> bool {
+    // The level is kept as a number so it can be atomic
+    level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Logs `msg` with `level` on the global logger.
+pub fn log(level: Level, msg: &str) {
+    log_target(level, "", msg);
+}
+
+/// Logs `msg` with `level` from `target` on the global logger.
+pub fn log_target(level: Level, target: &str, msg: &str) {
+    logger()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .log_target(level, target, msg);
+}
+
+/// Logs `msg` with `level` from `location` on the global logger, using its module as the target.
+/// This is what the macros use, with the place they are called from.
+/// They check [`enabled`] first, so the message is only formatted if it could be logged.
+pub fn log_at(level: Level, location: Location, msg: &str) {
+    logger()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .log_at(level, location, msg);
+}
+
+/// The most verbose level the logging macros can log at, set at compile time with cargo features.
+/// Calls above it are compiled out, so their arguments are never even evaluated.
+///
+/// With the `max_level_<level>` features, ex. `max_level_info`, this is that level.
+/// The `release_max_level_<level>` features set it for builds without debug assertions instead, taking priority over `max_level_*`.
+/// If several features are on, the least verbose level wins.
+/// Otherwise, this is [`Level::Trace`] and only the runtime level applies.
+pub const STATIC_MAX_LEVEL: Level = static_max_level();
+
+const fn static_max_level() -> Level {
+    // Returns the level of the first of the features that is enabled
+    macro_rules! first {
+        ($($feature:tt => $level:ident),*) => {
+            $(
+                if cfg!(feature = $feature) {
+                    return Level::$level;
+                }
+            )*
+        };
+    }
+
+    if cfg!(not(debug_assertions)) {
+        first!(
+            "release_max_level_off" => Off,
+            "release_max_level_error" => Error,
+            "release_max_level_warn" => Warn,
+            "release_max_level_info" => Info,
+            "release_max_level_debug" => Debug,
+            "release_max_level_trace" => Trace
+        );
+    }
+
+    first!(
+        "max_level_off" => Off,
+        "max_level_error" => Error,
+        "max_level_warn" => Warn,
+        "max_level_info" => Info,
+        "max_level_debug" => Debug
+    );
+    Level::Trace
+}
+
+/// Logs a `format!` style message with `level` on the global logger, using the current module as the target.
+/// Ex. `log!(Level::Info, "started on port {}", port)`
+/// Nothing is done, and the message isn't formatted, if the level is above [`STATIC_MAX_LEVEL`] or isn't [`enabled`].
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        // A constant check first, so calls above the static max level are compiled out
+        if level as u8 <= $crate::STATIC_MAX_LEVEL as u8 && $crate::enabled(level) {
+            let location = $crate::Location {
+                file: file!(),
+                line: line!(),
+                module: module_path!(),
+            };
+            $crate::log_at(level, location, &format!($($arg)+));
+        }
+    }};
+}
+
+/// Error log on the global logger. ([`Level::Error`])
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::Level::Error, $($arg)+) };
+}
+
+/// Warning log on the global logger. ([`Level::Warn`])
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::Level::Warn, $($arg)+) };
+}
+
+/// Info log on the global logger. ([`Level::Info`])
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::Level::Info, $($arg)+) };
+}
+
+/// Debug log on the global logger. ([`Level::Debug`])
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::Level::Debug, $($arg)+) };
+}
+
+/// Trace log on the global logger. ([`Level::Trace`])
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::Level::Trace, $($arg)+) };
+}
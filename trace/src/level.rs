@@ -1,21 +1,37 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
 /// Log levels.
 /// Used to control the verbosity of logging.
-/// The default log level is [`Level::Error`].
+/// The default log level is [`Level::Debug`], which shows everything but [`Level::Trace`].
 /// 
 /// | Level            | Description                       |
 /// |------------------|-----------------------------------|
 /// | [`Level::Off`]   | Disables all logging.             |
 /// | [`Level::Error`] | For critical errors.              |
+/// | [`Level::Warn`]  | For problems that aren't errors.  |
 /// | [`Level::Info`]  | For general information.          |
 /// | [`Level::Debug`] | For verbose debugging information.|
+/// | [`Level::Trace`] | For very chatty tracing output.   |
+///
+/// [`Level::Off`] is only meant for setting a logger's level.
+/// Messages logged at it are never written, whatever the logger's level.
+///
+/// Levels are ordered by verbosity, the same as their numbers, so `Off < Error < Warn < Info < Debug < Trace`.
+/// Ex. `level <= Level::Warn` checks if a level is a warning or an error.
 #[repr(u8)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[rustfmt::skip]
 pub enum Level {
     Off   = 0,
     Error = 1,
-    Info  = 2,
-    Debug = 3,
+    Warn  = 2,
+    Info  = 3,
+    Debug = 4,
+    Trace = 5,
 }
 
 impl Level {
@@ -24,19 +40,50 @@ impl Level {
         match self {
             Level::Off => "OFF",
             Level::Error => "ERROR",
+            Level::Warn => "WARN",
             Level::Info => "INFO",
             Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
         }
     }
+}
 
-    /// Gets a color code for the log level.
-    /// This is used to colorize the log output if color logging is enabled.
-    pub(super) fn get_color(&self) -> &'static str {
-        match self {
-            Level::Off => unreachable!(),
-            Level::Info => "\x1b[0m",
-            Level::Error => "\x1b[31m",
-            Level::Debug => "\x1b[36m",
-        }
+/// Parses a level from its name or number, ignoring case.
+/// Ex. `info`, `WARN` or `4` (for [`Level::Debug`])
+impl FromStr for Level {
+    type Err = ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_ascii_lowercase().as_str() {
+            "off" | "0" => Level::Off,
+            "error" | "1" => Level::Error,
+            "warn" | "2" => Level::Warn,
+            "info" | "3" => Level::Info,
+            "debug" | "4" => Level::Debug,
+            "trace" | "5" => Level::Trace,
+            _ => return Err(ParseLevelError),
+        })
     }
 }
+
+/// Writes the level's name, as used in log lines.
+/// Ex. `INFO`
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error parsing a [`Level`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLevelError;
+
+impl Display for ParseLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            "invalid log level, expected one of off, error, warn, info, debug, trace or 0-5",
+        )
+    }
+}
+
+impl Error for ParseLevelError {}
@@ -1,5 +1,34 @@
-mod logger;
+mod background;
+mod color;
+mod filter;
+mod format;
+mod global;
 mod level;
+mod limit;
+#[cfg(feature = "log-compat")]
+mod log_compat;
+mod logger;
+mod rotate;
+mod style;
+mod target;
+mod timer;
 
+pub use background::{BackgroundTarget, QueuePolicy};
+pub use color::ColorMode;
+pub use format::{Formatter, Location, LogFormat, Record};
+pub use global::{enabled, flush, init, log, log_at, log_target, set_level, STATIC_MAX_LEVEL};
+pub use level::{Level, ParseLevelError};
+#[cfg(feature = "log-compat")]
+pub use log_compat::install_log_bridge;
 pub use logger::Logger;
-pub use level::Level;
\ No newline at end of file
+pub use rotate::RotatingFileTarget;
+pub use style::{Style, Styles};
+pub use target::{Buffer, LogTarget, SplitTarget};
+pub use timer::TimeGuard;
+
+// Used by the `kv!` macro
+#[doc(hidden)]
+pub use json as __json;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,38 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+/// What [`Logger::log_once`](crate::Logger::log_once) and [`Logger::log_rate_limited`](crate::Logger::log_rate_limited) have seen.
+#[derive(Default)]
+pub(crate) struct Limits {
+    once: HashSet<String>,
+    // The last time each key was logged and how many messages were suppressed since
+    rate: HashMap<String, (Instant, usize)>,
+}
+
+impl Limits {
+    /// Checks if `key` hasn't been seen before, marking it as seen.
+    pub fn once(&mut self, key: &str) -> bool {
+        self.once.insert(key.to_owned())
+    }
+
+    /// Checks if a message for `key` should be logged at `now`.
+    /// Returns the number of messages suppressed since the last one if so, and counts this one as suppressed if not.
+    pub fn rate(&mut self, key: &str, interval: Duration, now: Instant) -> Option<usize> {
+        match self.rate.get_mut(key) {
+            Some((last, suppressed)) if now.saturating_duration_since(*last) < interval => {
+                *suppressed += 1;
+                None
+            }
+            Some((last, suppressed)) => {
+                *last = now;
+                Some(std::mem::take(suppressed))
+            }
+            None => {
+                self.rate.insert(key.to_owned(), (now, 0));
+                Some(0)
+            }
+        }
+    }
+}
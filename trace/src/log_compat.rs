@@ -0,0 +1,59 @@
+//! A bridge from the [`log`] crate's facade, so records from dependencies using `log::info!` and friends end up in a [`Logger`].
+//! Only available with the `log-compat` feature.
+
+use log::{LevelFilter, Log, Metadata, Record as LogRecord, SetLoggerError};
+
+use crate::{Level, Logger};
+
+struct LogBridge(Logger);
+
+/// Registers `logger` as the [`log`] crate's logger, forwarding its records with their targets.
+/// Ex. `trace::install_log_bridge(Logger::new().filters("info,hyper=warn"))`
+///
+/// The `log` max level is set to the most verbose level the logger could log.
+/// Lowering it later with [`log::set_max_level`] is respected, as the `log` macros check it first.
+/// Fails if a `log` logger has already been set.
+pub fn install_log_bridge(logger: Logger) -> Result<(), SetLoggerError> {
+    let max_level = logger.max_level();
+    log::set_logger(Box::leak(Box::new(LogBridge(logger))))?;
+    log::set_max_level(match max_level {
+        Level::Off => LevelFilter::Off,
+        Level::Error => LevelFilter::Error,
+        Level::Warn => LevelFilter::Warn,
+        Level::Info => LevelFilter::Info,
+        Level::Debug => LevelFilter::Debug,
+        Level::Trace => LevelFilter::Trace,
+    });
+    Ok(())
+}
+
+fn level(level: log::Level) -> Level {
+    match level {
+        log::Level::Error => Level::Error,
+        log::Level::Warn => Level::Warn,
+        log::Level::Info => Level::Info,
+        log::Level::Debug => Level::Debug,
+        log::Level::Trace => Level::Trace,
+    }
+}
+
+impl Log for LogBridge {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+            && self
+                .0
+                .enabled_target(level(metadata.level()), metadata.target())
+    }
+
+    fn log(&self, record: &LogRecord) {
+        if self.enabled(record.metadata()) {
+            let msg = record.args().to_string();
+            self.0
+                .log_target(level(record.level()), record.target(), &msg);
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.0.flush();
+    }
+}
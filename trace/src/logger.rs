@@ -1,50 +1,439 @@
-use crate::Level;
+use std::{
+    borrow::Cow,
+    fs::OpenOptions,
+    io,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant, SystemTime},
+};
 
+use json::Value;
+
+use crate::{
+    filter::Filter, limit::Limits, BackgroundTarget, ColorMode, Formatter, Level, Location,
+    LogFormat, LogTarget, QueuePolicy, Record, SplitTarget, Style, Styles, TimeGuard,
+};
+
+/// A logger, writing messages at or below its level to a [`LogTarget`].
+/// Built by chaining methods, ex. `Logger::new().level(Level::Info).color(false)`.
+///
+/// Loggers are `Send + Sync`, so one can be shared between threads in an `Arc`.
+/// Each line is formatted first and then written to the target in one call, while holding its lock,
+/// so lines from different threads never interleave.
 pub struct Logger {
-    level: u8,
-    color: bool,
+    pub(crate) level: Level,
+    color: ColorMode,
+    styles: Styles,
+    formatter: Formatter,
+    filter: Filter,
+    targets: Arc<Mutex<Vec<Sink>>>,
+    // Set for children, ex. `[req-42] [db] `
+    prefix: String,
+    fields: Vec<(String, Value)>,
+    location: bool,
+    limits: Arc<Mutex<Limits>>,
+    clock: Arc<dyn Fn() -> Instant + Send + Sync>,
+}
+
+/// A target along with the most verbose level it gets, and its own color mode if it has one.
+struct Sink {
+    target: Box<dyn LogTarget>,
+    level: Level,
+    color: Option<ColorMode>,
+}
+
+impl Sink {
+    fn new(target: impl LogTarget + 'static, level: Level, color: Option<ColorMode>) -> Self {
+        Self {
+            target: Box::new(target),
+            level,
+            color,
+        }
+    }
 }
 
 impl Default for Logger {
     fn default() -> Self {
         Self {
-            level: Level::Debug as u8,
-            color: true,
+            level: Level::Debug,
+            color: ColorMode::Auto,
+            styles: Styles::default(),
+            formatter: Arc::new(|x| LogFormat::Text.format(x)),
+            filter: Filter::default(),
+            targets: Arc::new(Mutex::new(vec![Sink::new(
+                io::stdout(),
+                Level::Trace,
+                None,
+            )])),
+            prefix: String::new(),
+            fields: Vec::new(),
+            location: false,
+            limits: Arc::default(),
+            clock: Arc::new(Instant::now),
         }
     }
 }
 
 impl Logger {
-    /// Creates a new [`Logger`] with level [`Level::Info`] and color enabled.
+    /// Creates a new [`Logger`] with level [`Level::Debug`], [`ColorMode::Auto`] and output to stdout.
     pub fn new() -> Self {
         Default::default()
     }
 
+    /// Creates a new [`Logger`] with the level read from the environment variable `var`.
+    /// Ex. `Logger::from_env("MY_APP_LOG", Level::Info)` with `MY_APP_LOG=debug`
+    /// Uses `default` if the variable isn't set or isn't a valid [`Level`].
+    pub fn from_env(var: &str, default: Level) -> Self {
+        let level = std::env::var(var).ok().and_then(|x| x.parse().ok());
+        Self::new().level(level.unwrap_or(default))
+    }
+
     /// Sets the log level to `level`
-    pub fn level(&mut self, level: Level) -> &mut Self {
-        self.level = level as u8;
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets per-target levels from `RUST_LOG` style directives, replacing any set before.
+    /// Ex. `info,server::http=debug,json=off`
+    ///
+    /// Directives are comma separated, and each is one of:
+    /// - `level`, which sets the logger's level, used for targets without a directive
+    /// - `target=level`, which sets the level for the target and any targets under it (`json` includes `json::parser`)
+    /// - `target`, which enables every level for the target
+    ///
+    /// The directive with the longest matching target wins, and invalid directives are ignored.
+    pub fn filters(mut self, directives: &str) -> Self {
+        self.filter = Filter::parse(directives);
+        match self.filter.default {
+            Some(level) => self.level(level),
+            None => self,
+        }
+    }
+
+    /// Sets per-target levels with [`Logger::filters`] from the environment variable `var`, if it is set.
+    /// Ex. `logger.filters_from_env("RUST_LOG")`
+    pub fn filters_from_env(self, var: &str) -> Self {
+        match std::env::var(var) {
+            Ok(directives) => self.filters(&directives),
+            Err(_) => self,
+        }
+    }
+
+    /// En/disables color in logging.
+    /// Same as setting [`ColorMode::Always`] or [`ColorMode::Never`] with [`Logger::color_mode`].
+    pub fn color(self, color: bool) -> Self {
+        self.color_mode(match color {
+            true => ColorMode::Always,
+            false => ColorMode::Never,
+        })
+    }
+
+    /// Sets when log lines are colored.
+    pub fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.color = mode;
+        self
+    }
+
+    /// Sets the style `level` is colored with, when lines are colored.
+    /// Ex. `logger.style(Level::Error, Style::new().red().bold())`
+    pub fn style(mut self, level: Level, style: Style) -> Self {
+        self.styles.set(level, style);
+        self
+    }
+
+    /// Sets the styles for every level at once, replacing any set before.
+    pub fn styles(mut self, styles: Styles) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Sets how log lines are written to one of the built-in formats, replacing any custom [`Logger::formatter`].
+    pub fn format(self, format: LogFormat) -> Self {
+        self.formatter(move |x| format.format(x))
+    }
+
+    /// Sets a custom formatter to turn records into log lines.
+    /// Ex. `logger.formatter(|x| format!("{} {:<5} {}", x.timestamp(), x.level.to_string(), x.msg))`
+    ///
+    /// Lines are only colored if the formatter uses [`Record::color`] and [`Record::reset`], or [`Record::styled_level`].
+    pub fn formatter(
+        mut self,
+        formatter: impl Fn(&Record) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.formatter = Arc::new(formatter);
+        self
+    }
+
+    /// Sets the clock used by [`Logger::log_rate_limited`], [`Instant::now`] by default.
+    /// Mostly useful for testing.
+    pub fn clock(mut self, clock: impl Fn() -> Instant + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// En/disables including where messages were logged from, for the logging macros and [`Logger::log_at`].
+    /// The built-in formats show it after the level, ex. `[DEBUG src/http.rs:142] request`, or as `file` and `line` keys in JSON.
+    /// Off by default.
+    pub fn source_location(mut self, location: bool) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Sets where log lines are written to, replacing any other targets.
+    /// Ex. `logger.target(std::io::stderr())`
+    pub fn target(mut self, target: impl LogTarget + 'static) -> Self {
+        let sink = Sink::new(target, Level::Trace, None);
+        self.targets = Arc::new(Mutex::new(vec![sink]));
+        self
+    }
+
+    /// Adds another target, which gets every line at or below `level` as well as the existing targets.
+    /// Ex. `Logger::new().target(io::stdout()).add_target(file, Level::Trace)`
+    ///
+    /// Lines are still only logged if enabled by the logger's own level and filters,
+    /// so the logger's level should be at least as verbose as its most verbose target.
+    /// A target failing to write doesn't stop the others getting the line.
+    pub fn add_target(self, target: impl LogTarget + 'static, level: Level) -> Self {
+        self.add_sink(Sink::new(target, level, None))
+    }
+
+    /// Like [`Logger::add_target`], but with a color mode just for this target, rather than the logger's.
+    /// Ex. `logger.add_target_with_color(file, Level::Debug, ColorMode::Never)`
+    pub fn add_target_with_color(
+        self,
+        target: impl LogTarget + 'static,
+        level: Level,
+        color: ColorMode,
+    ) -> Self {
+        self.add_sink(Sink::new(target, level, Some(color)))
+    }
+
+    fn add_sink(self, sink: Sink) -> Self {
+        self.targets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(sink);
         self
     }
 
-    //// En/disabled color in logging
-    pub fn color(&mut self, color: bool) -> &mut Self {
-        self.color = color;
+    /// En/disables sending errors and warnings to stderr, with everything else going to stdout.
+    /// This replaces any other target.
+    pub fn split_streams(self, split: bool) -> Self {
+        match split {
+            true => self.target(SplitTarget::new(io::stderr(), io::stdout())),
+            false => self.target(io::stdout()),
+        }
+    }
+
+    /// Moves writing to a background thread for each target, so logging doesn't wait on slow output.
+    /// Ex. `Logger::new().file("app.log")?.buffered(1024, QueuePolicy::Block)`
+    ///
+    /// Each target gets a queue with room for `capacity` lines, and `policy` decides what happens when it is full.
+    /// Lines from one thread are always written in the order they were logged.
+    /// Queued lines are written when the logger, and any [children](Logger::child), are dropped, or by [`Logger::flush`].
+    /// Targets added after this aren't buffered.
+    pub fn buffered(self, capacity: usize, policy: QueuePolicy) -> Self {
+        {
+            let mut sinks = self.targets.lock().unwrap_or_else(PoisonError::into_inner);
+            *sinks = std::mem::take(&mut *sinks)
+                .into_iter()
+                .map(|x| {
+                    let target = BackgroundTarget::new(x.target, capacity, policy);
+                    Sink::new(target, x.level, x.color)
+                })
+                .collect();
+        }
         self
     }
 
-    // Logs `msg` with `level` if that level or a lower one is enabled
+    /// Sets the target to the file at `path`, which is created if needed and appended to.
+    pub fn file(self, path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(self.target(file))
+    }
+
+    /// Creates a child logger that puts `[prefix]` before every message.
+    /// Ex. `logger.child("req-42").info("parsing body")` logs `[INFO] [req-42] parsing body`
+    ///
+    /// The child starts with a copy of this logger's level, filters, color and formatter, and shares its target.
+    /// Children of children have both prefixes, ex. `[req-42] [db] `.
+    /// The child is separate from then on, so changing the level of one doesn't change the other.
+    pub fn child(&self, prefix: &str) -> Logger {
+        self.child_with_fields(prefix, &[])
+    }
+
+    /// Like [`Logger::child`], but also adds `fields` to every message the child logs, before the message's own fields.
+    /// Ex. `logger.child_with_fields("req-42", &kv!("user" => "ferris"))`
+    pub fn child_with_fields(&self, prefix: &str, fields: &[(&str, Value)]) -> Logger {
+        let fields = fields.iter().map(|(k, v)| (k.to_string(), v.clone()));
+        Logger {
+            level: self.level,
+            color: self.color,
+            styles: self.styles,
+            formatter: self.formatter.clone(),
+            filter: self.filter.clone(),
+            targets: self.targets.clone(),
+            prefix: format!("{}[{prefix}] ", self.prefix),
+            fields: self.fields.iter().cloned().chain(fields).collect(),
+            location: self.location,
+            limits: self.limits.clone(),
+            clock: self.clock.clone(),
+        }
+    }
+
+    /// Writes out anything the targets are holding on to, waiting for any [buffered](Logger::buffered) lines to be written.
+    /// Every target is flushed, and the first error is returned.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut sinks = self.targets.lock().unwrap_or_else(PoisonError::into_inner);
+        sinks
+            .iter_mut()
+            .map(|x| x.target.flush())
+            .fold(Ok(()), io::Result::and)
+    }
+
+    /// Checks if messages at `level` would be logged.
+    /// A level is enabled if it is at or below the logger's level, and [`Level::Off`] is never enabled.
+    /// Per-target [`Logger::filters`] aren't used, see [`Logger::enabled_target`] for those.
+    pub fn enabled(&self, level: Level) -> bool {
+        level != Level::Off && level <= self.level
+    }
+
+    /// Checks if messages at `level` from `target` would be logged, using any per-target [`Logger::filters`].
+    pub fn enabled_target(&self, level: Level, target: &str) -> bool {
+        let max = self.filter.level(target).unwrap_or(self.level);
+        level != Level::Off && level <= max
+    }
+
+    /// Gets the most verbose level that could be logged for any target.
+    pub(crate) fn max_level(&self) -> Level {
+        let targets = self.filter.directives.iter().map(|x| x.1);
+        targets.fold(self.level, Level::max)
+    }
+
+    /// Logs `msg` with `level` if that level is enabled.
+    /// Nothing is logged with [`Level::Off`].
     pub fn log(&self, level: Level, msg: &str) {
-        if level as u8 > self.level {
+        self.log_target(level, "", msg);
+    }
+
+    /// Logs `msg` with `level` from `target`, usually a module path, if it is enabled for that target.
+    /// Ex. `logger.log_target(Level::Debug, "server::http", "GET /")`
+    pub fn log_target(&self, level: Level, target: &str, msg: &str) {
+        self.write(level, target, msg, &[], None);
+    }
+
+    /// Logs `msg` with `level` from `location`, using its module as the target.
+    /// Used by the logging macros, which capture the location where they are called.
+    pub fn log_at(&self, level: Level, location: Location, msg: &str) {
+        self.write(level, location.module, msg, &[], Some(location));
+    }
+
+    /// Logs `msg` with `level` and some key-value fields.
+    /// Ex. `logger.log_with(Level::Info, "request done", &kv!("status" => 200, "user" => "ferris"))`
+    pub fn log_with(&self, level: Level, msg: &str, fields: &[(&str, Value)]) {
+        self.write(level, "", msg, fields, None);
+    }
+
+    /// Logs the message returned by `msg` with `level`, only calling it if the level is enabled.
+    /// Ex. `logger.log_lazy(Level::Debug, || format!("state: {:?}", state))`
+    /// Useful when building the message is expensive, see also [`Logger::enabled`].
+    pub fn log_lazy(&self, level: Level, msg: impl FnOnce() -> String) {
+        if self.enabled_target(level, "") {
+            self.log(level, &msg());
+        }
+    }
+
+    /// Logs `msg` with `level` only the first time this is called with `key`.
+    /// Ex. `logger.log_once(Level::Warn, "config-fallback", "no config found, using defaults")`
+    /// Keys are remembered for the life of the logger and shared with its [children](Logger::child).
+    /// Nothing is remembered if the level isn't enabled.
+    pub fn log_once(&self, level: Level, key: &str, msg: &str) {
+        if self.enabled_target(level, "") && self.limits().once(key) {
+            self.log(level, msg);
+        }
+    }
+
+    /// Logs `msg` with `level` at most once every `interval` for each `key`.
+    /// Ex. `logger.log_rate_limited(Level::Error, "db", Duration::from_secs(5), "connection lost")`
+    /// Messages in between are suppressed and counted, and the next one logged has a note added,
+    /// ex. `connection lost (137 similar messages suppressed)`.
+    pub fn log_rate_limited(&self, level: Level, key: &str, interval: Duration, msg: &str) {
+        if !self.enabled_target(level, "") {
+            return;
+        }
+
+        match self.limits().rate(key, interval, (self.clock)()) {
+            Some(0) => self.log(level, msg),
+            Some(suppressed) => self.log(
+                level,
+                &format!("{msg} ({suppressed} similar messages suppressed)"),
+            ),
+            None => {}
+        }
+    }
+
+    fn limits(&self) -> MutexGuard<'_, Limits> {
+        self.limits.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Starts timing a scope, logging `"<label> took 12.3ms"` when the returned guard is dropped.
+    /// Ex. `let _timer = logger.time("load config");`
+    /// The time is logged at [`Level::Debug`] unless changed with [`TimeGuard::level`].
+    pub fn time(&self, label: &str) -> TimeGuard<'_> {
+        TimeGuard::new(self, label)
+    }
+
+    fn write(
+        &self,
+        level: Level,
+        target: &str,
+        msg: &str,
+        fields: &[(&str, Value)],
+        location: Option<Location>,
+    ) {
+        // `enabled_target` never enables `Level::Off`, so nothing is ever logged at it
+        if !self.enabled_target(level, target) {
             return;
         }
 
-        println!(
-            "[{}] {}{}{}",
-            level.as_str(),
-            if self.color { level.get_color() } else { "" },
-            msg,
-            if self.color { "\x1b[0m" } else { "" }
-        );
+        // Only children need to build a new message and fields
+        let msg = match self.prefix.is_empty() {
+            true => Cow::Borrowed(msg),
+            false => Cow::Owned(format!("{}{msg}", self.prefix)),
+        };
+        let fields = match self.fields.is_empty() {
+            true => Cow::Borrowed(fields),
+            false => {
+                let inherited = self.fields.iter().map(|(k, v)| (k.as_str(), v.clone()));
+                Cow::Owned(inherited.chain(fields.iter().cloned()).collect())
+            }
+        };
+
+        let mut record = Record {
+            level,
+            target,
+            msg: &msg,
+            fields: &fields,
+            time: SystemTime::now(),
+            location: location.filter(|_| self.location),
+            colored: false,
+            style: self.styles.get(level),
+        };
+
+        // Lines are formatted at most twice, once with color and once without, however many targets there are
+        let mut lines = [None, None];
+        let mut sinks = self.targets.lock().unwrap_or_else(PoisonError::into_inner);
+        for sink in sinks.iter_mut().filter(|x| level <= x.level) {
+            let color = sink.color.unwrap_or(self.color);
+            record.colored =
+                color.enabled(sink.target.is_terminal(level), |x| std::env::var(x).ok());
+            let line =
+                lines[record.colored as usize].get_or_insert_with(|| (self.formatter)(&record));
+
+            // Failing to log shouldn't take down the program, or stop the other targets getting the line
+            let _ = sink.target.write_line(level, line);
+        }
     }
 
     /// Error log. ([`Level::Error`])
@@ -52,13 +441,73 @@ impl Logger {
         self.log(Level::Error, msg.as_ref());
     }
 
+    /// Like [`Logger::error`], with key-value fields.
+    pub fn error_with(&self, msg: impl AsRef<str>, fields: &[(&str, Value)]) {
+        self.log_with(Level::Error, msg.as_ref(), fields);
+    }
+
+    /// Like [`Logger::error`], only calling `msg` if the level is enabled.
+    pub fn error_lazy(&self, msg: impl FnOnce() -> String) {
+        self.log_lazy(Level::Error, msg);
+    }
+
+    /// Warning log. ([`Level::Warn`])
+    pub fn warn(&self, msg: impl AsRef<str>) {
+        self.log(Level::Warn, msg.as_ref());
+    }
+
+    /// Like [`Logger::warn`], with key-value fields.
+    pub fn warn_with(&self, msg: impl AsRef<str>, fields: &[(&str, Value)]) {
+        self.log_with(Level::Warn, msg.as_ref(), fields);
+    }
+
+    /// Like [`Logger::warn`], only calling `msg` if the level is enabled.
+    pub fn warn_lazy(&self, msg: impl FnOnce() -> String) {
+        self.log_lazy(Level::Warn, msg);
+    }
+
     /// Info log. ([`Level::Info`])
     pub fn info(&self, msg: impl AsRef<str>) {
         self.log(Level::Info, msg.as_ref());
     }
 
+    /// Like [`Logger::info`], with key-value fields.
+    pub fn info_with(&self, msg: impl AsRef<str>, fields: &[(&str, Value)]) {
+        self.log_with(Level::Info, msg.as_ref(), fields);
+    }
+
+    /// Like [`Logger::info`], only calling `msg` if the level is enabled.
+    pub fn info_lazy(&self, msg: impl FnOnce() -> String) {
+        self.log_lazy(Level::Info, msg);
+    }
+
     /// Debug log. ([`Level::Debug`])
     pub fn debug(&self, msg: impl AsRef<str>) {
         self.log(Level::Debug, msg.as_ref());
     }
+
+    /// Like [`Logger::debug`], with key-value fields.
+    pub fn debug_with(&self, msg: impl AsRef<str>, fields: &[(&str, Value)]) {
+        self.log_with(Level::Debug, msg.as_ref(), fields);
+    }
+
+    /// Like [`Logger::debug`], only calling `msg` if the level is enabled.
+    pub fn debug_lazy(&self, msg: impl FnOnce() -> String) {
+        self.log_lazy(Level::Debug, msg);
+    }
+
+    /// Trace log. ([`Level::Trace`])
+    pub fn trace(&self, msg: impl AsRef<str>) {
+        self.log(Level::Trace, msg.as_ref());
+    }
+
+    /// Like [`Logger::trace`], with key-value fields.
+    pub fn trace_with(&self, msg: impl AsRef<str>, fields: &[(&str, Value)]) {
+        self.log_with(Level::Trace, msg.as_ref(), fields);
+    }
+
+    /// Like [`Logger::trace`], only calling `msg` if the level is enabled.
+    pub fn trace_lazy(&self, msg: impl FnOnce() -> String) {
+        self.log_lazy(Level::Trace, msg);
+    }
 }
@@ -0,0 +1,99 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{Level, LogTarget};
+
+/// A target that writes to a file, moving it aside once it gets too big.
+/// Ex. `RotatingFileTarget::new("app.log", 1024 * 1024, 3)`
+///
+/// When a line would push the file over `max_bytes`, `app.log` is renamed to `app.log.1`, `app.log.1` to `app.log.2` and so on,
+/// keeping at most `max_files` old files, and a new `app.log` is started.
+/// Lines are never split between files, so a line longer than `max_bytes` gets a file to itself.
+///
+/// Each line is written straight to the file, so nothing is held in memory waiting to be flushed.
+pub struct RotatingFileTarget {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingFileTarget {
+    /// Opens the log file at `path`, appending to it if it already exists.
+    pub fn new(path: impl AsRef<Path>, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            file: Some(file),
+            size,
+        })
+    }
+
+    /// Gets the path of the `n`th old log file.
+    /// Ex. `app.log.2`
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut path = OsString::from(&self.path);
+        path.push(format!(".{n}"));
+        path.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            // Shift every old file up by one, starting from the oldest so nothing is overwritten
+            for i in (1..self.max_files).rev() {
+                let from = self.rotated(i);
+                if from.exists() {
+                    fs::rename(from, self.rotated(i + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+
+        self.open()
+    }
+
+    fn open(&mut self) -> io::Result<()> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = file.metadata()?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+}
+
+impl LogTarget for RotatingFileTarget {
+    fn write_line(&mut self, _level: Level, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+
+        // Reopen the file if a previous rotation failed part way through
+        if self.file.is_none() {
+            self.open()?;
+        }
+
+        let mut buf = Vec::with_capacity(len as usize);
+        buf.extend_from_slice(line.as_bytes());
+        buf.push(b'\n');
+        self.file.as_mut().unwrap().write_all(&buf)?;
+        self.size += len;
+        Ok(())
+    }
+}
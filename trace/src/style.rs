@@ -0,0 +1,114 @@
+use std::fmt::{self, Display};
+
+use crate::Level;
+
+/// How a level's part of a log line looks on a terminal, written as an ANSI escape code.
+/// Built by chaining methods, ex. `Style::new().red().bold()`.
+///
+/// An empty [`Style::new`] writes the reset code, so the text is plain.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Style {
+    // The foreground color, as an offset from 30
+    color: Option<u8>,
+    bold: bool,
+    dim: bool,
+}
+
+macro_rules! impl_colors {
+    ($($name:ident => $code:expr),*) => {
+        $(
+            #[doc = concat!("Sets the text color to ", stringify!($name), ".")]
+            pub const fn $name(mut self) -> Self {
+                self.color = Some($code);
+                self
+            }
+        )*
+    };
+}
+
+impl Style {
+    /// Creates a new [`Style`] with no color, bold or dim.
+    pub const fn new() -> Self {
+        Self {
+            color: None,
+            bold: false,
+            dim: false,
+        }
+    }
+
+    impl_colors!(
+        black => 0,
+        red => 1,
+        green => 2,
+        yellow => 3,
+        blue => 4,
+        magenta => 5,
+        cyan => 6,
+        white => 7
+    );
+
+    /// Makes the text bold.
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Makes the text dim.
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+}
+
+/// Writes the escape code that turns the style on.
+/// Ex. `\x1b[1;31m` for `Style::new().red().bold()`
+impl Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let codes = [
+            self.bold.then_some(1),
+            self.dim.then_some(2),
+            self.color.map(|x| 30 + x),
+        ];
+        let codes = codes.into_iter().flatten().map(|x| x.to_string());
+
+        match codes.collect::<Vec<_>>().join(";") {
+            x if x.is_empty() => f.write_str("\x1b[0m"),
+            x => write!(f, "\x1b[{x}m"),
+        }
+    }
+}
+
+/// The [`Style`] for each [`Level`], set with [`Logger::style`](crate::Logger::style).
+/// Defaults to red errors, yellow warnings, plain info, cyan debug and dim trace.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Styles {
+    // Indexed by level, including `Level::Off` so levels can be used directly
+    styles: [Style; 6],
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Self {
+            styles: [
+                Style::new(),
+                Style::new().red(),
+                Style::new().yellow(),
+                Style::new(),
+                Style::new().cyan(),
+                Style::new().dim(),
+            ],
+        }
+    }
+}
+
+impl Styles {
+    /// Gets the style for `level`.
+    pub fn get(&self, level: Level) -> Style {
+        self.styles[level as usize]
+    }
+
+    /// Sets the style for `level`.
+    pub fn set(&mut self, level: Level, style: Style) {
+        self.styles[level as usize] = style;
+    }
+}
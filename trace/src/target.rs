@@ -0,0 +1,148 @@
+use std::{
+    fs::File,
+    io::{self, IsTerminal, Write},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::Level;
+
+/// Somewhere log lines are written to.
+///
+/// [`io::stdout`], [`io::stderr`] and [`File`]s can be used directly.
+pub trait LogTarget: Send {
+    /// Writes one formatted log line, without its trailing newline.
+    /// `level` is the level the line was logged at, for targets that treat levels differently.
+    fn write_line(&mut self, level: Level, line: &str) -> io::Result<()>;
+
+    /// Checks if lines logged at `level` go to a terminal, for [`ColorMode::Auto`](crate::ColorMode::Auto).
+    /// Defaults to false.
+    fn is_terminal(&self, _level: Level) -> bool {
+        false
+    }
+
+    /// Writes out anything the target is holding on to, ex. in a buffer or queue.
+    /// Called by [`Logger::flush`](crate::Logger::flush), and does nothing by default.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T: LogTarget + ?Sized> LogTarget for Box<T> {
+    fn write_line(&mut self, level: Level, line: &str) -> io::Result<()> {
+        (**self).write_line(level, line)
+    }
+
+    fn is_terminal(&self, level: Level) -> bool {
+        (**self).is_terminal(level)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+}
+
+macro_rules! impl_write_target {
+    ($($type:ty),*) => {
+        $(
+            impl LogTarget for $type {
+                // Written with one call, as `writeln!` can split the line across several writes
+                fn write_line(&mut self, _level: Level, line: &str) -> io::Result<()> {
+                    self.write_all(format!("{line}\n").as_bytes())
+                }
+
+                fn is_terminal(&self, _level: Level) -> bool {
+                    IsTerminal::is_terminal(self)
+                }
+
+                fn flush(&mut self) -> io::Result<()> {
+                    Write::flush(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_write_target!(io::Stdout, io::Stderr, File);
+
+/// A target that sends [`Level::Error`] and [`Level::Warn`] lines to one target and everything else to another.
+/// Ex. `SplitTarget::new(io::stderr(), io::stdout())`, which is what [`Logger::split_streams`](crate::Logger::split_streams) uses.
+pub struct SplitTarget {
+    errors: Box<dyn LogTarget>,
+    rest: Box<dyn LogTarget>,
+}
+
+impl SplitTarget {
+    /// Creates a new [`SplitTarget`], with errors and warnings going to `errors`.
+    pub fn new(errors: impl LogTarget + 'static, rest: impl LogTarget + 'static) -> Self {
+        Self {
+            errors: Box::new(errors),
+            rest: Box::new(rest),
+        }
+    }
+}
+
+impl SplitTarget {
+    fn target(&self, level: Level) -> &dyn LogTarget {
+        match level {
+            Level::Error | Level::Warn => self.errors.as_ref(),
+            _ => self.rest.as_ref(),
+        }
+    }
+}
+
+impl LogTarget for SplitTarget {
+    fn write_line(&mut self, level: Level, line: &str) -> io::Result<()> {
+        match level {
+            Level::Error | Level::Warn => self.errors.write_line(level, line),
+            _ => self.rest.write_line(level, line),
+        }
+    }
+
+    // Each stream is checked on its own, so piping stdout still leaves errors colored on the terminal
+    fn is_terminal(&self, level: Level) -> bool {
+        self.target(level).is_terminal(level)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Both are flushed even if the first fails
+        let errors = self.errors.flush();
+        self.rest.flush().and(errors)
+    }
+}
+
+/// A target that collects log output in memory.
+/// Clones share the same buffer, so one can be given to a [`Logger`](crate::Logger) and another kept to read the output.
+#[derive(Debug, Clone, Default)]
+pub struct Buffer {
+    inner: Arc<Mutex<Vec<u8>>>,
+}
+
+impl Buffer {
+    /// Creates a new empty [`Buffer`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Gets a copy of everything written so far.
+    pub fn contents(&self) -> Vec<u8> {
+        self.lock().clone()
+    }
+
+    /// Gets everything written so far, leaving the buffer empty.
+    pub fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl LogTarget for Buffer {
+    fn write_line(&mut self, _level: Level, line: &str) -> io::Result<()> {
+        let mut buf = self.lock();
+        buf.extend_from_slice(line.as_bytes());
+        buf.push(b'\n');
+        Ok(())
+    }
+}
@@ -0,0 +1,906 @@
+use std::{
+    cell::Cell,
+    str::FromStr,
+    sync::{mpsc, Arc},
+};
+
+use json::Value;
+
+use crate::{
+    BackgroundTarget, Buffer, ColorMode, Level, LogFormat, LogTarget, Logger, ParseLevelError,
+    QueuePolicy, RotatingFileTarget, SplitTarget, Style, Styles,
+};
+
+const LEVELS: [Level; 5] = [
+    Level::Error,
+    Level::Warn,
+    Level::Info,
+    Level::Debug,
+    Level::Trace,
+];
+
+#[test]
+fn test_level_filtering() {
+    let all = [
+        Level::Off,
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    for (i, max) in all.into_iter().enumerate() {
+        let logger = Logger::new().level(max);
+
+        let enabled = LEVELS.map(|x| logger.enabled(x));
+        let expected = std::array::from_fn::<_, 5, _>(|x| x < i);
+        assert_eq!(enabled, expected, "{max:?}");
+        assert!(!logger.enabled(Level::Off));
+    }
+}
+
+#[test]
+fn test_default_level() {
+    // Matches the docs on `Level` and `Logger::new`
+    for logger in [Logger::default(), Logger::new()] {
+        assert!(logger.enabled(Level::Debug));
+        assert!(!logger.enabled(Level::Trace));
+    }
+}
+
+#[test]
+fn test_log_off() {
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .level(Level::Trace)
+        .color(true)
+        .target(buffer.clone());
+    logger.log(Level::Off, "never");
+    logger.log_target(Level::Off, "app", "never");
+    logger.log_with(Level::Off, "never", &crate::kv!("a" => 1));
+    assert_eq!(buffer.contents(), b"");
+
+    // Off as the logger's level disables everything, including Off itself
+    let logger = Logger::new().level(Level::Off).target(buffer.clone());
+    logger.log(Level::Off, "never");
+    logger.error("never");
+    assert_eq!(buffer.contents(), b"");
+}
+
+#[test]
+fn test_level_names() {
+    assert_eq!(
+        LEVELS.map(|x| x.as_str()),
+        ["ERROR", "WARN", "INFO", "DEBUG", "TRACE"]
+    );
+    assert_eq!(Styles::default().get(Level::Warn).to_string(), "\x1b[33m");
+    assert_eq!(Level::Warn as u8, 2);
+    assert_eq!(Level::Trace as u8, 5);
+}
+
+// The global logger is shared between tests, so it is only touched here
+#[test]
+fn test_global_macros() {
+    // Macros above a static max level set by features are compiled out, which is covered by `test_static_max_level`
+    if crate::STATIC_MAX_LEVEL != Level::Trace {
+        return;
+    }
+
+    // Falls back to the default logger before `init`
+    assert!(crate::enabled(Level::Debug));
+    assert!(!crate::enabled(Level::Trace));
+
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .level(Level::Warn)
+        .color(false)
+        .target(buffer.clone());
+    crate::init(logger);
+    assert!(crate::enabled(Level::Warn));
+    assert!(!crate::enabled(Level::Info));
+
+    // Arguments are not evaluated for disabled levels
+    let formatted = Cell::new(0);
+    let count = || {
+        formatted.set(formatted.get() + 1);
+        formatted.get()
+    };
+    crate::info!("skipped {}", count());
+    crate::debug!("skipped {}", count());
+    crate::trace!("skipped {}", count());
+    crate::warn!("logged {}", count());
+    crate::error!("logged {}", count());
+    assert_eq!(formatted.get(), 2);
+    assert_eq!(buffer.take(), b"[WARN] logged 1\n[ERROR] logged 2\n");
+
+    crate::set_level(Level::Off);
+    assert!(LEVELS.iter().all(|&x| !crate::enabled(x)));
+    crate::error!("skipped {}", count());
+    assert_eq!(formatted.get(), 2);
+    assert!(buffer.contents().is_empty());
+
+    // The macros capture where they are called from
+    crate::init(
+        Logger::new()
+            .color(false)
+            .source_location(true)
+            .target(buffer.clone()),
+    );
+    let line = line!() + 1;
+    crate::info!("located");
+    assert_eq!(
+        String::from_utf8(buffer.take()).unwrap(),
+        format!("[INFO {}:{line}] located\n", file!())
+    );
+    crate::init(Logger::new().color(false).target(buffer.clone()));
+    crate::info!("not located");
+    assert_eq!(buffer.take(), b"[INFO] not located\n");
+}
+
+#[test]
+fn test_source_location() {
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .color(false)
+        .format(LogFormat::Json)
+        .source_location(true)
+        .target(buffer.clone());
+    let location = crate::Location {
+        file: "src/http.rs",
+        line: 142,
+        module: "server::http",
+    };
+
+    logger.log_at(Level::Debug, location, "request");
+    logger.info("no location");
+    let lines = String::from_utf8(buffer.take()).unwrap();
+    let lines = lines
+        .lines()
+        .map(|x| Value::from_str(x).unwrap())
+        .collect::<Vec<_>>();
+    let get = |i: usize, key| lines[i].pointer(key).cloned();
+    assert_eq!(get(0, "/target"), Some(Value::from("server::http")));
+    assert_eq!(get(0, "/file"), Some(Value::from("src/http.rs")));
+    assert_eq!(get(0, "/line"), Some(Value::from(142)));
+    assert_eq!(get(1, "/file"), None);
+
+    // Custom formatters get the location too
+    let logger = logger.formatter(|x| format!("{:?} {}", x.location.map(|x| x.line), x.msg));
+    logger.log_at(Level::Warn, location, "custom");
+    assert_eq!(buffer.take(), b"Some(142) custom\n");
+}
+
+#[test]
+fn test_buffer_target() {
+    let buffer = Buffer::new();
+    let mut logger = Logger::new().target(buffer.clone()).color(false);
+
+    logger.info("hello");
+    logger.error(format!("code {}", 5));
+    logger.trace("hidden");
+    assert_eq!(buffer.take(), b"[INFO] hello\n[ERROR] code 5\n");
+
+    logger = logger.level(Level::Trace).color(true);
+    logger.warn("careful");
+    logger.trace("chatty");
+    assert_eq!(
+        String::from_utf8(buffer.contents()).unwrap(),
+        "[WARN] \x1b[33mcareful\x1b[0m\n[TRACE] \x1b[2mchatty\x1b[0m\n"
+    );
+}
+
+#[test]
+fn test_file_target() {
+    let path = std::env::temp_dir().join(format!("trace-test-{}.log", std::process::id()));
+    std::fs::write(&path, "existing\n").unwrap();
+
+    let logger = Logger::new().file(&path).unwrap().color(false);
+    logger.info("appended");
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(contents, "existing\n[INFO] appended\n");
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("trace-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_rotating_file() {
+    let dir = temp_dir("rotate");
+    let path = dir.join("app.log");
+
+    // Each line is 15 bytes, so two fit in a file
+    let logger = Logger::new()
+        .color(false)
+        .target(RotatingFileTarget::new(&path, 30, 2).unwrap());
+    for i in 1..=7 {
+        logger.info(format!("line {i:02}"));
+    }
+
+    let read = |x: &str| std::fs::read_to_string(dir.join(x)).unwrap();
+    assert_eq!(read("app.log"), "[INFO] line 07\n");
+    assert_eq!(read("app.log.1"), "[INFO] line 05\n[INFO] line 06\n");
+    assert_eq!(read("app.log.2"), "[INFO] line 03\n[INFO] line 04\n");
+    assert!(!dir.join("app.log.3").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_rotating_file_keeps_lines() {
+    let dir = temp_dir("rotate-keep");
+    let path = dir.join("app.log");
+    std::fs::write(&path, "existing\n").unwrap();
+
+    let logger = Logger::new()
+        .color(false)
+        .target(RotatingFileTarget::new(&path, 40, 20).unwrap());
+    let lines = (0..20)
+        .map(|x| format!("[INFO] {}", "x".repeat(x)))
+        .collect::<Vec<_>>();
+    for i in &lines {
+        logger.info(&i[7..]);
+    }
+
+    let mut files = vec![std::fs::read_to_string(&path).unwrap()];
+    for i in 1.. {
+        match std::fs::read_to_string(dir.join(format!("app.log.{i}"))) {
+            Ok(x) => files.push(x),
+            Err(_) => break,
+        }
+    }
+    assert!(files.len() > 2);
+    assert!(files[..files.len() - 1].iter().all(|x| x.len() <= 40));
+
+    let all = files.into_iter().rev().collect::<String>();
+    assert_eq!(all, format!("existing\n{}\n", lines.join("\n")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_split_target() {
+    let (errors, rest) = (Buffer::new(), Buffer::new());
+    let logger = Logger::new()
+        .level(Level::Trace)
+        .color(false)
+        .target(SplitTarget::new(errors.clone(), rest.clone()));
+
+    logger.error("a");
+    logger.info("b");
+    logger.warn("c");
+    logger.debug("d");
+    logger.trace("e");
+
+    assert_eq!(errors.contents(), b"[ERROR] a\n[WARN] c\n");
+    assert_eq!(rest.contents(), b"[INFO] b\n[DEBUG] d\n[TRACE] e\n");
+}
+
+#[test]
+fn test_color_mode() {
+    let env = |vars: &'static [(&str, &str)]| {
+        move |name: &str| vars.iter().find(|x| x.0 == name).map(|x| x.1.to_owned())
+    };
+
+    assert!(ColorMode::Auto.enabled(true, env(&[])));
+    assert!(!ColorMode::Auto.enabled(false, env(&[])));
+    assert!(!ColorMode::Auto.enabled(true, env(&[("NO_COLOR", "1")])));
+    assert!(ColorMode::Auto.enabled(true, env(&[("NO_COLOR", "")])));
+    assert!(ColorMode::Auto.enabled(false, env(&[("CLICOLOR_FORCE", "1")])));
+    assert!(!ColorMode::Auto.enabled(false, env(&[("CLICOLOR_FORCE", "0")])));
+    assert!(ColorMode::Auto.enabled(false, env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")])));
+
+    assert!(ColorMode::Always.enabled(false, env(&[("NO_COLOR", "1")])));
+    assert!(!ColorMode::Never.enabled(true, env(&[("CLICOLOR_FORCE", "1")])));
+    assert_eq!(ColorMode::default(), ColorMode::Auto);
+}
+
+#[test]
+fn test_styles() {
+    assert_eq!(Style::new().to_string(), "\x1b[0m");
+    assert_eq!(Style::new().red().bold().to_string(), "\x1b[1;31m");
+    assert_eq!(Style::new().dim().blue().to_string(), "\x1b[2;34m");
+
+    let buffer = Buffer::new();
+    let mut logger = Logger::new()
+        .target(buffer.clone())
+        .color(true)
+        .style(Level::Error, Style::new().red().bold())
+        .style(Level::Info, Style::new().green());
+    logger.error("failed");
+    logger.info("started");
+    logger.warn("unchanged");
+    assert_eq!(
+        String::from_utf8(buffer.take()).unwrap(),
+        "[ERROR] \x1b[1;31mfailed\x1b[0m\n[INFO] \x1b[32mstarted\x1b[0m\n\
+         [WARN] \x1b[33munchanged\x1b[0m\n"
+    );
+
+    // Custom formatters get the styled level, which is plain without color
+    logger = logger.formatter(|x| format!("{} {}", x.styled_level(), x.msg));
+    logger.error("custom");
+    assert_eq!(buffer.take(), b"\x1b[1;31mERROR\x1b[0m custom\n");
+
+    logger = logger.color_mode(ColorMode::Never);
+    logger.error("custom");
+    logger = logger.format(LogFormat::Text);
+    logger.error("plain");
+    assert_eq!(buffer.take(), b"ERROR custom\n[ERROR] plain\n");
+
+    // Children keep the styles
+    let child = logger.color(true).child("db");
+    child.error("lost");
+    assert_eq!(buffer.take(), b"[ERROR] \x1b[1;31m[db] lost\x1b[0m\n");
+}
+
+#[test]
+fn test_color_auto_buffer() {
+    // Buffers aren't terminals, so `Auto` leaves them uncolored
+    let buffer = Buffer::new();
+    let logger = Logger::new().target(buffer.clone());
+    logger.error("plain");
+    assert_eq!(buffer.contents(), b"[ERROR] plain\n");
+}
+
+#[test]
+fn test_level_from_str() {
+    let names = ["off", "error", "warn", "info", "debug", "trace"];
+    for (i, name) in names.iter().enumerate() {
+        let level = name.parse::<Level>().unwrap();
+        assert_eq!(level as usize, i);
+        assert_eq!(name.to_uppercase().parse::<Level>().unwrap() as usize, i);
+        assert_eq!(i.to_string().parse::<Level>().unwrap() as usize, i);
+        assert_eq!(level.to_string().parse::<Level>().unwrap() as usize, i);
+    }
+
+    assert_eq!(" Info ".parse::<Level>().unwrap() as u8, Level::Info as u8);
+    assert_eq!(Level::Warn.to_string(), "WARN");
+    for i in ["", "verbose", "6", "-1"] {
+        assert_eq!(i.parse::<Level>().unwrap_err(), ParseLevelError, "{i}");
+    }
+    assert!(ParseLevelError.to_string().contains("debug"));
+}
+
+#[test]
+fn test_level_ord() {
+    let all = [
+        Level::Off,
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+    for (i, a) in all.iter().enumerate() {
+        for (j, b) in all.iter().enumerate() {
+            assert_eq!(a.cmp(b), i.cmp(&j), "{a} {b}");
+            assert_eq!(a == b, i == j);
+        }
+    }
+    assert!(Level::Error <= Level::Warn && Level::Trace > Level::Debug);
+    assert_eq!(Level::Off.max(Level::Info), Level::Info);
+
+    let sorted = [Level::Debug, Level::Error, Level::Trace, Level::Off]
+        .into_iter()
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(
+        sorted.into_iter().collect::<Vec<_>>(),
+        [Level::Off, Level::Error, Level::Debug, Level::Trace]
+    );
+
+    assert_eq!(
+        all.map(|x| x.to_string()),
+        ["OFF", "ERROR", "WARN", "INFO", "DEBUG", "TRACE"]
+    );
+    for level in all {
+        assert_eq!(level.to_string().parse::<Level>(), Ok(level));
+    }
+}
+
+#[test]
+fn test_logger_from_env() {
+    std::env::set_var("TRACE_TEST_LOG_SET", "trace");
+    std::env::set_var("TRACE_TEST_LOG_INVALID", "loud");
+
+    let logger = Logger::from_env("TRACE_TEST_LOG_SET", Level::Error);
+    assert!(logger.enabled(Level::Trace));
+
+    let logger = Logger::from_env("TRACE_TEST_LOG_INVALID", Level::Error);
+    assert!(logger.enabled(Level::Error));
+    assert!(!logger.enabled(Level::Warn));
+
+    let logger = Logger::from_env("TRACE_TEST_LOG_UNSET", Level::Info);
+    assert!(logger.enabled(Level::Info));
+    assert!(!logger.enabled(Level::Debug));
+}
+
+#[test]
+fn test_filters() {
+    let logger =
+        Logger::new().filters("warn, server::http=debug,json=off, db ,server=info,bad=loud,=trace");
+
+    let cases = [
+        ("app", Level::Warn, true),
+        ("app", Level::Info, false),
+        ("server", Level::Info, true),
+        ("server", Level::Debug, false),
+        ("server::db", Level::Info, true),
+        ("server::http", Level::Debug, true),
+        ("server::http::router", Level::Debug, true),
+        ("server::http", Level::Trace, false),
+        ("server::https", Level::Debug, false),
+        ("json", Level::Error, false),
+        ("json::parser", Level::Error, false),
+        ("jsonc", Level::Error, true),
+        ("db", Level::Trace, true),
+        ("bad", Level::Info, false),
+        ("", Level::Warn, true),
+    ];
+    for (target, level, enabled) in cases {
+        assert_eq!(
+            logger.enabled_target(level, target),
+            enabled,
+            "{target} {level}"
+        );
+    }
+    assert!(logger.enabled(Level::Warn));
+    assert!(!logger.enabled(Level::Info));
+}
+
+#[test]
+fn test_filters_output() {
+    let buffer = Buffer::new();
+    let mut logger = Logger::new()
+        .target(buffer.clone())
+        .filters("error,noisy=off,server=debug");
+
+    logger.log_target(Level::Debug, "server::http", "request");
+    logger.log_target(Level::Error, "noisy::dep", "hidden");
+    logger.log_target(Level::Info, "other", "hidden");
+    logger.log_target(Level::Error, "other", "failed");
+    assert_eq!(buffer.contents(), b"[DEBUG] request\n[ERROR] failed\n");
+
+    std::env::set_var("TRACE_TEST_FILTERS", "trace");
+    logger = logger.filters_from_env("TRACE_TEST_FILTERS_UNSET");
+    assert!(!logger.enabled(Level::Trace));
+    logger = logger.filters_from_env("TRACE_TEST_FILTERS");
+    assert!(logger.enabled_target(Level::Trace, "noisy"));
+}
+
+#[test]
+fn test_text_fields() {
+    let buffer = Buffer::new();
+    let logger = Logger::new().target(buffer.clone()).color(false);
+
+    logger.info_with(
+        "request done",
+        &crate::kv!("status" => 200, "path" => "a b"),
+    );
+    logger.error_with("no fields", &[]);
+    assert_eq!(
+        String::from_utf8(buffer.contents()).unwrap(),
+        "[INFO] request done status=200 path=\"a b\"\n[ERROR] no fields\n"
+    );
+}
+
+#[test]
+fn test_json_format() {
+    let buffer = Buffer::new();
+    let logger = Logger::new().target(buffer.clone()).format(LogFormat::Json);
+
+    let fields = crate::kv!("status" => 200, "tags" => vec!["a", "b"], "ok" => true);
+    logger.warn_with("request \"done\"", &fields);
+    logger.log_target(Level::Info, "server::http", "plain");
+
+    let out = String::from_utf8(buffer.contents()).unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(r#"{"ts":""#));
+
+    let record = Value::from_str(lines[0]).unwrap();
+    assert_eq!(record.pointer("/level"), Some(&Value::from("WARN")));
+    assert_eq!(
+        record.pointer("/msg"),
+        Some(&Value::from("request \"done\""))
+    );
+    assert_eq!(
+        record.pointer("/fields").unwrap().to_string(),
+        r#"{"ok":true,"status":200,"tags":["a","b"]}"#
+    );
+    assert!(record.pointer("/target").is_none());
+
+    let ts = record.pointer("/ts").unwrap().as_string().unwrap();
+    assert_eq!(ts.len(), "2024-01-02T03:04:05.678Z".len());
+    assert!(ts.ends_with('Z'));
+
+    let record = Value::from_str(lines[1]).unwrap();
+    assert_eq!(
+        record.pointer("/target"),
+        Some(&Value::from("server::http"))
+    );
+    assert_eq!(
+        record.pointer("/fields"),
+        Some(&Value::from([] as [(&str, i32); 0]))
+    );
+}
+
+#[test]
+fn test_timestamp() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let time = |secs: u64, millis: u64| {
+        crate::format::timestamp(UNIX_EPOCH + Duration::from_millis(secs * 1000 + millis))
+    };
+    assert_eq!(time(0, 0), "1970-01-01T00:00:00.000Z");
+    assert_eq!(time(951_782_400, 5), "2000-02-29T00:00:00.005Z");
+    assert_eq!(time(1_704_164_645, 678), "2024-01-02T03:04:05.678Z");
+    assert_eq!(time(4_102_444_799, 999), "2099-12-31T23:59:59.999Z");
+}
+
+#[test]
+fn test_custom_formatter() {
+    let buffer = Buffer::new();
+    let mut logger = Logger::new()
+        .target(buffer.clone())
+        .color(true)
+        .formatter(|x| {
+            let fields = x.fields.iter().map(|(k, v)| format!("{k}:{v}"));
+            format!(
+                "{}{:<5}{} [{}] {} {}",
+                x.color(),
+                x.level.to_string(),
+                x.reset(),
+                x.target,
+                x.msg,
+                fields.collect::<Vec<_>>().join(",")
+            )
+        });
+
+    logger.log_target(Level::Warn, "server", "slow");
+    logger.info_with("done", &crate::kv!("a" => 1, "b" => "x"));
+    logger = logger.color(false);
+    logger.error("plain");
+    assert_eq!(
+        String::from_utf8(buffer.take()).unwrap(),
+        "\x1b[33mWARN \x1b[0m [server] slow \n\x1b[0mINFO \x1b[0m [] done a:1,b:\"x\"\n\
+         ERROR [] plain \n"
+    );
+
+    // Going back to a built-in format replaces the formatter
+    logger = logger.format(LogFormat::Text);
+    logger.info("text");
+    assert_eq!(buffer.take(), b"[INFO] text\n");
+}
+
+#[test]
+fn test_shared_logger() {
+    fn assert_shareable<T: Send + Sync>() {}
+    assert_shareable::<Logger>();
+
+    let buffer = Buffer::new();
+    let logger = Arc::new(Logger::new().target(buffer.clone()).color(false));
+    let threads = (0..8)
+        .map(|thread| {
+            let logger = logger.clone();
+            std::thread::spawn(move || {
+                for i in 0..500 {
+                    logger.info_with("message", &crate::kv!("thread" => thread, "i" => i));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    threads.into_iter().for_each(|x| x.join().unwrap());
+
+    let contents = String::from_utf8(buffer.take()).unwrap();
+    let mut next = [0; 8];
+    for line in contents.lines() {
+        let (thread, i) = line
+            .strip_prefix("[INFO] message thread=")
+            .and_then(|x| x.split_once(" i="))
+            .unwrap_or_else(|| panic!("partial line: {line:?}"));
+        let thread = thread.parse::<usize>().unwrap();
+        assert_eq!(i.parse::<usize>().unwrap(), next[thread]);
+        next[thread] += 1;
+    }
+    assert_eq!(next, [500; 8]);
+}
+
+#[test]
+fn test_lazy_messages() {
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .level(Level::Info)
+        .color(false)
+        .target(buffer.clone());
+
+    let called = Cell::new(false);
+    let msg = |text: &str| {
+        called.set(true);
+        text.to_owned()
+    };
+
+    logger.debug_lazy(|| msg("hidden"));
+    logger.log_lazy(Level::Trace, || msg("hidden"));
+    assert!(!called.get());
+    assert!(!logger.enabled(Level::Debug));
+
+    logger.warn_lazy(|| msg("shown"));
+    assert!(called.get());
+    assert_eq!(buffer.contents(), b"[WARN] shown\n");
+}
+
+#[test]
+fn test_timer() {
+    let buffer = Buffer::new();
+    let logger = Logger::new().color(false).target(buffer.clone());
+
+    {
+        let _outer = logger.time("outer").level(Level::Info);
+        let inner = logger.time("inner");
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        inner.finish();
+        let _hidden = logger.time("hidden").level(Level::Trace);
+    }
+
+    let contents = String::from_utf8(buffer.take()).unwrap();
+    let lines = contents.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2, "{contents}");
+
+    // Finishing logs immediately, so the inner timer comes first and isn't logged again on drop
+    let inner = lines[0].strip_prefix("[DEBUG]   inner took ").unwrap();
+    let outer = lines[1].strip_prefix("[INFO] outer took ").unwrap();
+    for time in [inner, outer] {
+        assert!(time.strip_suffix("ms").unwrap().parse::<f64>().unwrap() >= 2.0);
+    }
+
+    // Timers on their own aren't indented
+    drop(logger.time("again"));
+    assert!(buffer.contents().starts_with(b"[DEBUG] again took "));
+}
+
+#[test]
+fn test_child_logger() {
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .level(Level::Info)
+        .color(false)
+        .target(buffer.clone());
+
+    let request = logger.child("req-42");
+    let db = request.child_with_fields("db", &crate::kv!("pool" => 1));
+    request.info("parsing body");
+    request.debug("hidden");
+    db.warn_with("slow query", &crate::kv!("ms" => 250));
+    logger.info("parent");
+    assert_eq!(
+        String::from_utf8(buffer.take()).unwrap(),
+        "[INFO] [req-42] parsing body\n[WARN] [req-42] [db] slow query pool=1 ms=250\n[INFO] parent\n"
+    );
+
+    // Children copy the parent's settings when created, so later changes don't reach them
+    let logger = logger.level(Level::Error);
+    request.info("still shown");
+    logger.child("new").info("hidden");
+    let request = request.level(Level::Trace);
+    request.trace("chatty");
+    logger.debug("hidden");
+    assert_eq!(
+        buffer.take(),
+        b"[INFO] [req-42] still shown\n[TRACE] [req-42] chatty\n"
+    );
+}
+
+#[test]
+fn test_log_once() {
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .level(Level::Info)
+        .color(false)
+        .target(buffer.clone());
+
+    logger.log_once(Level::Debug, "a", "disabled");
+    for _ in 0..3 {
+        logger.log_once(Level::Warn, "a", "first a");
+        logger.log_once(Level::Info, "b", "first b");
+    }
+    logger.child("child").log_once(Level::Warn, "a", "shared");
+    assert_eq!(buffer.take(), b"[WARN] first a\n[INFO] first b\n");
+}
+
+#[test]
+fn test_log_rate_limited() {
+    let start = std::time::Instant::now();
+    let now = Arc::new(std::sync::Mutex::new(start));
+    let clock = now.clone();
+
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .color(false)
+        .target(buffer.clone())
+        .clock(move || *clock.lock().unwrap());
+    let interval = std::time::Duration::from_secs(5);
+    let advance = |secs| *now.lock().unwrap() = start + std::time::Duration::from_secs(secs);
+
+    for _ in 0..138 {
+        logger.log_rate_limited(Level::Error, "db", interval, "connection lost");
+    }
+    logger.log_rate_limited(Level::Error, "other", interval, "other key");
+    advance(4);
+    logger.log_rate_limited(Level::Error, "db", interval, "connection lost");
+    assert_eq!(
+        buffer.take(),
+        b"[ERROR] connection lost\n[ERROR] other key\n"
+    );
+
+    advance(5);
+    logger.log_rate_limited(Level::Error, "db", interval, "connection lost");
+    advance(9);
+    logger.log_rate_limited(Level::Error, "db", interval, "connection lost");
+    advance(10);
+    logger.log_rate_limited(Level::Error, "db", interval, "connection lost");
+    assert_eq!(
+        String::from_utf8(buffer.take()).unwrap(),
+        "[ERROR] connection lost (138 similar messages suppressed)\n\
+         [ERROR] connection lost (1 similar messages suppressed)\n"
+    );
+
+    advance(20);
+    logger.log_rate_limited(Level::Error, "db", interval, "connection lost");
+    assert_eq!(buffer.take(), b"[ERROR] connection lost\n");
+}
+
+#[test]
+fn test_multiple_targets() {
+    struct Failing;
+    impl LogTarget for Failing {
+        fn write_line(&mut self, _level: Level, _line: &str) -> std::io::Result<()> {
+            Err(std::io::ErrorKind::Other.into())
+        }
+    }
+
+    let (file, console) = (Buffer::new(), Buffer::new());
+    let logger = Logger::new()
+        .level(Level::Debug)
+        .color(false)
+        .target(Failing)
+        .add_target(file.clone(), Level::Trace)
+        .add_target_with_color(console.clone(), Level::Info, ColorMode::Always);
+
+    logger.error("failed");
+    logger.info("started");
+    logger.debug("details");
+    logger.trace("hidden");
+    assert_eq!(
+        String::from_utf8(file.take()).unwrap(),
+        "[ERROR] failed\n[INFO] started\n[DEBUG] details\n"
+    );
+    assert_eq!(
+        String::from_utf8(console.take()).unwrap(),
+        "[ERROR] \x1b[31mfailed\x1b[0m\n[INFO] \x1b[0mstarted\x1b[0m\n"
+    );
+
+    // Setting a target replaces all of them
+    let logger = logger.target(file.clone());
+    logger.info("only file");
+    assert_eq!(file.take(), b"[INFO] only file\n");
+    assert_eq!(console.contents(), b"");
+}
+
+#[test]
+fn test_static_max_level() {
+    let calls = Cell::new(0);
+    let count = || {
+        calls.set(calls.get() + 1);
+        0
+    };
+
+    #[cfg(not(any(
+        feature = "max_level_off",
+        feature = "max_level_error",
+        feature = "max_level_warn",
+        feature = "max_level_info",
+        feature = "max_level_debug",
+        feature = "release_max_level_off",
+        feature = "release_max_level_error",
+        feature = "release_max_level_warn",
+        feature = "release_max_level_info",
+        feature = "release_max_level_debug",
+    )))]
+    assert!(matches!(crate::STATIC_MAX_LEVEL, Level::Trace));
+
+    // Above the static max, the arguments aren't evaluated, whatever the runtime level
+    for level in LEVELS.into_iter().filter(|&x| x > crate::STATIC_MAX_LEVEL) {
+        crate::log!(level, "{}", count());
+    }
+    if crate::STATIC_MAX_LEVEL < Level::Error {
+        crate::error!("{}", count());
+    }
+    if crate::STATIC_MAX_LEVEL < Level::Trace {
+        crate::trace!("{}", count());
+    }
+    assert_eq!(calls.get(), 0);
+
+    // The runtime check still applies, and nothing is ever enabled at `Off`
+    crate::log!(Level::Off, "{}", count());
+    assert_eq!(calls.get(), 0);
+}
+
+#[test]
+fn test_buffered() {
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .level(Level::Trace)
+        .color(false)
+        .target(buffer.clone())
+        .buffered(16, QueuePolicy::Block);
+
+    let mut expected = String::new();
+    for i in 0..1000 {
+        logger.info(format!("line {i}"));
+        expected.push_str(&format!("[INFO] line {i}\n"));
+    }
+    logger.flush().unwrap();
+    assert_eq!(String::from_utf8(buffer.take()).unwrap(), expected);
+
+    // Dropping the logger writes anything still queued
+    logger.warn("last");
+    drop(logger);
+    assert_eq!(buffer.take(), b"[WARN] last\n");
+}
+
+#[test]
+fn test_buffered_drop_policy() {
+    // Tells the test when it starts writing a line, then waits to be let through
+    struct Gated {
+        buffer: Buffer,
+        entered: mpsc::Sender<()>,
+        permits: mpsc::Receiver<()>,
+    }
+    impl LogTarget for Gated {
+        fn write_line(&mut self, level: Level, line: &str) -> std::io::Result<()> {
+            let _ = self.entered.send(());
+            // Everything is let through once the test drops its sender
+            let _ = self.permits.recv();
+            self.buffer.write_line(level, line)
+        }
+    }
+
+    let buffer = Buffer::new();
+    let ((entered, wait), (permit, permits)) = (mpsc::channel(), mpsc::channel());
+    let gated = Gated {
+        buffer: buffer.clone(),
+        entered,
+        permits,
+    };
+    let mut target = BackgroundTarget::new(gated, 1, QueuePolicy::Drop);
+
+    // The thread holds line 0, line 1 fills the queue and the rest are dropped
+    target.write_line(Level::Info, "line 0").unwrap();
+    wait.recv().unwrap();
+    for i in 1..10 {
+        target
+            .write_line(Level::Info, &format!("line {i}"))
+            .unwrap();
+    }
+    assert_eq!(target.dropped(), 8);
+
+    // With the thread on line 1 there is exactly one free slot, which goes to the next line rather than the report
+    permit.send(()).unwrap();
+    wait.recv().unwrap();
+    target.write_line(Level::Info, "line 10").unwrap();
+    assert_eq!(target.dropped(), 8);
+
+    drop(permit);
+    target.flush().unwrap();
+    assert_eq!(target.dropped(), 0);
+    assert_eq!(
+        String::from_utf8(buffer.take()).unwrap(),
+        "line 0\nline 1\nline 10\n8 log lines dropped as the queue was full\n"
+    );
+}
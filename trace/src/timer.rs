@@ -0,0 +1,80 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use crate::{Level, Logger};
+
+thread_local! {
+    // How many timers are running on this thread, used to indent nested timers
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Times a scope, logging how long it took when dropped or [finished](TimeGuard::finish).
+/// Created with [`Logger::time`].
+/// Ex. `[DEBUG] load config took 12.3ms`
+///
+/// Timers started while another is running on the same thread are nested,
+/// and their lines are indented two spaces per level.
+#[must_use = "the time is logged when the guard is dropped, so dropping it immediately times nothing"]
+pub struct TimeGuard<'a> {
+    logger: &'a Logger,
+    label: String,
+    level: Level,
+    start: Instant,
+    depth: usize,
+    done: bool,
+}
+
+impl<'a> TimeGuard<'a> {
+    pub(crate) fn new(logger: &'a Logger, label: &str) -> Self {
+        let depth = DEPTH.with(|x| x.replace(x.get() + 1));
+        Self {
+            logger,
+            label: label.to_owned(),
+            level: Level::Debug,
+            start: Instant::now(),
+            depth,
+            done: false,
+        }
+    }
+
+    /// Sets the level the time is logged at, [`Level::Debug`] by default.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Gets how long it has been since the timer started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Logs the time now, rather than when the guard is dropped.
+    pub fn finish(mut self) {
+        self.log();
+    }
+
+    fn log(&mut self) {
+        if self.done {
+            return;
+        }
+
+        self.done = true;
+        DEPTH.with(|x| x.set(x.get().saturating_sub(1)));
+        self.logger.log_lazy(self.level, || {
+            format!(
+                "{}{} took {:.1}ms",
+                "  ".repeat(self.depth),
+                self.label,
+                self.elapsed().as_secs_f64() * 1000.0
+            )
+        });
+    }
+}
+
+impl Drop for TimeGuard<'_> {
+    fn drop(&mut self) {
+        self.log();
+    }
+}
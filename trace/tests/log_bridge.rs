@@ -0,0 +1,31 @@
+//! Installs the `log` bridge, which is global for the whole process, so it gets its own test binary.
+
+use trace::{Buffer, Logger};
+
+#[test]
+fn test_log_bridge() {
+    let buffer = Buffer::new();
+    let logger = Logger::new()
+        .color(false)
+        .target(buffer.clone())
+        .filters("info,noisy=error,deps::db=trace");
+    trace::install_log_bridge(logger).unwrap();
+    assert_eq!(log::max_level(), log::LevelFilter::Trace);
+    assert!(trace::install_log_bridge(Logger::new()).is_err());
+
+    log::info!("started on port {}", 8080);
+    log::debug!("hidden");
+    log::warn!(target: "noisy", "hidden");
+    log::error!(target: "noisy::inner", "shown");
+    log::trace!(target: "deps::db::pool", "query");
+    assert_eq!(
+        String::from_utf8(buffer.take()).unwrap(),
+        "[INFO] started on port 8080\n[ERROR] shown\n[TRACE] query\n"
+    );
+
+    log::set_max_level(log::LevelFilter::Warn);
+    log::info!("hidden");
+    log::trace!(target: "deps::db", "hidden");
+    log::warn!("shown");
+    assert_eq!(buffer.take(), b"[WARN] shown\n");
+}